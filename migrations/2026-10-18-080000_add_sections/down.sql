@@ -0,0 +1,5 @@
+ALTER TABLE instructions
+DROP COLUMN section;
+
+ALTER TABLE ingredients
+DROP COLUMN section;
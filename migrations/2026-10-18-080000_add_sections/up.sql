@@ -0,0 +1,5 @@
+ALTER TABLE instructions
+ADD COLUMN section VARCHAR;
+
+ALTER TABLE ingredients
+ADD COLUMN section VARCHAR;
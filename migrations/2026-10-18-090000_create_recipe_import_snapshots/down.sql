@@ -0,0 +1 @@
+DROP TABLE recipe_import_snapshots;
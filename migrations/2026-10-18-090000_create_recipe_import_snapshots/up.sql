@@ -0,0 +1,8 @@
+CREATE TABLE recipe_import_snapshots (
+  recipe_id VARCHAR PRIMARY KEY REFERENCES recipes(id),
+  snapshot TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('recipe_import_snapshots');
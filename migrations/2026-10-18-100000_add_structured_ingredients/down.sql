@@ -0,0 +1,6 @@
+ALTER TABLE ingredients
+DROP COLUMN quantity,
+DROP COLUMN max_quantity,
+DROP COLUMN unit,
+DROP COLUMN name,
+DROP COLUMN note;
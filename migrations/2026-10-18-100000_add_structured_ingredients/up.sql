@@ -0,0 +1,6 @@
+ALTER TABLE ingredients
+ADD COLUMN quantity DOUBLE PRECISION,
+ADD COLUMN max_quantity DOUBLE PRECISION,
+ADD COLUMN unit VARCHAR,
+ADD COLUMN name VARCHAR,
+ADD COLUMN note VARCHAR;
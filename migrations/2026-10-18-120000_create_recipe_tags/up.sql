@@ -0,0 +1,7 @@
+CREATE TABLE recipe_tags (
+  recipe_id VARCHAR NOT NULL REFERENCES recipes(id),
+  tag VARCHAR NOT NULL,
+  PRIMARY KEY (recipe_id, tag)
+);
+
+CREATE INDEX recipe_tags_tag_idx ON recipe_tags (tag);
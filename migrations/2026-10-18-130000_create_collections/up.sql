@@ -0,0 +1,17 @@
+CREATE TABLE collections (
+  id VARCHAR PRIMARY KEY,
+  user_id VARCHAR NOT NULL REFERENCES users(id),
+  name VARCHAR NOT NULL,
+  description VARCHAR,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('collections');
+
+CREATE TABLE collection_recipes (
+  collection_id VARCHAR NOT NULL REFERENCES collections(id),
+  recipe_id VARCHAR NOT NULL REFERENCES recipes(id),
+  position integer NOT NULL,
+  PRIMARY KEY (collection_id, recipe_id)
+);
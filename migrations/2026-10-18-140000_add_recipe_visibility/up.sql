@@ -0,0 +1,2 @@
+ALTER TABLE recipes
+ADD COLUMN visibility VARCHAR NOT NULL DEFAULT 'private';
@@ -0,0 +1,8 @@
+CREATE TABLE recipe_shares (
+  recipe_id VARCHAR NOT NULL REFERENCES recipes(id),
+  user_id VARCHAR NOT NULL REFERENCES users(id),
+  role VARCHAR NOT NULL,
+  PRIMARY KEY (recipe_id, user_id)
+);
+
+CREATE INDEX recipe_shares_user_id_idx ON recipe_shares (user_id);
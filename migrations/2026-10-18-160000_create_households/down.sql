@@ -0,0 +1,6 @@
+ALTER TABLE recipes
+DROP COLUMN household_id;
+ALTER TABLE users
+DROP COLUMN household_id;
+DROP TABLE household_invitations;
+DROP TABLE households;
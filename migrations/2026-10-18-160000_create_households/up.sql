@@ -0,0 +1,22 @@
+CREATE TABLE households (
+  id VARCHAR PRIMARY KEY,
+  name VARCHAR NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('households');
+
+CREATE TABLE household_invitations (
+  household_id VARCHAR NOT NULL REFERENCES households(id),
+  email VARCHAR NOT NULL,
+  PRIMARY KEY (household_id, email)
+);
+
+CREATE INDEX household_invitations_email_idx ON household_invitations (email);
+
+ALTER TABLE users
+ADD COLUMN household_id VARCHAR REFERENCES households(id);
+
+ALTER TABLE recipes
+ADD COLUMN household_id VARCHAR REFERENCES households(id);
@@ -0,0 +1,5 @@
+DROP FUNCTION refresh_recipe_search_vector(VARCHAR);
+DROP FUNCTION recipe_search_document(TEXT, "char");
+DROP INDEX recipes_search_vector_idx;
+ALTER TABLE recipes
+DROP COLUMN search_vector;
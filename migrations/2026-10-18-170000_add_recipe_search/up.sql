@@ -0,0 +1,24 @@
+ALTER TABLE recipes
+ADD COLUMN search_vector TSVECTOR NOT NULL DEFAULT '';
+
+CREATE INDEX recipes_search_vector_idx ON recipes USING GIN (search_vector);
+
+-- Recipes are written in French or in English, so each text is stemmed in
+-- both languages.
+CREATE FUNCTION recipe_search_document(document TEXT, weight "char") RETURNS TSVECTOR AS $$
+  SELECT setweight(to_tsvector('french', coalesce(document, '')), weight)
+    || setweight(to_tsvector('english', coalesce(document, '')), weight)
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE FUNCTION refresh_recipe_search_vector(recipe VARCHAR) RETURNS VOID AS $$
+  UPDATE recipes SET search_vector =
+    recipe_search_document(title, 'A')
+    || recipe_search_document(description, 'B')
+    || recipe_search_document(
+      (SELECT string_agg(ingredient, ' ') FROM ingredients WHERE recipe_id = recipe), 'C')
+    || recipe_search_document(
+      (SELECT string_agg(instruction, ' ') FROM instructions WHERE recipe_id = recipe), 'D')
+  WHERE id = recipe
+$$ LANGUAGE SQL;
+
+SELECT refresh_recipe_search_vector(id) FROM recipes;
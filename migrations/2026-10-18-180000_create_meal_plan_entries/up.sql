@@ -0,0 +1,14 @@
+CREATE TABLE meal_plan_entries (
+  id VARCHAR PRIMARY KEY,
+  user_id VARCHAR NOT NULL REFERENCES users(id),
+  recipe_id VARCHAR NOT NULL REFERENCES recipes(id),
+  date DATE NOT NULL,
+  slot VARCHAR NOT NULL,
+  servings INTEGER,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('meal_plan_entries');
+
+CREATE INDEX meal_plan_entries_user_id_date_idx ON meal_plan_entries (user_id, date);
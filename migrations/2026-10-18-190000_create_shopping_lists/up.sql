@@ -0,0 +1,23 @@
+CREATE TABLE shopping_lists (
+  id VARCHAR PRIMARY KEY,
+  user_id VARCHAR NOT NULL REFERENCES users(id),
+  name VARCHAR NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('shopping_lists');
+
+CREATE TABLE shopping_list_items (
+  id VARCHAR PRIMARY KEY,
+  shopping_list_id VARCHAR NOT NULL REFERENCES shopping_lists(id),
+  position INTEGER NOT NULL,
+  text VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  quantity DOUBLE PRECISION,
+  unit VARCHAR,
+  aisle VARCHAR NOT NULL,
+  checked BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX shopping_list_items_shopping_list_id_idx ON shopping_list_items (shopping_list_id);
@@ -0,0 +1,14 @@
+CREATE TABLE pantry_items (
+  id VARCHAR PRIMARY KEY,
+  user_id VARCHAR NOT NULL REFERENCES users(id),
+  name VARCHAR NOT NULL,
+  quantity DOUBLE PRECISION,
+  unit VARCHAR,
+  expires_on DATE,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('pantry_items');
+
+CREATE INDEX pantry_items_user_id_idx ON pantry_items (user_id);
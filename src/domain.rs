@@ -1,2 +1,6 @@
+pub mod collections;
+pub mod meal_plans;
+pub mod pantries;
 pub mod recipes;
+pub mod shopping_lists;
 pub mod users;
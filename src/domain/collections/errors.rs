@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CollectionError {
+    #[error("Collection is not yours")]
+    CollectionDoNotbelongToUser,
+    #[error("Collection not found")]
+    CollectionNotFound,
+    #[error("Collection must have a name")]
+    CollectionWithoutName,
+    #[error("Recipe is not in the collection")]
+    RecipeNotInCollection,
+    #[error("unknown error")]
+    Unknown,
+}
+
+impl CollectionError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            CollectionError::CollectionDoNotbelongToUser => "COLLECTION_NOT_YOURS",
+            CollectionError::CollectionNotFound => "COLLECTION_NOT_FOUND",
+            CollectionError::CollectionWithoutName => "MISSING_NAME",
+            CollectionError::RecipeNotInCollection => "RECIPE_NOT_IN_COLLECTION",
+            CollectionError::Unknown => "UNKNOWN",
+        }
+    }
+}
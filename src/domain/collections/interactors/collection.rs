@@ -0,0 +1,113 @@
+use crate::domain::collections::errors::CollectionError;
+use crate::domain::collections::models::collection::Collection;
+use crate::domain::collections::ports::dao::{CollectionDao, NewCollection};
+use crate::domain::recipes::errors::RecipeError;
+use crate::domain::recipes::interactors::recipe::can_read_recipe;
+use crate::domain::recipes::ports::dao::RecipeDao;
+use crate::domain::users::ports::dao::UserDao;
+
+use std::error::Error;
+use uuid::Uuid;
+
+pub struct CollectionInteractor {
+    pub collection_dao: Box<dyn CollectionDao>,
+    pub recipe_dao: Box<dyn RecipeDao>,
+    pub user_dao: Box<dyn UserDao>,
+}
+
+impl CollectionInteractor {
+    pub fn get_my_collections(&self, user_id: String) -> Result<Vec<Collection>, Box<dyn Error>> {
+        self.collection_dao.get_my_collections(&user_id)
+    }
+
+    pub fn get_collection(
+        &self,
+        id: String,
+        user_id: String,
+    ) -> Result<Collection, Box<dyn Error>> {
+        self.get_own_collection(&id, &user_id)
+    }
+
+    pub fn add_collection(
+        &self,
+        user_id: String,
+        name: String,
+        description: Option<String>,
+    ) -> Result<Collection, Box<dyn Error>> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Box::new(CollectionError::CollectionWithoutName));
+        }
+        self.collection_dao.add_collection(NewCollection {
+            id: Uuid::new_v4().to_hyphenated().to_string().as_str(),
+            user_id: &user_id,
+            name,
+            description: description.as_deref(),
+        })
+    }
+
+    pub fn update_collection(
+        &self,
+        id: String,
+        user_id: String,
+        name: String,
+        description: Option<String>,
+    ) -> Result<Collection, Box<dyn Error>> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Box::new(CollectionError::CollectionWithoutName));
+        }
+        self.get_own_collection(&id, &user_id)?;
+        self.collection_dao
+            .update_collection(&id, name, description.as_deref())
+    }
+
+    pub fn delete_collection(&self, id: String, user_id: String) -> Result<(), Box<dyn Error>> {
+        self.get_own_collection(&id, &user_id)?;
+        self.collection_dao.delete_collection(&id)
+    }
+
+    /// Adds a recipe the user can read at the position, or at the end. Moves it
+    /// when it is already in the collection.
+    pub fn add_recipe(
+        &self,
+        id: String,
+        user_id: String,
+        recipe_id: String,
+        position: Option<usize>,
+    ) -> Result<Collection, Box<dyn Error>> {
+        let collection = self.get_own_collection(&id, &user_id)?;
+        let recipe = self.recipe_dao.get_recipe(recipe_id.clone())?;
+        if !can_read_recipe(
+            self.recipe_dao.as_ref(),
+            self.user_dao.as_ref(),
+            &recipe,
+            Some(&user_id),
+        )? {
+            return Err(Box::new(RecipeError::RecipeNotFound));
+        }
+        self.collection_dao
+            .set_recipes(&id, &collection.get_recipe_ids_with(&recipe_id, position))
+    }
+
+    pub fn remove_recipe(
+        &self,
+        id: String,
+        user_id: String,
+        recipe_id: String,
+    ) -> Result<Collection, Box<dyn Error>> {
+        let collection = self.get_own_collection(&id, &user_id)?;
+        let recipe_ids = collection
+            .get_recipe_ids_without(&recipe_id)
+            .ok_or(CollectionError::RecipeNotInCollection)?;
+        self.collection_dao.set_recipes(&id, &recipe_ids)
+    }
+
+    fn get_own_collection(&self, id: &str, user_id: &str) -> Result<Collection, Box<dyn Error>> {
+        let collection = self.collection_dao.get_collection(id)?;
+        if collection.user_id != user_id {
+            return Err(Box::new(CollectionError::CollectionDoNotbelongToUser));
+        }
+        Ok(collection)
+    }
+}
@@ -0,0 +1,99 @@
+use uuid::Uuid;
+
+#[derive(PartialEq, Debug)]
+pub struct Collection {
+    pub id: Uuid,
+    pub user_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Recipes of the collection, in the order chosen by its owner.
+    pub recipe_ids: Vec<String>,
+}
+
+impl Collection {
+    /// Returns the recipes once the recipe is placed at the position, or at
+    /// the end. A recipe already in the collection is moved.
+    pub fn get_recipe_ids_with(&self, recipe_id: &str, position: Option<usize>) -> Vec<String> {
+        let mut recipe_ids: Vec<String> = self
+            .recipe_ids
+            .iter()
+            .filter(|id| *id != recipe_id)
+            .cloned()
+            .collect();
+        let position = position.unwrap_or(recipe_ids.len()).min(recipe_ids.len());
+        recipe_ids.insert(position, String::from(recipe_id));
+        recipe_ids
+    }
+
+    /// Returns the recipes once the recipe is removed, or None when it is not
+    /// in the collection.
+    pub fn get_recipe_ids_without(&self, recipe_id: &str) -> Option<Vec<String>> {
+        if !self.recipe_ids.iter().any(|id| id == recipe_id) {
+            return None;
+        }
+        Some(
+            self.recipe_ids
+                .iter()
+                .filter(|id| *id != recipe_id)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection(recipe_ids: &[&str]) -> Collection {
+        Collection {
+            id: Uuid::new_v4(),
+            user_id: String::from("user"),
+            name: String::from("Weeknight"),
+            description: None,
+            recipe_ids: recipe_ids.iter().map(|id| String::from(*id)).collect(),
+        }
+    }
+
+    #[test]
+    fn adding_recipe() {
+        // given
+        let collection = collection(&["a", "b"]);
+
+        // when
+        let at_the_end = collection.get_recipe_ids_with("c", None);
+        let first = collection.get_recipe_ids_with("c", Some(0));
+        let too_far = collection.get_recipe_ids_with("c", Some(10));
+
+        // then
+        assert_eq!(at_the_end, vec!["a", "b", "c"]);
+        assert_eq!(first, vec!["c", "a", "b"]);
+        assert_eq!(too_far, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn moving_recipe() {
+        // given
+        let collection = collection(&["a", "b", "c"]);
+
+        // when
+        let recipe_ids = collection.get_recipe_ids_with("c", Some(1));
+
+        // then
+        assert_eq!(recipe_ids, vec!["a", "c", "b"]);
+    }
+
+    #[test]
+    fn removing_recipe() {
+        // given
+        let collection = collection(&["a", "b"]);
+
+        // when
+        let recipe_ids = collection.get_recipe_ids_without("a");
+        let missing = collection.get_recipe_ids_without("c");
+
+        // then
+        assert_eq!(recipe_ids, Some(vec![String::from("b")]));
+        assert_eq!(missing, None);
+    }
+}
@@ -0,0 +1,25 @@
+use crate::domain::collections::models::collection::Collection;
+use std::error::Error;
+
+#[derive(PartialEq, Debug)]
+pub struct NewCollection<'a> {
+    pub id: &'a str,
+    pub user_id: &'a str,
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+}
+
+pub trait CollectionDao {
+    fn get_my_collections(&self, user_id: &str) -> Result<Vec<Collection>, Box<dyn Error>>;
+    fn get_collection(&self, id: &str) -> Result<Collection, Box<dyn Error>>;
+    fn add_collection(&self, new_collection: NewCollection) -> Result<Collection, Box<dyn Error>>;
+    fn update_collection(
+        &self,
+        id: &str,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<Collection, Box<dyn Error>>;
+    fn delete_collection(&self, id: &str) -> Result<(), Box<dyn Error>>;
+    /// Replaces the recipes of the collection, keeping the given order.
+    fn set_recipes(&self, id: &str, recipe_ids: &[String]) -> Result<Collection, Box<dyn Error>>;
+}
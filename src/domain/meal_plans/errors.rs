@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MealPlanError {
+    #[error("Meal plan entry is not yours")]
+    MealPlanEntryDoNotbelongToUser,
+    #[error("Meal plan entry not found")]
+    MealPlanEntryNotFound,
+    #[error("Servings must be a positive number")]
+    MealPlanServingsInvalid,
+    #[error("Date range must end after it starts and last at most {0} days")]
+    MealPlanRangeInvalid(i64),
+    #[error("unknown error")]
+    Unknown,
+}
+
+impl MealPlanError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            MealPlanError::MealPlanEntryDoNotbelongToUser => "ENTRY_NOT_YOURS",
+            MealPlanError::MealPlanEntryNotFound => "ENTRY_NOT_FOUND",
+            MealPlanError::MealPlanServingsInvalid => "INVALID_SERVINGS",
+            MealPlanError::MealPlanRangeInvalid(_) => "INVALID_RANGE",
+            MealPlanError::Unknown => "UNKNOWN",
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::domain::meal_plans::errors::MealPlanError;
+use crate::domain::meal_plans::models::calendar::MealEvent;
+use crate::domain::meal_plans::models::meal_plan::{MealPlan, MealPlanEntry, MealSlot};
+use crate::domain::meal_plans::ports::dao::{MealPlanDao, NewMealPlanEntry};
+use crate::domain::recipes::errors::RecipeError;
+use crate::domain::recipes::interactors::recipe::{can_read_recipe, get_readable_recipes};
+use crate::domain::recipes::ports::dao::RecipeDao;
+use crate::domain::users::ports::dao::UserDao;
+
+use chrono::{Duration, NaiveDate};
+use std::error::Error;
+use uuid::Uuid;
+
+const MAX_RANGE_IN_DAYS: i64 = 62;
+/// Days before and after today of the meals in the calendar feed.
+const CALENDAR_PAST_DAYS: i64 = 14;
+const CALENDAR_NEXT_DAYS: i64 = 62;
+
+pub struct MealPlanInteractor {
+    pub meal_plan_dao: Box<dyn MealPlanDao>,
+    pub recipe_dao: Box<dyn RecipeDao>,
+    pub user_dao: Box<dyn UserDao>,
+}
+
+impl MealPlanInteractor {
+    /// The meals planned between the two dates, both included.
+    pub fn get_my_meal_plan(
+        &self,
+        user_id: String,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<MealPlan, Box<dyn Error>> {
+        check_meal_plan_range(start, end)?;
+        let entries = self.meal_plan_dao.get_entries(&user_id, start, end)?;
+        Ok(MealPlan::new(start, end, entries))
+    }
+
+    /// Plans a recipe the user can read: theirs, shared with them, in their
+    /// household or public.
+    pub fn add_entry(
+        &self,
+        user_id: String,
+        recipe_id: String,
+        date: NaiveDate,
+        slot: MealSlot,
+        servings: Option<i32>,
+    ) -> Result<MealPlanEntry, Box<dyn Error>> {
+        if servings.unwrap_or(1) <= 0 {
+            return Err(Box::new(MealPlanError::MealPlanServingsInvalid));
+        }
+        let recipe = self.recipe_dao.get_recipe(recipe_id.clone())?;
+        if !can_read_recipe(
+            self.recipe_dao.as_ref(),
+            self.user_dao.as_ref(),
+            &recipe,
+            Some(&user_id),
+        )? {
+            return Err(Box::new(RecipeError::RecipeNotFound));
+        }
+        self.meal_plan_dao.add_entry(NewMealPlanEntry {
+            id: Uuid::new_v4().to_hyphenated().to_string().as_str(),
+            user_id: &user_id,
+            date,
+            slot,
+            recipe_id: &recipe_id,
+            servings,
+        })
+    }
+
+    pub fn move_entry(
+        &self,
+        id: String,
+        user_id: String,
+        date: NaiveDate,
+        slot: MealSlot,
+    ) -> Result<MealPlanEntry, Box<dyn Error>> {
+        self.get_own_entry(&id, &user_id)?;
+        self.meal_plan_dao.move_entry(&id, date, slot)
+    }
+
+    pub fn remove_entry(&self, id: String, user_id: String) -> Result<(), Box<dyn Error>> {
+        self.get_own_entry(&id, &user_id)?;
+        self.meal_plan_dao.delete_entry(&id)
+    }
+
+    /// The meals planned around today by the user having this calendar token,
+    /// without the recipes the user can no longer read.
+    pub fn get_calendar(
+        &self,
+        calendar_token: &str,
+        today: NaiveDate,
+    ) -> Result<Vec<MealEvent>, Box<dyn Error>> {
+        let user = self.user_dao.get_user_by_calendar_token(calendar_token)?;
+        let user_id = user.id.to_hyphenated().to_string();
+        let entries = self.meal_plan_dao.get_entries(
+            &user_id,
+            today - Duration::days(CALENDAR_PAST_DAYS),
+            today + Duration::days(CALENDAR_NEXT_DAYS),
+        )?;
+        let recipe_ids: Vec<String> = entries.iter().map(|e| e.recipe_id.clone()).collect();
+        let recipes = get_readable_recipes(
+            self.recipe_dao.as_ref(),
+            self.user_dao.as_ref(),
+            self.recipe_dao.get_recipes(&recipe_ids)?,
+            Some(&user_id),
+        )?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| {
+                let recipe = recipes
+                    .iter()
+                    .find(|r| r.id.to_hyphenated().to_string() == entry.recipe_id)?
+                    .clone();
+                Some(MealEvent { entry, recipe })
+            })
+            .collect())
+    }
+
+    fn get_own_entry(&self, id: &str, user_id: &str) -> Result<MealPlanEntry, Box<dyn Error>> {
+        let entry = self.meal_plan_dao.get_entry(id)?;
+        if entry.user_id != user_id {
+            return Err(Box::new(MealPlanError::MealPlanEntryDoNotbelongToUser));
+        }
+        Ok(entry)
+    }
+}
+
+/// Checks the range spans at least one day and at most `MAX_RANGE_IN_DAYS`,
+/// both dates included.
+pub fn check_meal_plan_range(start: NaiveDate, end: NaiveDate) -> Result<(), Box<dyn Error>> {
+    let days = end.signed_duration_since(start).num_days() + 1;
+    if !(1..=MAX_RANGE_IN_DAYS).contains(&days) {
+        return Err(Box::new(MealPlanError::MealPlanRangeInvalid(
+            MAX_RANGE_IN_DAYS,
+        )));
+    }
+    Ok(())
+}
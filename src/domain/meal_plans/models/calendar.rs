@@ -0,0 +1,182 @@
+use crate::domain::meal_plans::models::meal_plan::{MealPlanEntry, MealSlot};
+use crate::domain::recipes::models::recipe::Recipe;
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+
+/// Time spent cooking the recipes without prep nor cook time.
+const DEFAULT_DURATION_IN_MINUTE: i32 = 30;
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+/// Longest line allowed by RFC 5545, in bytes, without the line break.
+const MAX_LINE_LENGTH: usize = 75;
+
+/// A planned meal with its recipe.
+#[derive(PartialEq, Debug)]
+pub struct MealEvent {
+    pub entry: MealPlanEntry,
+    pub recipe: Recipe,
+}
+
+/// When the meal is served, in the local time of the user.
+fn get_meal_time(slot: MealSlot) -> NaiveTime {
+    match slot {
+        MealSlot::Breakfast => NaiveTime::from_hms_opt(8, 0, 0),
+        MealSlot::Lunch => NaiveTime::from_hms_opt(12, 30, 0),
+        MealSlot::Snack => NaiveTime::from_hms_opt(16, 30, 0),
+        MealSlot::Dinner => NaiveTime::from_hms_opt(19, 30, 0),
+    }
+    .unwrap()
+}
+
+/// The meals as an iCalendar feed. An event lasts the prep and cook time of
+/// its recipe and ends when the meal is served, so that it tells when to
+/// start cooking. `recipes_url` is followed by the recipe id to link back to
+/// the recipe.
+pub fn get_icalendar(events: &[MealEvent], recipes_url: &str, stamp: NaiveDateTime) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//Rustipe//Meal plan//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+        String::from("X-WR-CALNAME:Meal plan"),
+    ];
+    for event in events {
+        lines.extend(get_event_lines(event, recipes_url, stamp));
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+fn get_event_lines(event: &MealEvent, recipes_url: &str, stamp: NaiveDateTime) -> Vec<String> {
+    let MealEvent { entry, recipe } = event;
+    let recipe_time =
+        recipe.prep_time_in_minute.unwrap_or(0) + recipe.cook_time_in_minute.unwrap_or(0);
+    let duration = if recipe_time > 0 {
+        recipe_time
+    } else {
+        DEFAULT_DURATION_IN_MINUTE
+    };
+    let end = entry.date.and_time(get_meal_time(entry.slot));
+    let start = end - Duration::minutes(i64::from(duration));
+    let url = format!("{}{}", recipes_url, recipe.id.to_hyphenated());
+
+    let mut description = vec![];
+    if let Some(prep_time) = recipe.prep_time_in_minute {
+        description.push(format!("Prep: {} min", prep_time));
+    }
+    if let Some(cook_time) = recipe.cook_time_in_minute {
+        description.push(format!("Cook: {} min", cook_time));
+    }
+    if let Some(servings) = entry.servings {
+        description.push(format!("Servings: {}", servings));
+    }
+    description.push(url.clone());
+
+    vec![
+        String::from("BEGIN:VEVENT"),
+        format!("UID:{}@rustipe", entry.id.to_hyphenated()),
+        format!("DTSTAMP:{}Z", stamp.format(DATE_TIME_FORMAT)),
+        format!("DTSTART:{}", start.format(DATE_TIME_FORMAT)),
+        format!("DURATION:PT{}M", duration),
+        format!("SUMMARY:{}", escape_text(&recipe.title)),
+        format!("DESCRIPTION:{}", escape_text(&description.join("\n"))),
+        format!("URL:{}", url),
+        String::from("END:VEVENT"),
+    ]
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Splits the line in lines of at most 75 bytes, the next ones starting with
+/// a space, without cutting a character.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::recipes::models::recipe::RecipeBuilder;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    fn event(title: &str, prep_time: Option<i32>, cook_time: Option<i32>) -> MealEvent {
+        MealEvent {
+            entry: MealPlanEntry {
+                id: Uuid::parse_str("0a2a5bc2-0a3c-4a7e-a8ce-6e5c1f1d6a4b").unwrap(),
+                user_id: String::from("user"),
+                date: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+                slot: MealSlot::Dinner,
+                recipe_id: String::from("b8f2e2d4-5a1e-4c4e-9f7a-2f6d2c1e9a10"),
+                servings: Some(4),
+            },
+            recipe: RecipeBuilder::new(title)
+                .id("b8f2e2d4-5a1e-4c4e-9f7a-2f6d2c1e9a10")
+                .prep_time_in_minute(prep_time)
+                .cook_time_in_minute(cook_time)
+                .build(),
+        }
+    }
+
+    #[test]
+    fn meal_events_end_when_served() {
+        // given
+        let events = [event("Soup, leeks; potatoes", Some(15), Some(30))];
+        let stamp = NaiveDate::from_ymd_opt(2026, 10, 18)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+
+        // when
+        let calendar = get_icalendar(&events, "https://rustipe.com/recipes/", stamp);
+
+        // then
+        let lines: Vec<&str> = calendar.split("\r\n").collect();
+        assert_eq!(
+            lines[5..15],
+            [
+                "BEGIN:VEVENT",
+                "UID:0a2a5bc2-0a3c-4a7e-a8ce-6e5c1f1d6a4b@rustipe",
+                "DTSTAMP:20261018T090000Z",
+                "DTSTART:20261019T184500",
+                "DURATION:PT45M",
+                "SUMMARY:Soup\\, leeks\\; potatoes",
+                "DESCRIPTION:Prep: 15 min\\nCook: 30 min\\nServings: 4\\nhttps://rustipe.com/re",
+                " cipes/b8f2e2d4-5a1e-4c4e-9f7a-2f6d2c1e9a10",
+                "URL:https://rustipe.com/recipes/b8f2e2d4-5a1e-4c4e-9f7a-2f6d2c1e9a10",
+                "END:VEVENT",
+            ]
+        );
+        assert_eq!(lines[15..], ["END:VCALENDAR", ""]);
+    }
+
+    #[test]
+    fn folding_long_lines_without_cutting_characters() {
+        // given
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+
+        // when
+        let folded = fold_line(&line);
+
+        // then
+        let lines: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[..2].iter().all(|l| l.len() <= MAX_LINE_LENGTH));
+        assert_eq!(lines[0].len(), 74);
+        assert_eq!(folded.replace("\r\n ", ""), format!("{}\r\n", line));
+    }
+}
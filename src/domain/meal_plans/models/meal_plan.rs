@@ -0,0 +1,102 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+/// When in the day a meal is planned, in the order of the day.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum MealSlot {
+    Breakfast,
+    Lunch,
+    Snack,
+    Dinner,
+}
+
+impl MealSlot {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "breakfast",
+            MealSlot::Lunch => "lunch",
+            MealSlot::Snack => "snack",
+            MealSlot::Dinner => "dinner",
+        }
+    }
+
+    pub fn parse(slot: &str) -> Option<MealSlot> {
+        match slot {
+            "breakfast" => Some(MealSlot::Breakfast),
+            "lunch" => Some(MealSlot::Lunch),
+            "snack" => Some(MealSlot::Snack),
+            "dinner" => Some(MealSlot::Dinner),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct MealPlanEntry {
+    pub id: Uuid,
+    pub user_id: String,
+    pub date: NaiveDate,
+    pub slot: MealSlot,
+    pub recipe_id: String,
+    /// Servings to cook, the yield of the recipe when None.
+    pub servings: Option<i32>,
+}
+
+/// The meals planned by a user between two dates, both included.
+#[derive(PartialEq, Debug)]
+pub struct MealPlan {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Entries by date then by slot.
+    pub entries: Vec<MealPlanEntry>,
+}
+
+impl MealPlan {
+    pub fn new(start: NaiveDate, end: NaiveDate, mut entries: Vec<MealPlanEntry>) -> MealPlan {
+        entries.sort_by(|a, b| a.date.cmp(&b.date).then(a.slot.cmp(&b.slot)));
+        MealPlan {
+            start,
+            end,
+            entries,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(date: NaiveDate, slot: MealSlot, recipe_id: &str) -> MealPlanEntry {
+        MealPlanEntry {
+            id: Uuid::new_v4(),
+            user_id: String::from("user"),
+            date,
+            slot,
+            recipe_id: String::from(recipe_id),
+            servings: None,
+        }
+    }
+
+    #[test]
+    fn ordering_entries_by_date_then_slot() {
+        // given
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let tuesday = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+        let entries = vec![
+            entry(tuesday, MealSlot::Breakfast, "porridge"),
+            entry(monday, MealSlot::Dinner, "soup"),
+            entry(monday, MealSlot::Lunch, "salad"),
+        ];
+
+        // when
+        let meal_plan = MealPlan::new(monday, tuesday, entries);
+
+        // then
+        let recipe_ids: Vec<&str> = meal_plan
+            .entries
+            .iter()
+            .map(|e| e.recipe_id.as_str())
+            .collect();
+        assert_eq!(recipe_ids, vec!["salad", "soup", "porridge"]);
+    }
+}
@@ -0,0 +1,32 @@
+use crate::domain::meal_plans::models::meal_plan::{MealPlanEntry, MealSlot};
+use chrono::NaiveDate;
+use std::error::Error;
+
+#[derive(PartialEq, Debug)]
+pub struct NewMealPlanEntry<'a> {
+    pub id: &'a str,
+    pub user_id: &'a str,
+    pub date: NaiveDate,
+    pub slot: MealSlot,
+    pub recipe_id: &'a str,
+    pub servings: Option<i32>,
+}
+
+pub trait MealPlanDao {
+    /// Entries of the user between the two dates, both included.
+    fn get_entries(
+        &self,
+        user_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<MealPlanEntry>, Box<dyn Error>>;
+    fn get_entry(&self, id: &str) -> Result<MealPlanEntry, Box<dyn Error>>;
+    fn add_entry(&self, new_entry: NewMealPlanEntry) -> Result<MealPlanEntry, Box<dyn Error>>;
+    fn move_entry(
+        &self,
+        id: &str,
+        date: NaiveDate,
+        slot: MealSlot,
+    ) -> Result<MealPlanEntry, Box<dyn Error>>;
+    fn delete_entry(&self, id: &str) -> Result<(), Box<dyn Error>>;
+}
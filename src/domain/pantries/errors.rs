@@ -0,0 +1,30 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PantryError {
+    #[error("Pantry item is not yours")]
+    PantryItemDoNotbelongToUser,
+    #[error("Pantry item not found")]
+    PantryItemNotFound,
+    #[error("Pantry item must have a name")]
+    PantryItemWithoutName,
+    #[error("Quantity must be a positive number")]
+    PantryQuantityInvalid,
+    #[error("Days must be between 0 and {0}")]
+    PantryDaysInvalid(i64),
+    #[error("unknown error")]
+    Unknown,
+}
+
+impl PantryError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            PantryError::PantryItemDoNotbelongToUser => "PANTRY_ITEM_NOT_YOURS",
+            PantryError::PantryItemNotFound => "PANTRY_ITEM_NOT_FOUND",
+            PantryError::PantryItemWithoutName => "MISSING_NAME",
+            PantryError::PantryQuantityInvalid => "INVALID_QUANTITY",
+            PantryError::PantryDaysInvalid(_) => "INVALID_DAYS",
+            PantryError::Unknown => "UNKNOWN",
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::domain::pantries::errors::PantryError;
+use crate::domain::pantries::models::pantry_item::{sort_by_expiry, PantryItem};
+use crate::domain::pantries::ports::dao::{NewPantryItem, PantryDao};
+
+use chrono::{Duration, NaiveDate};
+use std::error::Error;
+use uuid::Uuid;
+
+const MAX_EXPIRY_DAYS: i64 = 365;
+
+pub struct PantryInteractor {
+    pub pantry_dao: Box<dyn PantryDao>,
+}
+
+impl PantryInteractor {
+    /// The items of the user, the ones expiring first first.
+    pub fn get_my_pantry(&self, user_id: String) -> Result<Vec<PantryItem>, Box<dyn Error>> {
+        let mut items = self.pantry_dao.get_my_pantry(&user_id)?;
+        sort_by_expiry(&mut items);
+        Ok(items)
+    }
+
+    /// The items not expired yet, which count as on hand.
+    pub fn get_my_stock(
+        &self,
+        user_id: String,
+        today: NaiveDate,
+    ) -> Result<Vec<PantryItem>, Box<dyn Error>> {
+        let items = self.get_my_pantry(user_id)?;
+        Ok(items
+            .into_iter()
+            .filter(|item| !item.is_expired(today))
+            .collect())
+    }
+
+    /// The items expiring within the days, or already expired.
+    pub fn get_expiring_items(
+        &self,
+        user_id: String,
+        today: NaiveDate,
+        days: i64,
+    ) -> Result<Vec<PantryItem>, Box<dyn Error>> {
+        if !(0..=MAX_EXPIRY_DAYS).contains(&days) {
+            return Err(Box::new(PantryError::PantryDaysInvalid(MAX_EXPIRY_DAYS)));
+        }
+        let limit = today + Duration::days(days);
+        let items = self.get_my_pantry(user_id)?;
+        Ok(items
+            .into_iter()
+            .filter(|item| matches!(item.expires_on, Some(expires_on) if expires_on <= limit))
+            .collect())
+    }
+
+    pub fn add_item(
+        &self,
+        user_id: String,
+        name: String,
+        quantity: Option<f64>,
+        unit: Option<String>,
+        expires_on: Option<NaiveDate>,
+    ) -> Result<PantryItem, Box<dyn Error>> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Box::new(PantryError::PantryItemWithoutName));
+        }
+        check_quantity(quantity)?;
+        self.pantry_dao.add_item(NewPantryItem {
+            id: Uuid::new_v4().to_hyphenated().to_string().as_str(),
+            user_id: &user_id,
+            name,
+            quantity,
+            unit: get_unit(&unit),
+            expires_on,
+        })
+    }
+
+    pub fn update_item(
+        &self,
+        id: String,
+        user_id: String,
+        quantity: Option<f64>,
+        unit: Option<String>,
+        expires_on: Option<NaiveDate>,
+    ) -> Result<PantryItem, Box<dyn Error>> {
+        check_quantity(quantity)?;
+        self.get_own_item(&id, &user_id)?;
+        self.pantry_dao
+            .update_item(&id, quantity, get_unit(&unit), expires_on)
+    }
+
+    pub fn remove_item(&self, id: String, user_id: String) -> Result<(), Box<dyn Error>> {
+        self.get_own_item(&id, &user_id)?;
+        self.pantry_dao.delete_item(&id)
+    }
+
+    fn get_own_item(&self, id: &str, user_id: &str) -> Result<PantryItem, Box<dyn Error>> {
+        let item = self.pantry_dao.get_item(id)?;
+        if item.user_id != user_id {
+            return Err(Box::new(PantryError::PantryItemDoNotbelongToUser));
+        }
+        Ok(item)
+    }
+}
+
+fn check_quantity(quantity: Option<f64>) -> Result<(), PantryError> {
+    match quantity {
+        Some(quantity) if quantity <= 0.0 => Err(PantryError::PantryQuantityInvalid),
+        _ => Ok(()),
+    }
+}
+
+fn get_unit(unit: &Option<String>) -> Option<&str> {
+    unit.as_deref()
+        .map(str::trim)
+        .filter(|unit| !unit.is_empty())
+}
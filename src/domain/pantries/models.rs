@@ -0,0 +1 @@
+pub mod pantry_item;
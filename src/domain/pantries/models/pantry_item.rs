@@ -0,0 +1,72 @@
+use chrono::NaiveDate;
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct PantryItem {
+    pub id: Uuid,
+    pub user_id: String,
+    pub name: String,
+    /// None when the amount is not tracked, e.g. for salt.
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl PantryItem {
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        matches!(self.expires_on, Some(expires_on) if expires_on < today)
+    }
+}
+
+/// Sorts the items expiring first first, the ones without expiry date last.
+pub fn sort_by_expiry(items: &mut [PantryItem]) {
+    items.sort_by(|a, b| {
+        match (a.expires_on, b.expires_on) {
+            (Some(a_expires_on), Some(b_expires_on)) => a_expires_on.cmp(&b_expires_on),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, expires_on: Option<NaiveDate>) -> PantryItem {
+        PantryItem {
+            id: Uuid::new_v4(),
+            user_id: String::from("user"),
+            name: String::from(name),
+            quantity: None,
+            unit: None,
+            expires_on,
+        }
+    }
+
+    #[test]
+    fn sorting_items_by_expiry() {
+        // given
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let tomorrow = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let yesterday = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let mut items = vec![
+            item("salt", None),
+            item("milk", Some(tomorrow)),
+            item("cream", Some(yesterday)),
+            item("eggs", Some(tomorrow)),
+        ];
+
+        // when
+        sort_by_expiry(&mut items);
+
+        // then
+        let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["cream", "eggs", "milk", "salt"]);
+        let expired: Vec<bool> = items.iter().map(|i| i.is_expired(today)).collect();
+        assert_eq!(expired, vec![true, false, false, false]);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::domain::pantries::models::pantry_item::PantryItem;
+use chrono::NaiveDate;
+use std::error::Error;
+
+#[derive(PartialEq, Debug)]
+pub struct NewPantryItem<'a> {
+    pub id: &'a str,
+    pub user_id: &'a str,
+    pub name: &'a str,
+    pub quantity: Option<f64>,
+    pub unit: Option<&'a str>,
+    pub expires_on: Option<NaiveDate>,
+}
+
+pub trait PantryDao {
+    fn get_my_pantry(&self, user_id: &str) -> Result<Vec<PantryItem>, Box<dyn Error>>;
+    fn get_item(&self, id: &str) -> Result<PantryItem, Box<dyn Error>>;
+    fn add_item(&self, new_item: NewPantryItem) -> Result<PantryItem, Box<dyn Error>>;
+    fn update_item(
+        &self,
+        id: &str,
+        quantity: Option<f64>,
+        unit: Option<&str>,
+        expires_on: Option<NaiveDate>,
+    ) -> Result<PantryItem, Box<dyn Error>>;
+    fn delete_item(&self, id: &str) -> Result<(), Box<dyn Error>>;
+}
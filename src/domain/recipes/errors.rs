@@ -4,12 +4,66 @@ use thiserror::Error;
 pub enum RecipeError {
     #[error("Recipe is not yours")]
     RecipeDoNotbelongToUser,
+    #[error("Website address is not allowed")]
+    RecipeImportedWebsiteForbidden,
+    #[error("Website unreachable")]
+    RecipeImportedWebsiteUnreachable,
+    #[error("Website answered with status {0}")]
+    RecipeImportedWebsiteBadStatus(u16),
+    #[error("Website page is too large")]
+    RecipeImportedWebsiteTooLarge,
     #[error("Website not supported")]
     RecipeImportedWebsiteNotSupported,
-    #[error("Recipe not imported")]
-    RecipeNotImported,
+    #[error("Recipe data is malformed")]
+    RecipeImportedWithMalformedData,
+    #[error("Recipe has no title")]
+    RecipeImportedWithoutTitle,
+    #[error("Recipe has no ingredients")]
+    RecipeImportedWithoutIngredients,
     #[error("Recipe not found")]
     RecipeNotFound,
+    #[error("Recipe not imported")]
+    RecipeNotImported,
+    #[error("Recipe was not imported from a website")]
+    RecipeWithoutSource,
+    #[error("Recipe yield has no number of servings")]
+    RecipeYieldUnknown,
+    #[error("Servings must be a positive number")]
+    RecipeServingsInvalid,
+    #[error("Tag must not be empty")]
+    RecipeTagInvalid,
+    #[error("Recipe cannot be shared with its owner")]
+    RecipeSharedWithOwner,
+    #[error("Cursor is invalid")]
+    RecipeCursorInvalid,
+    #[error("Recipe is planned in the meal plan of other users")]
+    RecipePlannedByOtherUsers,
     #[error("unknown error")]
     Unknown,
 }
+
+impl RecipeError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            RecipeError::RecipeDoNotbelongToUser => "RECIPE_NOT_YOURS",
+            RecipeError::RecipeImportedWebsiteForbidden => "WEBSITE_FORBIDDEN",
+            RecipeError::RecipeImportedWebsiteUnreachable => "WEBSITE_UNREACHABLE",
+            RecipeError::RecipeImportedWebsiteBadStatus(_) => "WEBSITE_BAD_STATUS",
+            RecipeError::RecipeImportedWebsiteTooLarge => "WEBSITE_TOO_LARGE",
+            RecipeError::RecipeImportedWebsiteNotSupported => "WEBSITE_NOT_SUPPORTED",
+            RecipeError::RecipeImportedWithMalformedData => "MALFORMED_DATA",
+            RecipeError::RecipeImportedWithoutTitle => "MISSING_TITLE",
+            RecipeError::RecipeImportedWithoutIngredients => "MISSING_INGREDIENTS",
+            RecipeError::RecipeNotFound => "RECIPE_NOT_FOUND",
+            RecipeError::RecipeNotImported => "RECIPE_NOT_IMPORTED",
+            RecipeError::RecipeWithoutSource => "RECIPE_WITHOUT_SOURCE",
+            RecipeError::RecipeYieldUnknown => "UNKNOWN_YIELD",
+            RecipeError::RecipeServingsInvalid => "INVALID_SERVINGS",
+            RecipeError::RecipeTagInvalid => "INVALID_TAG",
+            RecipeError::RecipeSharedWithOwner => "SHARED_WITH_OWNER",
+            RecipeError::RecipeCursorInvalid => "INVALID_CURSOR",
+            RecipeError::RecipePlannedByOtherUsers => "RECIPE_PLANNED_BY_OTHERS",
+            RecipeError::Unknown => "UNKNOWN",
+        }
+    }
+}
@@ -1,118 +1,782 @@
 use crate::domain::recipes::errors::RecipeError;
-use crate::domain::recipes::models::recipe::Recipe;
-use crate::domain::recipes::ports::dao::{NewRecipe, RecipeDao};
+use crate::domain::recipes::models::cookable::{get_cookable_recipes, CookableRecipe};
+use crate::domain::recipes::models::facet::{normalize_facet_values, RecipeFacets};
+use crate::domain::recipes::models::page::{decode_cursor, RecipePage, RecipeSort};
+use crate::domain::recipes::models::recipe::{Recipe, RecipeVisibility};
+use crate::domain::recipes::models::resync::{RecipeFieldChange, RecipeResync};
+use crate::domain::recipes::models::search::RecipeSearchResult;
+use crate::domain::recipes::models::share::{RecipeShare, ShareRole};
+use crate::domain::recipes::models::tag::{normalize_tag, normalize_tags};
+use crate::domain::recipes::ports::dao::{NewRecipe, RecipeDao, RecipeFilter};
 use crate::domain::recipes::ports::image_store::ImageStore;
 use crate::domain::recipes::ports::parser::Parser;
+use crate::domain::users::errors::UserError;
 use crate::domain::users::ports::dao::UserDao;
 
 use std::error::Error;
 use std::marker::Send;
 use std::marker::Sync;
+use std::sync::Arc;
 use uuid::Uuid;
 
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+const DEFAULT_PAGE_SIZE: usize = 20;
+const MAX_PAGE_SIZE: usize = 100;
+
 pub struct RecipeInteractor {
     pub recipe_dao: Box<dyn RecipeDao>,
     pub user_dao: Box<dyn UserDao>,
-    pub parser: Box<dyn Parser + Send + Sync>,
+    pub parser: Arc<dyn Parser + Send + Sync>,
     pub image_store: Box<dyn ImageStore>,
 }
 
 impl RecipeInteractor {
     pub fn import_from(&self, url: String, user_id: String) -> Result<Recipe, Box<dyn Error>> {
-        let new_recipe = &self.parser.parse_recipe(url, user_id)?;
-        self.recipe_dao.add_recipe(NewRecipe {
-            id: new_recipe.id.to_hyphenated().to_string().as_str(),
-            user_id: &new_recipe.user_id.as_str(),
-            title: &new_recipe.title.as_str(),
-            description: new_recipe.description.as_deref(),
-            recipe_yield: new_recipe.recipe_yield.as_deref(),
-            category: new_recipe.category.as_deref(),
-            cuisine: new_recipe.cuisine.as_deref(),
-            prep_time_in_minute: (&new_recipe.prep_time_in_minute).as_ref(),
-            cook_time_in_minute: (&new_recipe.cook_time_in_minute).as_ref(),
-            instructions: new_recipe.instructions.iter().map(|s| s.as_str()).collect(),
-            ingredients: new_recipe.ingredients.iter().map(|s| s.as_str()).collect(),
-            imported_from: new_recipe.imported_from.as_deref(),
-            image_url: new_recipe.image_url.as_deref(),
-        })
+        let new_recipe = self.parser.parse_recipe(url, user_id)?;
+        self.insert_recipe(&new_recipe, Some(&new_recipe))
     }
 
-    pub fn add_recipe(&self, new_recipe: Recipe) -> Result<Recipe, Box<dyn Error>> {
-        self.recipe_dao.add_recipe(NewRecipe {
-            id: new_recipe.id.to_hyphenated().to_string().as_str(),
-            user_id: &new_recipe.user_id.as_str(),
-            title: &new_recipe.title.as_str(),
-            description: new_recipe.description.as_deref(),
-            recipe_yield: new_recipe.recipe_yield.as_deref(),
-            category: new_recipe.category.as_deref(),
-            cuisine: new_recipe.cuisine.as_deref(),
-            prep_time_in_minute: (&new_recipe.prep_time_in_minute).as_ref(),
-            cook_time_in_minute: (&new_recipe.cook_time_in_minute).as_ref(),
-            instructions: new_recipe.instructions.iter().map(|s| s.as_str()).collect(),
-            ingredients: new_recipe.ingredients.iter().map(|s| s.as_str()).collect(),
-            imported_from: new_recipe.imported_from.as_deref(),
-            image_url: new_recipe.image_url.as_deref(),
-        })
+    pub fn preview_import(&self, url: String, user_id: String) -> Result<Recipe, Box<dyn Error>> {
+        self.parser.parse_recipe(url, user_id)
+    }
+
+    pub fn import_from_html(
+        &self,
+        html: String,
+        url: Option<String>,
+        user_id: String,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let new_recipe = self.parser.parse_recipe_from_html(html, url, user_id)?;
+        self.insert_recipe(&new_recipe, Some(&new_recipe))
     }
 
-    pub fn update_recipe(&self, new_recipe: Recipe) -> Result<Recipe, Box<dyn Error>> {
-        let recipe = self
-            .recipe_dao
-            .get_recipe(new_recipe.id.to_hyphenated().to_string())?;
-        if recipe.user_id != new_recipe.user_id {
+    /// Parses the recipe website again and compares it to the stored recipe.
+    /// Fields edited by the owner since the last import are never overwritten;
+    /// the other ones take the imported value when `apply` is set.
+    /// Recipes imported before snapshots existed give no way to tell the local
+    /// edits apart, so their fields differing from the website are all kept.
+    pub fn resync_recipe(
+        &self,
+        id: String,
+        user_id: String,
+        apply: bool,
+    ) -> Result<RecipeResync, Box<dyn Error>> {
+        let mut recipe = self.recipe_dao.get_recipe(id.clone())?;
+        if recipe.user_id != user_id {
             return Err(Box::new(RecipeError::RecipeDoNotbelongToUser));
         }
-        self.recipe_dao.update_recipe(new_recipe)
+        let url = recipe
+            .imported_from
+            .clone()
+            .ok_or(RecipeError::RecipeWithoutSource)?;
+        let imported = self.parser.parse_recipe(url, user_id)?;
+        let base = self.recipe_dao.get_import_snapshot(id.clone())?;
+        let base = base.as_ref();
+
+        let mut merge = FieldMerge {
+            apply,
+            changes: vec![],
+        };
+        merge.field(
+            "title",
+            base.map(|b| &b.title),
+            &mut recipe.title,
+            &imported.title,
+            |v| Some(v.clone()),
+        );
+        merge.field(
+            "description",
+            base.map(|b| &b.description),
+            &mut recipe.description,
+            &imported.description,
+            |v| v.clone(),
+        );
+        merge.field(
+            "recipeYield",
+            base.map(|b| &b.recipe_yield),
+            &mut recipe.recipe_yield,
+            &imported.recipe_yield,
+            |v| v.clone(),
+        );
+        merge.field(
+            "category",
+            base.map(|b| &b.category),
+            &mut recipe.category,
+            &imported.category,
+            |v| v.clone(),
+        );
+        merge.field(
+            "cuisine",
+            base.map(|b| &b.cuisine),
+            &mut recipe.cuisine,
+            &imported.cuisine,
+            |v| v.clone(),
+        );
+        merge.field(
+            "prepTimeInMinute",
+            base.map(|b| &b.prep_time_in_minute),
+            &mut recipe.prep_time_in_minute,
+            &imported.prep_time_in_minute,
+            |v| v.map(|m| m.to_string()),
+        );
+        merge.field(
+            "cookTimeInMinute",
+            base.map(|b| &b.cook_time_in_minute),
+            &mut recipe.cook_time_in_minute,
+            &imported.cook_time_in_minute,
+            |v| v.map(|m| m.to_string()),
+        );
+        merge.field(
+            "imageUrl",
+            base.map(|b| &b.image_url),
+            &mut recipe.image_url,
+            &imported.image_url,
+            |v| v.clone(),
+        );
+        if merge.field(
+            "instructions",
+            base.map(|b| &b.instructions),
+            &mut recipe.instructions,
+            &imported.instructions,
+            |v| Some(v.join("\n")),
+        ) {
+            recipe.instruction_sections = imported.instruction_sections.clone();
+        }
+        if merge.field(
+            "ingredients",
+            base.map(|b| &b.ingredients),
+            &mut recipe.ingredients,
+            &imported.ingredients,
+            |v| Some(v.join("\n")),
+        ) {
+            recipe.ingredient_sections = imported.ingredient_sections.clone();
+            recipe.structured_ingredients = imported.structured_ingredients.clone();
+        }
+
+        let changes = merge.changes;
+        if !apply {
+            return Ok(RecipeResync { recipe, changes });
+        }
+        let recipe = self.recipe_dao.update_recipe(recipe, Some(&imported))?;
+        Ok(RecipeResync { recipe, changes })
+    }
+
+    pub fn add_recipe(&self, new_recipe: Recipe) -> Result<Recipe, Box<dyn Error>> {
+        self.insert_recipe(&new_recipe, None)
+    }
+
+    /// The recipe can be updated by its owner, by the users it is shared with
+    /// as editors and by the members of its household; `new_recipe.user_id` is
+    /// the user updating it.
+    /// Without new sections, the stored ones are kept for the lines they still
+    /// have.
+    pub fn update_recipe(
+        &self,
+        mut new_recipe: Recipe,
+        instruction_sections: Option<Vec<Option<String>>>,
+        ingredient_sections: Option<Vec<Option<String>>>,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let id = new_recipe.id.to_hyphenated().to_string();
+        let recipe = self.recipe_dao.get_recipe(id)?;
+        self.check_can_edit(&recipe, &new_recipe.user_id)?;
+        new_recipe.instruction_sections = instruction_sections.unwrap_or_else(|| {
+            keep_sections(&recipe.instruction_sections, new_recipe.instructions.len())
+        });
+        new_recipe.ingredient_sections = ingredient_sections.unwrap_or_else(|| {
+            keep_sections(&recipe.ingredient_sections, new_recipe.ingredients.len())
+        });
+        self.recipe_dao.update_recipe(new_recipe, None)
     }
 
+    /// Copies a recipe the user can read to their own library, as private.
     pub fn copy_recipe(
         &self,
         user_id: String,
         recipe_id: String,
     ) -> Result<Recipe, Box<dyn Error>> {
-        let new_recipe = self.recipe_dao.get_recipe(recipe_id)?;
-        self.recipe_dao.add_recipe(NewRecipe {
-            id: Uuid::new_v4().to_hyphenated().to_string().as_str(),
-            user_id: &user_id.as_str(),
-            title: &new_recipe.title.as_str(),
-            description: new_recipe.description.as_deref(),
-            recipe_yield: new_recipe.recipe_yield.as_deref(),
-            category: new_recipe.category.as_deref(),
-            cuisine: new_recipe.cuisine.as_deref(),
-            prep_time_in_minute: (&new_recipe.prep_time_in_minute).as_ref(),
-            cook_time_in_minute: (&new_recipe.cook_time_in_minute).as_ref(),
-            instructions: new_recipe.instructions.iter().map(|s| s.as_str()).collect(),
-            ingredients: new_recipe.ingredients.iter().map(|s| s.as_str()).collect(),
-            imported_from: new_recipe.imported_from.as_deref(),
-            image_url: new_recipe.image_url.as_deref(),
+        let recipe = self.get_recipe(recipe_id, Some(user_id.clone()))?;
+        self.add_recipe(Recipe {
+            id: Uuid::new_v4(),
+            user_id,
+            visibility: RecipeVisibility::Private,
+            household_id: None,
+            ..recipe
         })
     }
 
+    /// The recipe cannot be deleted while other users plan to cook it.
     pub fn delete_recipe(&self, id: String, user_id: String) -> Result<(), Box<dyn Error>> {
         let recipe = self.recipe_dao.get_recipe(id.clone())?;
         if recipe.user_id != user_id {
             return Err(Box::new(RecipeError::RecipeDoNotbelongToUser));
         }
+        if self.recipe_dao.is_planned_by_other_users(&id, &user_id)? {
+            return Err(Box::new(RecipeError::RecipePlannedByOtherUsers));
+        }
         self.recipe_dao.delete_recipe(id)
     }
 
-    pub fn get_recipe(&self, id: String) -> Result<Recipe, Box<dyn Error>> {
+    /// Private recipes of other users are reported as not found so that their
+    /// ids cannot be probed, unless they are shared with the user or belong to
+    /// their household.
+    pub fn get_recipe(
+        &self,
+        id: String,
+        user_id: Option<String>,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let recipe = self.recipe_dao.get_recipe(id)?;
+        if !can_read_recipe(
+            self.recipe_dao.as_ref(),
+            self.user_dao.as_ref(),
+            &recipe,
+            user_id.as_deref(),
+        )? {
+            return Err(Box::new(RecipeError::RecipeNotFound));
+        }
+        Ok(recipe)
+    }
+
+    /// Moves the recipe to the library of the household of its owner, or back
+    /// to their own library.
+    pub fn set_household(
+        &self,
+        id: String,
+        user_id: String,
+        in_household: bool,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        self.get_own_recipe(&id, &user_id)?;
+        let household_id = if in_household {
+            Some(
+                self.user_dao
+                    .get_household_id(&user_id)?
+                    .ok_or(UserError::UserNotInHousehold)?,
+            )
+        } else {
+            None
+        };
+        self.recipe_dao
+            .set_household(&id, household_id.as_deref())?;
+        self.recipe_dao.get_recipe(id)
+    }
+
+    pub fn get_shares(
+        &self,
+        id: String,
+        user_id: String,
+    ) -> Result<Vec<RecipeShare>, Box<dyn Error>> {
+        self.get_own_recipe(&id, &user_id)?;
+        self.recipe_dao.get_shares(&id)
+    }
+
+    /// Shares the recipe with the user having this email, or changes their role
+    /// if it is already shared with them.
+    /// Sharing with an email having no account does nothing, and answers
+    /// the same way so that the accounts cannot be probed.
+    pub fn share_recipe(
+        &self,
+        id: String,
+        user_id: String,
+        email: String,
+        role: ShareRole,
+    ) -> Result<Vec<RecipeShare>, Box<dyn Error>> {
+        self.get_own_recipe(&id, &user_id)?;
+        if let Some(shared_with) = self.find_user_by_email(&email)? {
+            if shared_with == user_id {
+                return Err(Box::new(RecipeError::RecipeSharedWithOwner));
+            }
+            self.recipe_dao.share_recipe(&id, &shared_with, role)?;
+        }
+        self.recipe_dao.get_shares(&id)
+    }
+
+    pub fn unshare_recipe(
+        &self,
+        id: String,
+        user_id: String,
+        email: String,
+    ) -> Result<Vec<RecipeShare>, Box<dyn Error>> {
+        self.get_own_recipe(&id, &user_id)?;
+        if let Some(shared_with) = self.find_user_by_email(&email)? {
+            self.recipe_dao.unshare_recipe(&id, &shared_with)?;
+        }
+        self.recipe_dao.get_shares(&id)
+    }
+
+    pub fn set_visibility(
+        &self,
+        id: String,
+        user_id: String,
+        visibility: RecipeVisibility,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let recipe = self.recipe_dao.get_recipe(id.clone())?;
+        if recipe.user_id != user_id {
+            return Err(Box::new(RecipeError::RecipeDoNotbelongToUser));
+        }
+        self.recipe_dao.set_visibility(&id, visibility)?;
         self.recipe_dao.get_recipe(id)
     }
 
+    /// The recipes having these ids, in the same order, leaving out the ones
+    /// the user cannot read or which do not exist anymore.
+    pub fn get_recipes(
+        &self,
+        ids: &[String],
+        user_id: Option<String>,
+    ) -> Result<Vec<Recipe>, Box<dyn Error>> {
+        let mut recipes = get_readable_recipes(
+            self.recipe_dao.as_ref(),
+            self.user_dao.as_ref(),
+            self.recipe_dao.get_recipes(ids)?,
+            user_id.as_deref(),
+        )?;
+        let mut ordered_recipes = vec![];
+        for id in ids {
+            if let Some(index) = recipes
+                .iter()
+                .position(|r| r.id.to_hyphenated().to_string() == *id)
+            {
+                ordered_recipes.push(recipes.swap_remove(index));
+            }
+        }
+        Ok(ordered_recipes)
+    }
+
     pub fn get_my_recipes(
         &self,
         user_id: String,
-        query: Option<String>,
+        filter: RecipeFilter,
+        sort: RecipeSort,
     ) -> Result<Vec<Recipe>, Box<dyn Error>> {
-        let recipes = self.recipe_dao.get_my_recipes(&user_id, query)?;
+        let filter = RecipeFilter {
+            tags: normalize_tags(&filter.tags),
+            categories: normalize_facet_values(&filter.categories),
+            cuisines: normalize_facet_values(&filter.cuisines),
+            ..filter
+        };
+        let recipes = self.recipe_dao.get_my_recipes(&user_id, &filter, sort)?;
         if recipes.is_empty() {
             self.user_dao.get_user(&user_id)?;
         }
         Ok(recipes)
     }
 
+    /// At most `first` recipes, following the one pointed at by the `after`
+    /// cursor, the recipes sorted the same way being ordered by id.
+    pub fn get_my_recipes_page(
+        &self,
+        user_id: String,
+        filter: RecipeFilter,
+        sort: RecipeSort,
+        first: Option<usize>,
+        after: Option<String>,
+    ) -> Result<RecipePage, Box<dyn Error>> {
+        let after = match after {
+            Some(cursor) => Some(
+                decode_cursor(&cursor)
+                    .filter(|cursor| cursor.key.field() == sort.field)
+                    .ok_or(RecipeError::RecipeCursorInvalid)?,
+            ),
+            None => None,
+        };
+        let first = first.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let filter = RecipeFilter {
+            tags: normalize_tags(&filter.tags),
+            categories: normalize_facet_values(&filter.categories),
+            cuisines: normalize_facet_values(&filter.cuisines),
+            ..filter
+        };
+        let total_count = self.recipe_dao.count_my_recipes(&user_id, &filter)?;
+        if total_count == 0 {
+            self.user_dao.get_user(&user_id)?;
+        }
+        // One more recipe tells whether there is a next page.
+        let mut recipes = self.recipe_dao.get_my_recipes_after(
+            &user_id,
+            &filter,
+            sort,
+            after.as_ref(),
+            first + 1,
+        )?;
+        let has_next_page = recipes.len() > first;
+        recipes.truncate(first);
+        let (recipes, cursors) = recipes.into_iter().unzip();
+        Ok(RecipePage {
+            recipes,
+            cursors,
+            has_next_page,
+            has_previous_page: after.is_some(),
+            total_count,
+        })
+    }
+
+    /// Distinct categories and cuisines of the recipes matching the filter,
+    /// with how many recipes have each of them. The categories are counted
+    /// whatever the filtered categories, and the same goes for the cuisines.
+    pub fn get_my_recipe_facets(
+        &self,
+        user_id: String,
+        filter: RecipeFilter,
+    ) -> Result<RecipeFacets, Box<dyn Error>> {
+        let filter = RecipeFilter {
+            tags: normalize_tags(&filter.tags),
+            categories: normalize_facet_values(&filter.categories),
+            cuisines: normalize_facet_values(&filter.cuisines),
+            ..filter
+        };
+        self.user_dao.get_user(&user_id)?;
+        self.recipe_dao.get_my_recipe_facets(&user_id, &filter)
+    }
+
+    /// The recipes of the user and of their household that can be cooked with
+    /// the ingredients on hand, the ones missing the fewest ingredients first.
+    pub fn get_cookable_recipes(
+        &self,
+        user_id: String,
+        ingredients: Vec<String>,
+    ) -> Result<Vec<CookableRecipe>, Box<dyn Error>> {
+        let recipes =
+            self.get_my_recipes(user_id, RecipeFilter::default(), RecipeSort::default())?;
+        Ok(get_cookable_recipes(recipes, &ingredients))
+    }
+
+    pub fn search_recipes(
+        &self,
+        user_id: String,
+        query: String,
+        limit: Option<usize>,
+    ) -> Result<Vec<RecipeSearchResult>, Box<dyn Error>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+        let limit = limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+        self.recipe_dao.search_recipes(&user_id, query, limit)
+    }
+
+    pub fn add_tag(
+        &self,
+        id: String,
+        user_id: String,
+        tag: String,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let tag = normalize_tag(&tag).ok_or(RecipeError::RecipeTagInvalid)?;
+        let recipe = self.recipe_dao.get_recipe(id.clone())?;
+        self.check_can_edit(&recipe, &user_id)?;
+        self.recipe_dao.add_tag(&id, &tag)?;
+        self.recipe_dao.get_recipe(id)
+    }
+
+    pub fn remove_tag(
+        &self,
+        id: String,
+        user_id: String,
+        tag: String,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let tag = normalize_tag(&tag).ok_or(RecipeError::RecipeTagInvalid)?;
+        let recipe = self.recipe_dao.get_recipe(id.clone())?;
+        self.check_can_edit(&recipe, &user_id)?;
+        self.recipe_dao.remove_tag(&id, &tag)?;
+        self.recipe_dao.get_recipe(id)
+    }
+
     pub fn get_photo_upload_url(&self, extension: &str) -> Result<String, Box<dyn Error>> {
         self.image_store.get_photo_upload_url(extension)
     }
+
+    /// Saves the recipe with normalized tags, and with the snapshot of the
+    /// website it was imported from, if any.
+    fn insert_recipe(
+        &self,
+        new_recipe: &Recipe,
+        import_snapshot: Option<&Recipe>,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let tags = normalize_tags(&new_recipe.tags);
+        self.recipe_dao.add_recipe(
+            NewRecipe {
+                id: new_recipe.id.to_hyphenated().to_string().as_str(),
+                user_id: &new_recipe.user_id.as_str(),
+                title: &new_recipe.title.as_str(),
+                description: new_recipe.description.as_deref(),
+                recipe_yield: new_recipe.recipe_yield.as_deref(),
+                category: new_recipe.category.as_deref(),
+                cuisine: new_recipe.cuisine.as_deref(),
+                prep_time_in_minute: (&new_recipe.prep_time_in_minute).as_ref(),
+                cook_time_in_minute: (&new_recipe.cook_time_in_minute).as_ref(),
+                instructions: new_recipe.instructions.iter().map(|s| s.as_str()).collect(),
+                instruction_sections: new_recipe
+                    .instruction_sections
+                    .iter()
+                    .map(|s| s.as_deref())
+                    .collect(),
+                ingredients: new_recipe.ingredients.iter().map(|s| s.as_str()).collect(),
+                ingredient_sections: new_recipe
+                    .ingredient_sections
+                    .iter()
+                    .map(|s| s.as_deref())
+                    .collect(),
+                structured_ingredients: &new_recipe.structured_ingredients,
+                tags: tags.iter().map(|s| s.as_str()).collect(),
+                imported_from: new_recipe.imported_from.as_deref(),
+                image_url: new_recipe.image_url.as_deref(),
+            },
+            import_snapshot,
+        )
+    }
+
+    fn get_own_recipe(&self, id: &str, user_id: &str) -> Result<Recipe, Box<dyn Error>> {
+        let recipe = self.recipe_dao.get_recipe(String::from(id))?;
+        if recipe.user_id != user_id {
+            return Err(Box::new(RecipeError::RecipeDoNotbelongToUser));
+        }
+        Ok(recipe)
+    }
+
+    /// Fails unless the user owns the recipe, edits it through a share or
+    /// belongs to its household.
+    fn check_can_edit(&self, recipe: &Recipe, user_id: &str) -> Result<(), Box<dyn Error>> {
+        let id = recipe.id.to_hyphenated().to_string();
+        if recipe.user_id != user_id
+            && self.recipe_dao.get_share_role(&id, user_id)? != Some(ShareRole::Editor)
+            && !is_in_household_of(self.user_dao.as_ref(), recipe, user_id)?
+        {
+            return Err(Box::new(RecipeError::RecipeDoNotbelongToUser));
+        }
+        Ok(())
+    }
+
+    /// Returns the id of the user having this email, if any.
+    fn find_user_by_email(&self, email: &str) -> Result<Option<String>, Box<dyn Error>> {
+        match self.user_dao.get_user_by_email(email) {
+            Ok(user) => Ok(Some(user.id.to_hyphenated().to_string())),
+            Err(error) => match error.downcast_ref::<UserError>() {
+                Some(UserError::UserNotFound) => Ok(None),
+                _ => Err(error),
+            },
+        }
+    }
+}
+
+struct FieldMerge {
+    apply: bool,
+    changes: Vec<RecipeFieldChange>,
+}
+
+impl FieldMerge {
+    /// Records the change of a field and applies it if it was not edited
+    /// locally, which is assumed without a `base` to compare it with.
+    /// Returns whether the imported value has been applied.
+    fn field<T: PartialEq + Clone>(
+        &mut self,
+        field: &str,
+        base: Option<&T>,
+        current: &mut T,
+        imported: &T,
+        to_text: fn(&T) -> Option<String>,
+    ) -> bool {
+        if current == imported {
+            return false;
+        }
+        let locally_edited = base != Some(current);
+        self.changes.push(RecipeFieldChange {
+            field: String::from(field),
+            current: to_text(current),
+            imported: to_text(imported),
+            locally_edited,
+        });
+        if self.apply && !locally_edited {
+            *current = imported.clone();
+            return true;
+        }
+        false
+    }
+}
+
+/// Whether the user can read the recipe: it is visible to them, shared with
+/// them or in the library of their household.
+pub fn can_read_recipe(
+    recipe_dao: &dyn RecipeDao,
+    user_dao: &dyn UserDao,
+    recipe: &Recipe,
+    user_id: Option<&str>,
+) -> Result<bool, Box<dyn Error>> {
+    if recipe.is_visible_to(user_id) {
+        return Ok(true);
+    }
+    match user_id {
+        Some(user_id) => Ok(recipe_dao
+            .get_share_role(&recipe.id.to_hyphenated().to_string(), user_id)?
+            .is_some()
+            || is_in_household_of(user_dao, recipe, user_id)?),
+        None => Ok(false),
+    }
+}
+
+/// The recipes the user can read, checking the shares and the household of
+/// the user once for all of them.
+pub fn get_readable_recipes(
+    recipe_dao: &dyn RecipeDao,
+    user_dao: &dyn UserDao,
+    recipes: Vec<Recipe>,
+    user_id: Option<&str>,
+) -> Result<Vec<Recipe>, Box<dyn Error>> {
+    let hidden_ids: Vec<String> = recipes
+        .iter()
+        .filter(|r| !r.is_visible_to(user_id))
+        .map(|r| r.id.to_hyphenated().to_string())
+        .collect();
+    let user_id = match user_id {
+        Some(user_id) if !hidden_ids.is_empty() => user_id,
+        _ => {
+            return Ok(recipes
+                .into_iter()
+                .filter(|r| r.is_visible_to(user_id))
+                .collect())
+        }
+    };
+    let shared_ids = recipe_dao.get_shared_recipe_ids(&hidden_ids, user_id)?;
+    let household_id = user_dao.get_household_id(user_id)?;
+    Ok(recipes
+        .into_iter()
+        .filter(|r| {
+            r.is_visible_to(Some(user_id))
+                || shared_ids.contains(&r.id.to_hyphenated().to_string())
+                || (r.household_id.is_some() && r.household_id == household_id)
+        })
+        .collect())
+}
+
+/// The stored sections of the lines, as many as there are lines now.
+fn keep_sections(sections: &[Option<String>], line_count: usize) -> Vec<Option<String>> {
+    sections
+        .iter()
+        .cloned()
+        .chain(std::iter::repeat(None))
+        .take(line_count)
+        .collect()
+}
+
+fn is_in_household_of(
+    user_dao: &dyn UserDao,
+    recipe: &Recipe,
+    user_id: &str,
+) -> Result<bool, Box<dyn Error>> {
+    match &recipe.household_id {
+        Some(household_id) => {
+            Ok(user_dao.get_household_id(user_id)?.as_ref() == Some(household_id))
+        }
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_applies_fields_not_edited_locally() {
+        // given
+        let mut merge = FieldMerge {
+            apply: true,
+            changes: vec![],
+        };
+        let base = String::from("Pâte brisée");
+        let mut current = String::from("Pâte brisée");
+
+        // when
+        let applied = merge.field(
+            "title",
+            Some(&base),
+            &mut current,
+            &String::from("Pâte brisée vite faite"),
+            |v| Some(v.clone()),
+        );
+
+        // then
+        assert!(applied);
+        assert_eq!(current, "Pâte brisée vite faite");
+        assert_eq!(
+            merge.changes,
+            vec![RecipeFieldChange {
+                field: String::from("title"),
+                current: Some(String::from("Pâte brisée")),
+                imported: Some(String::from("Pâte brisée vite faite")),
+                locally_edited: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn merge_keeps_fields_edited_locally() {
+        // given
+        let mut merge = FieldMerge {
+            apply: true,
+            changes: vec![],
+        };
+        let base = Some(15);
+        let mut current = Some(20);
+
+        // when
+        let applied = merge.field(
+            "prepTimeInMinute",
+            Some(&base),
+            &mut current,
+            &Some(10),
+            |v| v.map(|m| m.to_string()),
+        );
+
+        // then
+        assert!(!applied);
+        assert_eq!(current, Some(20));
+        assert!(merge.changes[0].locally_edited);
+    }
+
+    #[test]
+    fn merge_keeps_changed_fields_without_snapshot() {
+        // given
+        let mut merge = FieldMerge {
+            apply: true,
+            changes: vec![],
+        };
+        let mut current = Some(String::from("Dessert"));
+
+        // when
+        let applied = merge.field(
+            "category",
+            None,
+            &mut current,
+            &Some(String::from("Entremets")),
+            |v| v.clone(),
+        );
+
+        // then
+        assert!(!applied);
+        assert_eq!(current, Some(String::from("Dessert")));
+        assert!(merge.changes[0].locally_edited);
+    }
+
+    #[test]
+    fn merge_ignores_unchanged_fields() {
+        // given
+        let mut merge = FieldMerge {
+            apply: true,
+            changes: vec![],
+        };
+        let mut current = Some(String::from("Dessert"));
+
+        // when
+        let applied = merge.field(
+            "category",
+            None,
+            &mut current,
+            &Some(String::from("Dessert")),
+            |v| v.clone(),
+        );
+
+        // then
+        assert!(!applied);
+        assert!(merge.changes.is_empty());
+    }
 }
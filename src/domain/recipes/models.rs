@@ -1 +1,11 @@
+pub mod conversion;
+pub mod cookable;
+pub mod facet;
+pub mod ingredient;
+pub mod page;
 pub mod recipe;
+pub mod resync;
+pub mod scaling;
+pub mod search;
+pub mod share;
+pub mod tag;
@@ -0,0 +1,452 @@
+use crate::domain::recipes::models::ingredient::{
+    format_quantity, get_canonical_unit, get_words, strip_quantity, StructuredIngredient,
+};
+use crate::domain::recipes::models::recipe::Recipe;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+impl UnitSystem {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "metric",
+            UnitSystem::Imperial => "imperial",
+        }
+    }
+
+    pub fn parse(unit_system: &str) -> Option<UnitSystem> {
+        match unit_system {
+            "metric" => Some(UnitSystem::Metric),
+            "imperial" => Some(UnitSystem::Imperial),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ConvertedIngredient {
+    pub text: String,
+    pub ingredient: StructuredIngredient,
+    /// False when the line was already in the unit system or has no known unit.
+    pub converted: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RecipeConversion {
+    pub unit_system: UnitSystem,
+    pub ingredients: Vec<ConvertedIngredient>,
+    pub instructions: Vec<String>,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Dimension {
+    Mass,
+    Volume,
+}
+
+/// Unit, dimension, size in grams or millilitres and the system it belongs to.
+/// Spoons are used everywhere, so they are never converted.
+static UNITS: &[(&str, Dimension, f64, Option<UnitSystem>)] = &[
+    ("mg", Dimension::Mass, 0.001, Some(UnitSystem::Metric)),
+    ("g", Dimension::Mass, 1.0, Some(UnitSystem::Metric)),
+    ("kg", Dimension::Mass, 1000.0, Some(UnitSystem::Metric)),
+    ("oz", Dimension::Mass, 28.3495, Some(UnitSystem::Imperial)),
+    ("lb", Dimension::Mass, 453.592, Some(UnitSystem::Imperial)),
+    ("ml", Dimension::Volume, 1.0, Some(UnitSystem::Metric)),
+    ("cl", Dimension::Volume, 10.0, Some(UnitSystem::Metric)),
+    ("dl", Dimension::Volume, 100.0, Some(UnitSystem::Metric)),
+    ("l", Dimension::Volume, 1000.0, Some(UnitSystem::Metric)),
+    ("tsp", Dimension::Volume, 4.92892, None),
+    ("tbsp", Dimension::Volume, 14.7868, None),
+    (
+        "fl oz",
+        Dimension::Volume,
+        29.5735,
+        Some(UnitSystem::Imperial),
+    ),
+    (
+        "cup",
+        Dimension::Volume,
+        236.588,
+        Some(UnitSystem::Imperial),
+    ),
+];
+
+/// Grams per millilitre of the ingredients usually measured both by volume
+/// and by weight.
+static DENSITIES: &[(&[&str], f64)] = &[
+    (&["flour", "farine"], 0.53),
+    (&["sugar", "sucre"], 0.85),
+    (&["butter", "beurre"], 0.96),
+];
+
+/// Words which may come along the names above without making another
+/// ingredient of them, unlike "peanut" in "peanut butter".
+static DENSITY_QUALIFIERS: &[&str] = &[
+    "all",
+    "purpose",
+    "plain",
+    "self",
+    "raising",
+    "rising",
+    "bread",
+    "cake",
+    "pastry",
+    "whole",
+    "wheat",
+    "white",
+    "brown",
+    "light",
+    "dark",
+    "granulated",
+    "caster",
+    "superfine",
+    "powdered",
+    "icing",
+    "confectioner",
+    "unsalted",
+    "salted",
+    "softened",
+    "melted",
+    "cold",
+    "de",
+    "d",
+    "a",
+    "en",
+    "ble",
+    "type",
+    "complete",
+    "blanc",
+    "roux",
+    "poudre",
+    "glace",
+    "semoule",
+    "vanille",
+    "doux",
+    "demi",
+    "sel",
+    "fondu",
+    "mou",
+    "pommade",
+];
+
+pub fn convert_recipe(recipe: &Recipe, unit_system: UnitSystem) -> RecipeConversion {
+    let ingredients = recipe
+        .ingredients
+        .iter()
+        .zip(recipe.structured_ingredients.iter())
+        .map(
+            |(text, ingredient)| match convert_ingredient(ingredient, unit_system) {
+                Some(converted) => ConvertedIngredient {
+                    text: get_converted_text(text, &converted),
+                    ingredient: converted,
+                    converted: true,
+                },
+                None => ConvertedIngredient {
+                    text: text.clone(),
+                    ingredient: ingredient.clone(),
+                    converted: false,
+                },
+            },
+        )
+        .collect();
+    RecipeConversion {
+        unit_system,
+        ingredients,
+        instructions: recipe
+            .instructions
+            .iter()
+            .map(|i| convert_temperatures(i, unit_system))
+            .collect(),
+    }
+}
+
+/// Returns the ingredient expressed in the unit system, or None when it
+/// already is or cannot be converted.
+pub fn convert_ingredient(
+    ingredient: &StructuredIngredient,
+    unit_system: UnitSystem,
+) -> Option<StructuredIngredient> {
+    let quantity = ingredient.quantity?;
+    let (dimension, size) = UNITS
+        .iter()
+        .find(|(unit, _, _, system)| {
+            Some(*unit) == ingredient.unit.as_deref()
+                && system.map(|s| s != unit_system).unwrap_or(false)
+        })
+        .map(|(_, dimension, size, _)| (*dimension, *size))?;
+    // Flour, sugar and butter are weighed in metric and measured in cups in imperial.
+    let (target, factor) = match (unit_system, dimension, get_density(&ingredient.name)) {
+        (UnitSystem::Metric, Dimension::Volume, Some(density)) => (Dimension::Mass, density),
+        (UnitSystem::Imperial, Dimension::Mass, Some(density)) => {
+            (Dimension::Volume, 1.0 / density)
+        }
+        _ => (dimension, 1.0),
+    };
+    let amount = quantity * size * factor;
+    let unit = match unit_system {
+        UnitSystem::Metric => get_metric_unit(target, amount),
+        UnitSystem::Imperial => get_imperial_unit(target, amount),
+    };
+    let quantity = amount / get_size(unit);
+    let max_quantity = ingredient
+        .max_quantity
+        .map(|max| max * size * factor / get_size(unit));
+    Some(StructuredIngredient {
+        quantity: Some(quantity),
+        max_quantity,
+        unit: Some(String::from(unit)),
+        ..ingredient.clone()
+    })
+}
+
+/// Adds two quantities of an ingredient, e.g. 500 g and 1 kg make 1.5 kg.
+/// Returns None when the units do not measure the same dimension.
+pub fn add_quantities(
+    first: f64,
+    first_unit: &str,
+    second: f64,
+    second_unit: &str,
+) -> Option<(f64, String)> {
+    if first_unit == second_unit {
+        return Some((first + second, String::from(first_unit)));
+    }
+    let (dimension, first_size, system) = UNITS
+        .iter()
+        .find(|(unit, ..)| *unit == first_unit)
+        .map(|(_, dimension, size, system)| (*dimension, *size, *system))?;
+    let second_size = UNITS
+        .iter()
+        .find(|(unit, other, ..)| *unit == second_unit && *other == dimension)
+        .map(|(_, _, size, _)| *size)?;
+    let amount = first * first_size + second * second_size;
+    let unit = match system {
+        Some(UnitSystem::Imperial) => get_imperial_unit(dimension, amount),
+        _ => get_metric_unit(dimension, amount),
+    };
+    Some((amount / get_size(unit), String::from(unit)))
+}
+
+/// Rewrites oven temperatures such as "350°F" or "180 °C" in the unit system.
+pub fn convert_temperatures(text: &str, unit_system: UnitSystem) -> String {
+    let (from, to) = match unit_system {
+        UnitSystem::Metric => ('F', 'C'),
+        UnitSystem::Imperial => ('C', 'F'),
+    };
+    let mut converted = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find('°') {
+        let after = &rest[index + '°'.len_utf8()..];
+        let scale_offset = after.len() - after.trim_start().len();
+        let is_from = after.trim_start().starts_with(from);
+        let before = rest[..index].trim_end();
+        let digits = before.len()
+            - before
+                .chars()
+                .rev()
+                .take_while(|c| c.is_ascii_digit())
+                .map(char::len_utf8)
+                .sum::<usize>();
+        match before[digits..].parse::<f64>() {
+            Ok(degrees) if is_from => {
+                let degrees = match unit_system {
+                    UnitSystem::Metric => (degrees - 32.0) * 5.0 / 9.0,
+                    UnitSystem::Imperial => degrees * 9.0 / 5.0 + 32.0,
+                };
+                converted.push_str(&before[..digits]);
+                converted.push_str(&format!("{}°{}", (degrees / 5.0).round() * 5.0, to));
+                rest = &after[scale_offset + 1..];
+            }
+            _ => {
+                converted.push_str(&rest[..index + '°'.len_utf8()]);
+                rest = after;
+            }
+        }
+    }
+    converted.push_str(rest);
+    converted
+}
+
+/// The density of the ingredient when its name is one of `DENSITIES`, give
+/// or take qualifiers such as "unsalted" or "de blé T55".
+fn get_density(name: &str) -> Option<f64> {
+    let words = get_words(name);
+    let is_qualifier = |word: &String| {
+        // Flour types, such as "T55" or "type 45".
+        let digits = word.strip_prefix('t').unwrap_or(word);
+        DENSITY_QUALIFIERS
+            .iter()
+            .any(|qualifier| get_words(qualifier) == [word.as_str()])
+            || (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+    };
+    DENSITIES
+        .iter()
+        .find(|(names, _)| {
+            words.iter().any(|word| names.contains(&word.as_str()))
+                && words
+                    .iter()
+                    .all(|word| names.contains(&word.as_str()) || is_qualifier(word))
+        })
+        .map(|(_, density)| *density)
+}
+
+fn get_size(unit: &str) -> f64 {
+    UNITS
+        .iter()
+        .find(|(u, ..)| *u == unit)
+        .map(|(_, _, size, _)| *size)
+        .unwrap_or(1.0)
+}
+
+/// Picks the metric unit reading best for an amount in grams or millilitres.
+fn get_metric_unit(dimension: Dimension, amount: f64) -> &'static str {
+    match dimension {
+        Dimension::Mass if amount >= 1000.0 => "kg",
+        Dimension::Mass => "g",
+        Dimension::Volume if amount >= 1000.0 => "l",
+        Dimension::Volume => "ml",
+    }
+}
+
+/// Picks the imperial unit reading best for an amount in grams or millilitres.
+fn get_imperial_unit(dimension: Dimension, amount: f64) -> &'static str {
+    match dimension {
+        Dimension::Mass if amount >= get_size("lb") => "lb",
+        Dimension::Mass => "oz",
+        Dimension::Volume if amount >= get_size("cup") / 4.0 => "cup",
+        Dimension::Volume if amount >= get_size("tbsp") => "tbsp",
+        Dimension::Volume => "tsp",
+    }
+}
+
+/// Replaces the quantity and unit of the original line, keeping its wording.
+fn get_converted_text(text: &str, ingredient: &StructuredIngredient) -> String {
+    let rest = strip_quantity(text);
+    let mut words = rest.splitn(2, ' ');
+    let first = words.next().unwrap_or("");
+    let rest = if first.eq_ignore_ascii_case("fl") {
+        rest.splitn(3, ' ').nth(2).unwrap_or("")
+    } else if get_canonical_unit(first).is_some() {
+        words.next().unwrap_or("")
+    } else {
+        rest
+    };
+    let amount = match (ingredient.quantity, ingredient.max_quantity) {
+        (Some(quantity), Some(max)) => {
+            format!("{}-{}", format_quantity(quantity), format_quantity(max))
+        }
+        (Some(quantity), None) => format_quantity(quantity),
+        _ => String::new(),
+    };
+    let unit = ingredient.unit.as_deref().unwrap_or("");
+    format!("{} {} {}", amount, unit, rest)
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(text: &str, unit_system: UnitSystem) -> (String, bool) {
+        let ingredient = StructuredIngredient::parse(text);
+        match convert_ingredient(&ingredient, unit_system) {
+            Some(converted) => (get_converted_text(text, &converted), true),
+            None => (String::from(text), false),
+        }
+    }
+
+    #[test]
+    fn converting_to_metric() {
+        assert_eq!(
+            convert("1 cup flour", UnitSystem::Metric),
+            (String::from("125 g flour"), true)
+        );
+        assert_eq!(
+            convert("2 cups milk", UnitSystem::Metric),
+            (String::from("473 ml milk"), true)
+        );
+        assert_eq!(
+            convert("2-3 lb potatoes, peeled", UnitSystem::Metric),
+            (String::from("907-1361 g potatoes, peeled"), true)
+        );
+        assert_eq!(
+            convert("2 tbsp olive oil", UnitSystem::Metric),
+            (String::from("2 tbsp olive oil"), false)
+        );
+        assert_eq!(
+            convert("150 g de beurre", UnitSystem::Metric),
+            (String::from("150 g de beurre"), false)
+        );
+    }
+
+    #[test]
+    fn adding_quantities() {
+        assert_eq!(
+            add_quantities(500.0, "g", 1.0, "kg"),
+            Some((1.5, String::from("kg")))
+        );
+        assert_eq!(
+            add_quantities(2.0, "clove", 1.0, "clove"),
+            Some((3.0, String::from("clove")))
+        );
+        assert_eq!(add_quantities(1.0, "cup", 100.0, "g"), None);
+    }
+
+    #[test]
+    fn converting_to_imperial() {
+        assert_eq!(
+            convert("200 g de sucre", UnitSystem::Imperial),
+            (String::from("1 cup de sucre"), true)
+        );
+        assert_eq!(
+            convert("500 g de boeuf haché", UnitSystem::Imperial),
+            (String::from("1.1 lb de boeuf haché"), true)
+        );
+        assert_eq!(
+            convert("25 cl de lait", UnitSystem::Imperial),
+            (String::from("1.1 cup de lait"), true)
+        );
+        assert_eq!(
+            convert("1 oignon", UnitSystem::Imperial),
+            (String::from("1 oignon"), false)
+        );
+    }
+
+    #[test]
+    fn finding_densities_of_whole_names() {
+        assert_eq!(get_density("Flour"), Some(0.53));
+        assert_eq!(get_density("all-purpose flour"), Some(0.53));
+        assert_eq!(get_density("farine de blé T55"), Some(0.53));
+        assert_eq!(get_density("brown sugar"), Some(0.85));
+        assert_eq!(get_density("beurre doux"), Some(0.96));
+        assert_eq!(get_density("buttermilk"), None);
+        assert_eq!(get_density("peanut butter"), None);
+        assert_eq!(get_density("sugar snap peas"), None);
+        assert_eq!(get_density("cornflour"), None);
+        assert_eq!(get_density("beurre de cacahuète"), None);
+        assert_eq!(
+            convert("1 cup buttermilk", UnitSystem::Metric),
+            (String::from("237 ml buttermilk"), true)
+        );
+    }
+
+    #[test]
+    fn converting_temperatures() {
+        assert_eq!(
+            convert_temperatures("Preheat the oven to 350°F.", UnitSystem::Metric),
+            "Preheat the oven to 175°C."
+        );
+        assert_eq!(
+            convert_temperatures("Préchauffer le four à 180 °C.", UnitSystem::Imperial),
+            "Préchauffer le four à 355°F."
+        );
+        assert_eq!(
+            convert_temperatures("Cuire à 180°C.", UnitSystem::Metric),
+            "Cuire à 180°C."
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::domain::recipes::models::ingredient::{get_words, StructuredIngredient};
+use crate::domain::recipes::models::recipe::Recipe;
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, Debug)]
+pub struct CookableRecipe {
+    pub recipe: Recipe,
+    /// Ingredient lines covered by the ingredients on hand or by staples.
+    pub covered: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+impl CookableRecipe {
+    /// Share of the ingredient lines covered, between 0 and 1.
+    pub fn coverage(&self) -> f64 {
+        self.covered.len() as f64 / (self.covered.len() + self.missing.len()) as f64
+    }
+}
+
+/// Ingredients every kitchen has, never listed as missing.
+static STAPLES: &[&str] = &["salt", "pepper", "water", "sel", "poivre", "eau"];
+
+/// Returns the recipes using at least one of the ingredients on hand, the ones
+/// missing the fewest ingredients first. Ingredients on hand may be given one
+/// by one or comma separated, e.g. "eggs, flour, milk".
+pub fn get_cookable_recipes(recipes: Vec<Recipe>, on_hand: &[String]) -> Vec<CookableRecipe> {
+    let on_hand: Vec<Vec<String>> = on_hand
+        .iter()
+        .flat_map(|i| i.split(','))
+        .map(get_words)
+        .filter(|words| !words.is_empty())
+        .collect();
+    let staples: Vec<Vec<String>> = STAPLES.iter().map(|s| get_words(s)).collect();
+
+    let mut cookable_recipes: Vec<CookableRecipe> = recipes
+        .into_iter()
+        .filter_map(|recipe| {
+            let mut covered = vec![];
+            let mut missing = vec![];
+            let mut used_on_hand = false;
+            for (text, ingredient) in recipe
+                .ingredients
+                .iter()
+                .zip(recipe.structured_ingredients.iter())
+            {
+                let words = get_words(get_name(text, ingredient));
+                if on_hand.iter().any(|item| contains_all(&words, item)) {
+                    used_on_hand = true;
+                    covered.push(text.clone());
+                } else if staples.iter().any(|staple| contains_all(&words, staple)) {
+                    covered.push(text.clone());
+                } else {
+                    missing.push(text.clone());
+                }
+            }
+            if !used_on_hand {
+                return None;
+            }
+            Some(CookableRecipe {
+                recipe,
+                covered,
+                missing,
+            })
+        })
+        .collect();
+    cookable_recipes.sort_by(|a, b| {
+        b.coverage()
+            .partial_cmp(&a.coverage())
+            .unwrap_or(Ordering::Equal)
+            .then(a.missing.len().cmp(&b.missing.len()))
+            .then(a.recipe.title.cmp(&b.recipe.title))
+    });
+    cookable_recipes
+}
+
+/// The words of each ingredient line of the recipe, for `uses_ingredient`.
+pub fn get_ingredient_words(recipe: &Recipe) -> Vec<Vec<String>> {
+    recipe
+        .ingredients
+        .iter()
+        .zip(recipe.structured_ingredients.iter())
+        .map(|(text, structured)| get_words(get_name(text, structured)))
+        .collect()
+}
+
+/// Whether one of the ingredient lines, given by their words, is the
+/// ingredient, e.g. "2 eggs" for "egg".
+pub fn uses_ingredient(ingredient_words: &[Vec<String>], ingredient: &str) -> bool {
+    let item = get_words(ingredient);
+    !item.is_empty()
+        && ingredient_words
+            .iter()
+            .any(|words| contains_all(words, &item))
+}
+
+fn get_name<'a>(text: &'a str, ingredient: &'a StructuredIngredient) -> &'a str {
+    if ingredient.name.is_empty() {
+        text
+    } else {
+        &ingredient.name
+    }
+}
+
+fn contains_all(words: &[String], item: &[String]) -> bool {
+    item.iter().all(|word| words.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::recipes::models::recipe::RecipeBuilder;
+
+    fn recipe(title: &str, ingredients: &[&str]) -> Recipe {
+        RecipeBuilder::new(title).ingredients(ingredients).build()
+    }
+
+    #[test]
+    fn ranking_recipes_by_covered_ingredients() {
+        // given
+        let recipes = vec![
+            recipe("Omelette", &["3 eggs", "1 tbsp butter", "Salt"]),
+            recipe("Pancakes", &["2 eggs", "1 cup flour", "1 cup milk"]),
+            recipe("Salad", &["1 lettuce", "2 tomatoes"]),
+        ];
+
+        // when
+        let cookable = get_cookable_recipes(recipes, &[String::from("eggs, flour, milk")]);
+
+        // then
+        let titles: Vec<&str> = cookable.iter().map(|c| c.recipe.title.as_str()).collect();
+        assert_eq!(titles, vec!["Pancakes", "Omelette"]);
+        assert!(cookable[0].missing.is_empty());
+        assert_eq!(cookable[1].covered, vec!["3 eggs", "Salt"]);
+        assert_eq!(cookable[1].missing, vec!["1 tbsp butter"]);
+    }
+
+    #[test]
+    fn ignoring_recipes_only_covered_by_staples() {
+        // given
+        let recipes = vec![recipe("Pâtes", &["500 g de pâtes", "Sel", "Poivre"])];
+
+        // when
+        let cookable = get_cookable_recipes(recipes, &[String::from("riz")]);
+
+        // then
+        assert!(cookable.is_empty());
+    }
+
+    #[test]
+    fn matching_plurals_and_accents() {
+        // given
+        let recipes = vec![recipe(
+            "Quiche",
+            &["3 Œufs", "200 g de crème fraîche", "2 tomates"],
+        )];
+
+        // when
+        let cookable = get_cookable_recipes(
+            recipes,
+            &[
+                String::from("oeuf"),
+                String::from("creme"),
+                String::from("tomate"),
+            ],
+        );
+
+        // then
+        assert_eq!(cookable[0].missing, Vec::<String>::new());
+        assert!((cookable[0].coverage() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn finding_recipes_using_an_ingredient() {
+        // given
+        let quiche = recipe("Quiche", &["3 Œufs", "200 g de crème fraîche"]);
+        let ingredient_words = get_ingredient_words(&quiche);
+
+        // when
+        let uses = ["oeuf", "crème", "lait", ""]
+            .iter()
+            .map(|i| uses_ingredient(&ingredient_words, i))
+            .collect::<Vec<bool>>();
+
+        // then
+        assert_eq!(uses, vec![true, true, false, false]);
+    }
+}
@@ -0,0 +1,49 @@
+#[derive(PartialEq, Debug)]
+pub struct FacetValue {
+    pub value: String,
+    /// Number of recipes having this value.
+    pub count: usize,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RecipeFacets {
+    pub categories: Vec<FacetValue>,
+    pub cuisines: Vec<FacetValue>,
+}
+
+/// Trims the values and removes the blank and duplicate ones, keeping their
+/// order, the way the facet values are counted.
+pub fn normalize_facet_values(values: &[String]) -> Vec<String> {
+    let mut normalized_values: Vec<String> = vec![];
+    for value in values.iter().map(|v| v.trim()).filter(|v| !v.is_empty()) {
+        if !normalized_values.iter().any(|v| v == value) {
+            normalized_values.push(String::from(value));
+        }
+    }
+    normalized_values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizing_facet_values() {
+        // given
+        let values = vec![
+            String::from(" Dessert"),
+            String::from("Plat"),
+            String::from(" "),
+            String::from("Dessert "),
+        ];
+
+        // when
+        let normalized_values = normalize_facet_values(&values);
+
+        // then
+        assert_eq!(
+            normalized_values,
+            vec![String::from("Dessert"), String::from("Plat")]
+        );
+    }
+}
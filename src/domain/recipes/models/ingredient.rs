@@ -0,0 +1,540 @@
+/// An ingredient line split into its parts, e.g. "150 g de beurre en dés"
+/// gives a quantity of 150, the unit "g", the name "beurre" and the note "en dés".
+#[derive(PartialEq, Debug, Clone)]
+pub struct StructuredIngredient {
+    pub quantity: Option<f64>,
+    /// Upper bound when the quantity is a range, e.g. "2-3 eggs".
+    pub max_quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub name: String,
+    pub note: Option<String>,
+}
+
+/// Canonical unit followed by the spellings found on recipe websites.
+static UNITS: &[(&str, &[&str])] = &[
+    (
+        "mg",
+        &[
+            "mg",
+            "milligramme",
+            "milligrammes",
+            "milligram",
+            "milligrams",
+        ],
+    ),
+    ("g", &["g", "gr", "gramme", "grammes", "gram", "grams"]),
+    (
+        "kg",
+        &[
+            "kg",
+            "kilo",
+            "kilos",
+            "kilogramme",
+            "kilogrammes",
+            "kilogram",
+            "kilograms",
+        ],
+    ),
+    (
+        "ml",
+        &[
+            "ml",
+            "millilitre",
+            "millilitres",
+            "milliliter",
+            "milliliters",
+        ],
+    ),
+    ("cl", &["cl", "centilitre", "centilitres"]),
+    ("dl", &["dl", "décilitre", "décilitres"]),
+    ("l", &["l", "litre", "litres", "liter", "liters"]),
+    ("tsp", &["tsp", "teaspoon", "teaspoons", "cc", "càc", "cac"]),
+    (
+        "tbsp",
+        &[
+            "tbsp",
+            "tbs",
+            "tablespoon",
+            "tablespoons",
+            "cs",
+            "càs",
+            "cas",
+        ],
+    ),
+    ("cup", &["cup", "cups", "tasse", "tasses"]),
+    ("oz", &["oz", "ounce", "ounces"]),
+    ("fl oz", &["floz"]),
+    ("lb", &["lb", "lbs", "pound", "pounds"]),
+    ("pinch", &["pinch", "pinches", "pincée", "pincées"]),
+    ("clove", &["clove", "cloves", "gousse", "gousses"]),
+    ("slice", &["slice", "slices", "tranche", "tranches"]),
+    (
+        "can",
+        &["can", "cans", "boîte", "boîtes", "boite", "boites"],
+    ),
+    ("sachet", &["sachet", "sachets", "packet", "packets"]),
+    (
+        "bunch",
+        &["bunch", "bunches", "botte", "bottes", "bouquet", "bouquets"],
+    ),
+];
+
+static FRACTIONS: &[(char, &str)] = &[
+    ('½', " 1/2"),
+    ('⅓', " 1/3"),
+    ('⅔', " 2/3"),
+    ('¼', " 1/4"),
+    ('¾', " 3/4"),
+    ('⅛', " 1/8"),
+];
+
+impl StructuredIngredient {
+    pub fn parse(text: &str) -> StructuredIngredient {
+        let mut text = text.trim().to_string();
+        for (fraction, replacement) in FRACTIONS {
+            text = text.replace(*fraction, replacement);
+        }
+        let mut tokens: Vec<String> =
+            text.split_whitespace()
+                .map(split_glued_unit)
+                .fold(vec![], |mut tokens, mut t| {
+                    tokens.append(&mut t);
+                    tokens
+                });
+        tokens.reverse();
+
+        let (quantity, max_quantity) = parse_quantity(&mut tokens);
+        let unit = match quantity {
+            Some(_) => parse_unit(&mut tokens),
+            None => None,
+        };
+        if unit.is_some() || quantity.is_some() {
+            skip_preposition(&mut tokens);
+        }
+        tokens.reverse();
+
+        let (name, note) = split_note(&tokens.join(" "));
+        StructuredIngredient {
+            quantity,
+            max_quantity,
+            unit,
+            name,
+            note,
+        }
+    }
+
+    pub fn parse_all(ingredients: &[String]) -> Vec<StructuredIngredient> {
+        ingredients
+            .iter()
+            .map(|i| StructuredIngredient::parse(i))
+            .collect()
+    }
+}
+
+pub fn get_canonical_unit(unit: &str) -> Option<&'static str> {
+    let unit = unit.trim_end_matches('.').to_lowercase();
+    UNITS
+        .iter()
+        .find(|(_, spellings)| spellings.contains(&unit.as_str()))
+        .map(|(canonical, _)| *canonical)
+}
+
+/// Returns the text following the leading quantity, e.g. "g de beurre" for
+/// "150 g de beurre" or "eggs" for "2-3 eggs".
+pub fn strip_quantity(text: &str) -> &str {
+    let is_amount =
+        |c: char| c.is_ascii_digit() || c == '.' || c == ',' || c == '/' || c == '-' || c == '–';
+    let mut rest = text.trim_start();
+    let mut has_amount = false;
+    loop {
+        let end = rest
+            .find(|c: char| !(is_amount(c) || FRACTIONS.iter().any(|(f, _)| *f == c)))
+            .unwrap_or(rest.len());
+        if end > 0 {
+            has_amount = true;
+            rest = rest[end..].trim_start();
+            continue;
+        }
+        if !has_amount {
+            return rest;
+        }
+        let separator = ["à ", "to ", "or ", "ou "]
+            .iter()
+            .find(|separator| rest.starts_with(*separator))
+            .map(|separator| rest[separator.len()..].trim_start())
+            .filter(|next| next.starts_with(|c: char| c.is_ascii_digit()));
+        match separator {
+            Some(next) => rest = next,
+            None => return rest,
+        }
+    }
+}
+
+/// Writes a quantity the way recipes do: "2", "1 1/2", "1/3", "0.2" or "250".
+pub fn format_quantity(quantity: f64) -> String {
+    if quantity >= 10.0 {
+        return format!("{}", quantity.round());
+    }
+    let whole = quantity.trunc();
+    let fraction = quantity - whole;
+    if fraction < 0.02 {
+        return format!("{}", whole);
+    }
+    if fraction > 0.98 {
+        return format!("{}", whole + 1.0);
+    }
+    let common = [
+        (0.25, "1/4"),
+        (1.0 / 3.0, "1/3"),
+        (0.5, "1/2"),
+        (2.0 / 3.0, "2/3"),
+        (0.75, "3/4"),
+    ]
+    .iter()
+    .find(|(value, _)| (fraction - value).abs() < 0.02)
+    .map(|(_, text)| *text);
+    match common {
+        Some(text) if whole == 0.0 => String::from(text),
+        Some(text) => format!("{} {}", whole, text),
+        None => format!("{}", (quantity * 10.0).round() / 10.0),
+    }
+}
+
+/// Lowercased words without accents nor plural mark, so that "Œufs" matches
+/// "oeuf", "tomatoes" matches "tomato" and "cherries" matches "cherry".
+pub fn get_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let word: String = word.to_lowercase().chars().map(fold_accent).collect();
+            let word = word.replace('œ', "oe");
+            if let Some(singular) = word.strip_suffix("ies").filter(|s| s.len() > 2) {
+                return format!("{}y", singular);
+            }
+            match word.strip_suffix("es") {
+                Some(singular) if singular.len() > 3 && singular.ends_with('o') => {
+                    String::from(singular)
+                }
+                _ => match word.strip_suffix(|c| c == 's' || c == 'x') {
+                    Some(singular) if singular.len() > 2 => String::from(singular),
+                    _ => word,
+                },
+            }
+        })
+        .collect()
+}
+
+fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'â' | 'ä' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'î' | 'ï' => 'i',
+        'ô' | 'ö' => 'o',
+        'ù' | 'û' | 'ü' => 'u',
+        'ç' => 'c',
+        _ => c,
+    }
+}
+
+/// "150g" is split into "150" and "g" when the suffix is a known unit.
+fn split_glued_unit(token: &str) -> Vec<String> {
+    let digits = token
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(token.len());
+    if digits > 0 && digits < token.len() && get_canonical_unit(&token[digits..]).is_some() {
+        return vec![token[..digits].to_string(), token[digits..].to_string()];
+    }
+    vec![token.to_string()]
+}
+
+fn parse_number(token: &str) -> Option<f64> {
+    if let Some(index) = token.find('/') {
+        let numerator = parse_decimal(&token[..index])?;
+        let denominator = parse_decimal(&token[index + 1..])?;
+        if denominator == 0.0 {
+            return None;
+        }
+        return Some(numerator / denominator);
+    }
+    parse_decimal(token)
+}
+
+/// Reads digits with at most one decimal point or comma, so that words such
+/// as "nan" or "inf" and exponents are not taken for quantities.
+fn parse_decimal(token: &str) -> Option<f64> {
+    let is_decimal = token.chars().any(|c| c.is_ascii_digit())
+        && token
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+        && token.matches(['.', ',']).count() <= 1;
+    if !is_decimal {
+        return None;
+    }
+    token
+        .replace(',', ".")
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite())
+}
+
+/// Reads "1", "1.5", "1,5", "1/2", "1 1/2" from the end of the reversed tokens.
+fn parse_amount(tokens: &mut Vec<String>) -> Option<f64> {
+    let mut amount = parse_number(tokens.last()?)?;
+    tokens.pop();
+    if let Some(next) = tokens.last() {
+        if next.contains('/') {
+            if let Some(fraction) = parse_number(next) {
+                amount += fraction;
+                tokens.pop();
+            }
+        }
+    }
+    Some(amount)
+}
+
+fn parse_quantity(tokens: &mut Vec<String>) -> (Option<f64>, Option<f64>) {
+    let range = tokens.last().and_then(|t| {
+        let mut bounds = t.splitn(2, '-');
+        let min = parse_number(bounds.next()?)?;
+        let max = parse_number(bounds.next()?)?;
+        Some((min, max))
+    });
+    if let Some((min, max)) = range {
+        tokens.pop();
+        return (Some(min), Some(max));
+    }
+
+    let quantity = parse_amount(tokens);
+    if quantity.is_none() {
+        return (None, None);
+    }
+    let is_range = tokens
+        .last()
+        .map(|t| t == "-" || t == "à" || t == "to" || t == "or" || t == "ou")
+        .unwrap_or(false);
+    if is_range {
+        let separator = tokens.pop();
+        match parse_amount(tokens) {
+            Some(max) => return (quantity, Some(max)),
+            None => tokens.extend(separator),
+        }
+    }
+    (quantity, None)
+}
+
+fn parse_unit(tokens: &mut Vec<String>) -> Option<String> {
+    let token = tokens.last()?.to_lowercase();
+    if token.starts_with("cuill") || token == "c." || token == "c" {
+        // "cuillère à soupe", "cuillerées à café", "c. à s."
+        let size = tokens
+            .get(tokens.len().saturating_sub(3))
+            .map(|t| t.to_lowercase());
+        let has_preposition = tokens
+            .get(tokens.len().saturating_sub(2))
+            .map(|t| t == "à" || t == "a")
+            .unwrap_or(false);
+        if tokens.len() >= 3 && has_preposition {
+            let unit = match size.as_deref() {
+                Some("soupe") | Some("s.") | Some("s") => Some("tbsp"),
+                Some("café") | Some("cafe") | Some("c.") | Some("c") => Some("tsp"),
+                _ => None,
+            };
+            if let Some(unit) = unit {
+                tokens.truncate(tokens.len() - 3);
+                return Some(String::from(unit));
+            }
+        }
+        return None;
+    }
+    if token == "fl" && tokens.len() >= 2 {
+        let next = tokens[tokens.len() - 2].to_lowercase();
+        if next.trim_end_matches('.') == "oz" {
+            tokens.truncate(tokens.len() - 2);
+            return Some(String::from("fl oz"));
+        }
+    }
+    let unit = get_canonical_unit(&token)?;
+    tokens.pop();
+    Some(String::from(unit))
+}
+
+fn skip_preposition(tokens: &mut Vec<String>) {
+    let last = match tokens.last_mut() {
+        Some(last) => last,
+        None => return,
+    };
+    if last == "de" || last == "of" {
+        tokens.pop();
+        return;
+    }
+    for prefix in &["d'", "d’"] {
+        if last.starts_with(prefix) && last.len() > prefix.len() {
+            *last = last[prefix.len()..].to_string();
+            return;
+        }
+    }
+}
+
+fn split_note(text: &str) -> (String, Option<String>) {
+    let index = [", ", "(", " en "]
+        .iter()
+        .filter_map(|separator| text.find(separator))
+        .min();
+    match index {
+        Some(index) if index > 0 => {
+            let note = text[index..]
+                .trim_start_matches(',')
+                .trim()
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .trim();
+            let note = if note.is_empty() {
+                None
+            } else {
+                Some(note.to_string())
+            };
+            (text[..index].trim().to_string(), note)
+        }
+        _ => (text.to_string(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ingredient(
+        quantity: Option<f64>,
+        max_quantity: Option<f64>,
+        unit: Option<&str>,
+        name: &str,
+        note: Option<&str>,
+    ) -> StructuredIngredient {
+        StructuredIngredient {
+            quantity,
+            max_quantity,
+            unit: unit.map(String::from),
+            name: String::from(name),
+            note: note.map(String::from),
+        }
+    }
+
+    #[test]
+    fn parsing_french_ingredients() {
+        assert_eq!(
+            StructuredIngredient::parse("150 g de beurre en dés"),
+            ingredient(Some(150.0), None, Some("g"), "beurre", Some("en dés"))
+        );
+        assert_eq!(
+            StructuredIngredient::parse("8 cl d'eau ou de lait tiède"),
+            ingredient(Some(8.0), None, Some("cl"), "eau ou de lait tiède", None)
+        );
+        assert_eq!(
+            StructuredIngredient::parse("1/2 cuillère à café de sel"),
+            ingredient(Some(0.5), None, Some("tsp"), "sel", None)
+        );
+        assert_eq!(
+            StructuredIngredient::parse("3 cuillères à soupe de sucre (si pâte sucrée)"),
+            ingredient(
+                Some(3.0),
+                None,
+                Some("tbsp"),
+                "sucre",
+                Some("si pâte sucrée")
+            )
+        );
+        assert_eq!(
+            StructuredIngredient::parse("1,5 kg de pommes de terre"),
+            ingredient(Some(1.5), None, Some("kg"), "pommes de terre", None)
+        );
+    }
+
+    #[test]
+    fn parsing_english_ingredients() {
+        assert_eq!(
+            StructuredIngredient::parse("1 1/2 cups flour"),
+            ingredient(Some(1.5), None, Some("cup"), "flour", None)
+        );
+        assert_eq!(
+            StructuredIngredient::parse("1 cup unsalted butter, softened"),
+            ingredient(
+                Some(1.0),
+                None,
+                Some("cup"),
+                "unsalted butter",
+                Some("softened")
+            )
+        );
+        assert_eq!(
+            StructuredIngredient::parse("200g plain flour"),
+            ingredient(Some(200.0), None, Some("g"), "plain flour", None)
+        );
+        assert_eq!(
+            StructuredIngredient::parse("¾ cup sugar"),
+            ingredient(Some(0.75), None, Some("cup"), "sugar", None)
+        );
+    }
+
+    #[test]
+    fn parsing_ranges() {
+        assert_eq!(
+            StructuredIngredient::parse("2-3 eggs"),
+            ingredient(Some(2.0), Some(3.0), None, "eggs", None)
+        );
+        assert_eq!(
+            StructuredIngredient::parse("2 à 3 gousses d'ail"),
+            ingredient(Some(2.0), Some(3.0), Some("clove"), "ail", None)
+        );
+    }
+
+    #[test]
+    fn parsing_ingredients_without_quantity() {
+        assert_eq!(
+            StructuredIngredient::parse("Sel, poivre"),
+            ingredient(None, None, None, "Sel", Some("poivre"))
+        );
+        assert_eq!(
+            StructuredIngredient::parse("salt and pepper"),
+            ingredient(None, None, None, "salt and pepper", None)
+        );
+    }
+
+    #[test]
+    fn stripping_quantity() {
+        assert_eq!(strip_quantity("150 g de beurre"), "g de beurre");
+        assert_eq!(strip_quantity("1 1/2 cups flour"), "cups flour");
+        assert_eq!(strip_quantity("2 à 3 gousses d'ail"), "gousses d'ail");
+        assert_eq!(strip_quantity("200g plain flour"), "g plain flour");
+        assert_eq!(strip_quantity("Sel, poivre"), "Sel, poivre");
+    }
+
+    #[test]
+    fn parsing_only_digits_as_quantities() {
+        for text in &[
+            "nan eggs",
+            "inf eggs",
+            "infinity eggs",
+            "+1e9 eggs",
+            "1.2.3 eggs",
+        ] {
+            assert_eq!(StructuredIngredient::parse(text).quantity, None, "{}", text);
+        }
+        assert_eq!(
+            StructuredIngredient::parse("1e3 g sugar").name,
+            "1e3 g sugar"
+        );
+        assert_eq!(
+            StructuredIngredient::parse("1,5 l milk").quantity,
+            Some(1.5)
+        );
+    }
+
+    #[test]
+    fn formatting_quantity() {
+        assert_eq!(format_quantity(2.0), "2");
+        assert_eq!(format_quantity(1.5), "1 1/2");
+        assert_eq!(format_quantity(1.0 / 3.0), "1/3");
+        assert_eq!(format_quantity(0.2), "0.2");
+        assert_eq!(format_quantity(266.66), "267");
+    }
+}
@@ -0,0 +1,173 @@
+use crate::domain::recipes::models::recipe::Recipe;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RecipeSortField {
+    CreatedAt,
+    UpdatedAt,
+    Title,
+    /// Preparation plus cooking time, recipes without any time last.
+    TotalTime,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RecipeSort {
+    pub field: RecipeSortField,
+    pub direction: SortDirection,
+}
+
+impl Default for RecipeSort {
+    /// Oldest recipes first, which is the order they were added in.
+    fn default() -> Self {
+        RecipeSort {
+            field: RecipeSortField::CreatedAt,
+            direction: SortDirection::Asc,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RecipePage {
+    pub recipes: Vec<Recipe>,
+    /// Cursors of the recipes of the page, in the same order.
+    pub cursors: Vec<RecipeCursor>,
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub total_count: usize,
+}
+
+/// The value a recipe is sorted by, e.g. its creation date in microseconds
+/// since the epoch. Recipes without any time have no total time.
+#[derive(Clone, PartialEq, Debug)]
+pub enum RecipeSortKey {
+    CreatedAt(u64),
+    UpdatedAt(u64),
+    Title(String),
+    TotalTime(Option<i32>),
+}
+
+impl RecipeSortKey {
+    pub fn field(&self) -> RecipeSortField {
+        match self {
+            RecipeSortKey::CreatedAt(_) => RecipeSortField::CreatedAt,
+            RecipeSortKey::UpdatedAt(_) => RecipeSortField::UpdatedAt,
+            RecipeSortKey::Title(_) => RecipeSortField::Title,
+            RecipeSortKey::TotalTime(_) => RecipeSortField::TotalTime,
+        }
+    }
+}
+
+/// Where a recipe stands in a sorted list: the recipes following it have a
+/// further sort key, or the same one and a greater id.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RecipeCursor {
+    pub key: RecipeSortKey,
+    pub id: String,
+}
+
+static CURSOR_PREFIX: &str = "recipe:";
+
+/// Opaque cursor pointing at the recipe.
+pub fn encode_cursor(cursor: &RecipeCursor) -> String {
+    let key = match &cursor.key {
+        RecipeSortKey::CreatedAt(micros) => format!("c:{}", micros),
+        RecipeSortKey::UpdatedAt(micros) => format!("u:{}", micros),
+        RecipeSortKey::Title(title) => format!("t:{}", title),
+        RecipeSortKey::TotalTime(Some(minutes)) => format!("m:{}", minutes),
+        RecipeSortKey::TotalTime(None) => String::from("m:"),
+    };
+    format!("{}{}:{}", CURSOR_PREFIX, cursor.id, key)
+        .bytes()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub fn decode_cursor(cursor: &str) -> Option<RecipeCursor> {
+    let bytes = cursor
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [_, _] => u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok(),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    let text = String::from_utf8(bytes).ok()?;
+    // Ids have no colon, unlike titles which come last.
+    let (id, key) = text.strip_prefix(CURSOR_PREFIX)?.split_once(':')?;
+    let key = match key.split_once(':')? {
+        ("c", micros) => RecipeSortKey::CreatedAt(micros.parse().ok()?),
+        ("u", micros) => RecipeSortKey::UpdatedAt(micros.parse().ok()?),
+        ("t", title) => RecipeSortKey::Title(String::from(title)),
+        ("m", "") => RecipeSortKey::TotalTime(None),
+        ("m", minutes) => RecipeSortKey::TotalTime(Some(minutes.parse().ok()?)),
+        _ => return None,
+    };
+    if id.is_empty() {
+        return None;
+    }
+    Some(RecipeCursor {
+        key,
+        id: String::from(id),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decoding_an_encoded_cursor() {
+        // given
+        let cursors = vec![
+            RecipeCursor {
+                key: RecipeSortKey::CreatedAt(1_600_000_000_000_000),
+                id: String::from("a1"),
+            },
+            RecipeCursor {
+                key: RecipeSortKey::Title(String::from("Pâtes : à la carbonara")),
+                id: String::from("a2"),
+            },
+            RecipeCursor {
+                key: RecipeSortKey::TotalTime(None),
+                id: String::from("a3"),
+            },
+        ];
+
+        // when
+        let decoded: Vec<Option<RecipeCursor>> = cursors
+            .iter()
+            .map(|c| decode_cursor(&encode_cursor(c)))
+            .collect();
+
+        // then
+        assert_eq!(encode_cursor(&cursors[2]), "7265636970653a61333a6d3a");
+        assert_eq!(decoded, cursors.into_iter().map(Some).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn rejecting_invalid_cursors() {
+        // given
+        let cursors = [
+            "",
+            "zz",
+            "7265636970653a",
+            "636f6c6c3a31",
+            "72é",
+            // recipe:42, an old position cursor
+            "7265636970653a3432",
+            // recipe:a1:m:-, with a malformed time
+            "7265636970653a61313a6d3a2d",
+        ];
+
+        // when
+        let decoded: Vec<Option<RecipeCursor>> = cursors.iter().map(|c| decode_cursor(c)).collect();
+
+        // then
+        assert_eq!(decoded, vec![None; 7]);
+    }
+}
@@ -1,6 +1,35 @@
+use crate::domain::recipes::models::ingredient::StructuredIngredient;
 use uuid::Uuid;
 
-#[derive(PartialEq, Debug)]
+/// Who can read a recipe besides its owner. Unlisted recipes can be read by
+/// anyone knowing their id, public ones are meant to be listed as well.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum RecipeVisibility {
+    Private,
+    Unlisted,
+    Public,
+}
+
+impl RecipeVisibility {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecipeVisibility::Private => "private",
+            RecipeVisibility::Unlisted => "unlisted",
+            RecipeVisibility::Public => "public",
+        }
+    }
+
+    pub fn parse(visibility: &str) -> Option<RecipeVisibility> {
+        match visibility {
+            "private" => Some(RecipeVisibility::Private),
+            "unlisted" => Some(RecipeVisibility::Unlisted),
+            "public" => Some(RecipeVisibility::Public),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct Recipe {
     pub id: Uuid,
     pub user_id: String,
@@ -13,6 +42,91 @@ pub struct Recipe {
     pub category: Option<String>,
     pub cuisine: Option<String>,
     pub instructions: Vec<String>,
+    /// Section heading (e.g. "For the sauce") of each instruction, by position.
+    pub instruction_sections: Vec<Option<String>>,
     pub ingredients: Vec<String>,
+    /// Section heading (e.g. "For the dough") of each ingredient, by position.
+    pub ingredient_sections: Vec<Option<String>>,
+    /// Each ingredient split into quantity, unit, name and note, by position.
+    pub structured_ingredients: Vec<StructuredIngredient>,
+    pub tags: Vec<String>,
+    pub visibility: RecipeVisibility,
+    /// Set when the recipe belongs to the library of the household of its owner.
+    pub household_id: Option<String>,
     pub imported_from: Option<String>,
 }
+
+impl Recipe {
+    pub fn is_visible_to(&self, user_id: Option<&str>) -> bool {
+        self.visibility != RecipeVisibility::Private || user_id == Some(self.user_id.as_str())
+    }
+}
+
+/// Builds the recipes of the unit tests, private to "user" and empty unless
+/// told otherwise.
+#[cfg(test)]
+pub struct RecipeBuilder {
+    recipe: Recipe,
+}
+
+#[cfg(test)]
+impl RecipeBuilder {
+    pub fn new(title: &str) -> RecipeBuilder {
+        RecipeBuilder {
+            recipe: Recipe {
+                id: Uuid::new_v4(),
+                user_id: String::from("user"),
+                title: String::from(title),
+                description: None,
+                cook_time_in_minute: None,
+                prep_time_in_minute: None,
+                image_url: None,
+                recipe_yield: None,
+                category: None,
+                cuisine: None,
+                instructions: vec![],
+                instruction_sections: vec![],
+                ingredients: vec![],
+                ingredient_sections: vec![],
+                structured_ingredients: vec![],
+                tags: vec![],
+                visibility: RecipeVisibility::Private,
+                household_id: None,
+                imported_from: None,
+            },
+        }
+    }
+
+    pub fn id(mut self, id: &str) -> RecipeBuilder {
+        self.recipe.id = Uuid::parse_str(id).unwrap();
+        self
+    }
+
+    pub fn prep_time_in_minute(mut self, prep_time_in_minute: Option<i32>) -> RecipeBuilder {
+        self.recipe.prep_time_in_minute = prep_time_in_minute;
+        self
+    }
+
+    pub fn cook_time_in_minute(mut self, cook_time_in_minute: Option<i32>) -> RecipeBuilder {
+        self.recipe.cook_time_in_minute = cook_time_in_minute;
+        self
+    }
+
+    pub fn recipe_yield(mut self, recipe_yield: Option<&str>) -> RecipeBuilder {
+        self.recipe.recipe_yield = recipe_yield.map(String::from);
+        self
+    }
+
+    /// Sets the ingredients, without section, and their parsed form.
+    pub fn ingredients(mut self, ingredients: &[&str]) -> RecipeBuilder {
+        let ingredients: Vec<String> = ingredients.iter().map(|i| String::from(*i)).collect();
+        self.recipe.ingredient_sections = vec![None; ingredients.len()];
+        self.recipe.structured_ingredients = StructuredIngredient::parse_all(&ingredients);
+        self.recipe.ingredients = ingredients;
+        self
+    }
+
+    pub fn build(self) -> Recipe {
+        self.recipe
+    }
+}
@@ -0,0 +1,16 @@
+use crate::domain::recipes::models::recipe::Recipe;
+
+#[derive(PartialEq, Debug)]
+pub struct RecipeFieldChange {
+    pub field: String,
+    pub current: Option<String>,
+    pub imported: Option<String>,
+    /// The owner changed this field since the last import, so it is kept as is.
+    pub locally_edited: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RecipeResync {
+    pub recipe: Recipe,
+    pub changes: Vec<RecipeFieldChange>,
+}
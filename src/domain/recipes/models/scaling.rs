@@ -0,0 +1,159 @@
+use crate::domain::recipes::errors::RecipeError;
+use crate::domain::recipes::models::ingredient::{
+    format_quantity, strip_quantity, StructuredIngredient,
+};
+use crate::domain::recipes::models::recipe::Recipe;
+
+#[derive(PartialEq, Debug)]
+pub struct ScaledIngredient {
+    pub text: String,
+    pub ingredient: StructuredIngredient,
+    /// False when the line has no quantity and was left untouched, e.g. "salt".
+    pub scaled: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RecipeScaling {
+    pub servings: i32,
+    pub factor: f64,
+    pub ingredients: Vec<ScaledIngredient>,
+}
+
+/// Reads the number of servings of a yield such as "6 personnes" or "Serves 4-6".
+pub fn get_servings(recipe_yield: &str) -> Option<f64> {
+    let start = recipe_yield.find(|c: char| c.is_ascii_digit())?;
+    let digits = &recipe_yield[start..];
+    let end = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    digits[..end]
+        .parse()
+        .ok()
+        .filter(|servings| *servings > 0.0)
+}
+
+pub fn scale_recipe(recipe: &Recipe, servings: i32) -> Result<RecipeScaling, RecipeError> {
+    if servings <= 0 {
+        return Err(RecipeError::RecipeServingsInvalid);
+    }
+    let current = recipe
+        .recipe_yield
+        .as_deref()
+        .and_then(get_servings)
+        .ok_or(RecipeError::RecipeYieldUnknown)?;
+    let factor = f64::from(servings) / current;
+
+    let ingredients = recipe
+        .ingredients
+        .iter()
+        .zip(recipe.structured_ingredients.iter())
+        .map(|(text, ingredient)| scale_ingredient(text, ingredient, factor))
+        .collect();
+    Ok(RecipeScaling {
+        servings,
+        factor,
+        ingredients,
+    })
+}
+
+fn scale_ingredient(
+    text: &str,
+    ingredient: &StructuredIngredient,
+    factor: f64,
+) -> ScaledIngredient {
+    let quantity = match ingredient.quantity {
+        Some(quantity) => quantity * factor,
+        None => {
+            return ScaledIngredient {
+                text: String::from(text),
+                ingredient: ingredient.clone(),
+                scaled: false,
+            }
+        }
+    };
+    let max_quantity = ingredient.max_quantity.map(|max| max * factor);
+    let amount = match max_quantity {
+        Some(max) => format!("{}-{}", format_quantity(quantity), format_quantity(max)),
+        None => format_quantity(quantity),
+    };
+    let rest = strip_quantity(text);
+    ScaledIngredient {
+        text: format!("{} {}", amount, rest).trim_end().to_string(),
+        ingredient: StructuredIngredient {
+            quantity: Some(quantity),
+            max_quantity,
+            ..ingredient.clone()
+        },
+        scaled: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::recipes::models::recipe::RecipeBuilder;
+
+    fn recipe(recipe_yield: Option<&str>, ingredients: &[&str]) -> Recipe {
+        RecipeBuilder::new("recipe")
+            .recipe_yield(recipe_yield)
+            .ingredients(ingredients)
+            .build()
+    }
+
+    #[test]
+    fn reading_servings() {
+        assert_eq!(get_servings("6 personnes"), Some(6.0));
+        assert_eq!(get_servings("Serves 4-6"), Some(4.0));
+        assert_eq!(get_servings("Pour 0 personne"), None);
+        assert_eq!(get_servings("une tarte"), None);
+    }
+
+    #[test]
+    fn scaling_recipe() {
+        // given
+        let recipe = recipe(
+            Some("4 personnes"),
+            &[
+                "150 g de beurre en dés",
+                "1 1/2 cups flour",
+                "2-3 eggs",
+                "Sel, poivre",
+            ],
+        );
+
+        // when
+        let scaling = scale_recipe(&recipe, 6).unwrap();
+
+        // then
+        assert_eq!(scaling.factor, 1.5);
+        let texts: Vec<&str> = scaling
+            .ingredients
+            .iter()
+            .map(|i| i.text.as_str())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "225 g de beurre en dés",
+                "2 1/4 cups flour",
+                "3-4 1/2 eggs",
+                "Sel, poivre"
+            ]
+        );
+        let scaled: Vec<bool> = scaling.ingredients.iter().map(|i| i.scaled).collect();
+        assert_eq!(scaled, vec![true, true, true, false]);
+        assert_eq!(scaling.ingredients[0].ingredient.quantity, Some(225.0));
+    }
+
+    #[test]
+    fn scaling_recipe_without_yield() {
+        // given
+        let recipe = recipe(None, &["150 g de beurre"]);
+
+        // when
+        let scaling = scale_recipe(&recipe, 6);
+
+        // then
+        assert!(matches!(scaling, Err(RecipeError::RecipeYieldUnknown)));
+    }
+}
@@ -0,0 +1,12 @@
+use crate::domain::recipes::models::recipe::Recipe;
+
+#[derive(PartialEq, Debug)]
+pub struct RecipeSearchResult {
+    pub recipe: Recipe,
+    /// Relevance of the recipe, titles weighting more than descriptions,
+    /// ingredients and instructions.
+    pub rank: f32,
+    /// HTML escaped excerpt of the recipe with the matching words wrapped in
+    /// `<mark>`.
+    pub snippet: String,
+}
@@ -0,0 +1,30 @@
+/// What a user a recipe is shared with can do besides reading it.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ShareRole {
+    Viewer,
+    Editor,
+}
+
+impl ShareRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ShareRole::Viewer => "viewer",
+            ShareRole::Editor => "editor",
+        }
+    }
+
+    pub fn parse(role: &str) -> Option<ShareRole> {
+        match role {
+            "viewer" => Some(ShareRole::Viewer),
+            "editor" => Some(ShareRole::Editor),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RecipeShare {
+    pub user_id: String,
+    pub email: String,
+    pub role: ShareRole,
+}
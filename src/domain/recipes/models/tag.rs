@@ -0,0 +1,62 @@
+/// Tags are compared case-insensitively, so they are stored trimmed and lowercased.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return None;
+    }
+    Some(tag)
+}
+
+/// Normalizes the tags and removes the duplicates, keeping their order.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized_tags: Vec<String> = vec![];
+    for tag in tags.iter().filter_map(|t| normalize_tag(t)) {
+        if !normalized_tags.contains(&tag) {
+            normalized_tags.push(tag);
+        }
+    }
+    normalized_tags
+}
+
+/// Splits schema.org `keywords` such as "dessert, chocolat" into tags.
+pub fn get_tags_from_keywords(keywords: &[String]) -> Vec<String> {
+    let tags: Vec<String> = keywords
+        .iter()
+        .flat_map(|k| k.split(','))
+        .map(String::from)
+        .collect();
+    normalize_tags(&tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizing_tag() {
+        assert_eq!(normalize_tag("  Dessert "), Some(String::from("dessert")));
+        assert_eq!(normalize_tag("  "), None);
+    }
+
+    #[test]
+    fn getting_tags_from_keywords() {
+        // given
+        let keywords = vec![
+            String::from("Gâteau, chocolat,, dessert"),
+            String::from("chocolat"),
+        ];
+
+        // when
+        let tags = get_tags_from_keywords(&keywords);
+
+        // then
+        assert_eq!(
+            tags,
+            vec![
+                String::from("gâteau"),
+                String::from("chocolat"),
+                String::from("dessert")
+            ]
+        );
+    }
+}
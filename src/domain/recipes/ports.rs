@@ -1,3 +1,4 @@
 pub mod dao;
+pub mod fetcher;
 pub mod image_store;
 pub mod parser;
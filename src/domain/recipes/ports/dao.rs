@@ -1,4 +1,9 @@
-use crate::domain::recipes::models::recipe::Recipe;
+use crate::domain::recipes::models::facet::RecipeFacets;
+use crate::domain::recipes::models::ingredient::StructuredIngredient;
+use crate::domain::recipes::models::page::{RecipeCursor, RecipeSort};
+use crate::domain::recipes::models::recipe::{Recipe, RecipeVisibility};
+use crate::domain::recipes::models::search::RecipeSearchResult;
+use crate::domain::recipes::models::share::{RecipeShare, ShareRole};
 use std::error::Error;
 
 #[derive(PartialEq, Debug)]
@@ -13,19 +18,123 @@ pub struct NewRecipe<'a> {
     pub prep_time_in_minute: Option<&'a i32>,
     pub cook_time_in_minute: Option<&'a i32>,
     pub instructions: Vec<&'a str>,
+    pub instruction_sections: Vec<Option<&'a str>>,
     pub ingredients: Vec<&'a str>,
+    pub ingredient_sections: Vec<Option<&'a str>>,
+    pub structured_ingredients: &'a [StructuredIngredient],
+    pub tags: Vec<&'a str>,
     pub imported_from: Option<&'a str>,
     pub image_url: Option<&'a str>,
 }
 
+/// Which of the recipes of a user to list.
+#[derive(PartialEq, Debug, Default, Clone)]
+pub struct RecipeFilter {
+    /// Start of the title, case insensitive.
+    pub query: Option<String>,
+    /// Normalized tags the recipes must all have.
+    pub tags: Vec<String>,
+    /// The recipes shared with the user instead of their own and their
+    /// household's.
+    pub shared_with_me: bool,
+    /// Trimmed categories the recipes must have one of, any category if empty.
+    pub categories: Vec<String>,
+    /// Trimmed cuisines the recipes must have one of, any cuisine if empty.
+    pub cuisines: Vec<String>,
+    /// Longest preparation plus cooking time, leaving out the recipes without
+    /// any time.
+    pub max_total_time_in_minute: Option<i32>,
+    pub has_image: Option<bool>,
+}
+
 pub trait RecipeDao {
+    /// The recipes matching the filter in the given order.
     fn get_my_recipes(
         &self,
         user_id: &str,
-        query: Option<String>,
+        filter: &RecipeFilter,
+        sort: RecipeSort,
     ) -> Result<Vec<Recipe>, Box<dyn Error>>;
+    /// At most `limit` recipes matching the filter in the given order,
+    /// following the `after` cursor, each with its own cursor.
+    fn get_my_recipes_after(
+        &self,
+        user_id: &str,
+        filter: &RecipeFilter,
+        sort: RecipeSort,
+        after: Option<&RecipeCursor>,
+        limit: usize,
+    ) -> Result<Vec<(Recipe, RecipeCursor)>, Box<dyn Error>>;
+    fn count_my_recipes(
+        &self,
+        user_id: &str,
+        filter: &RecipeFilter,
+    ) -> Result<usize, Box<dyn Error>>;
+    /// Distinct trimmed categories and cuisines of the recipes matching the
+    /// filter, the most frequent first, each facet ignoring its own filter.
+    fn get_my_recipe_facets(
+        &self,
+        user_id: &str,
+        filter: &RecipeFilter,
+    ) -> Result<RecipeFacets, Box<dyn Error>>;
+    /// Full-text search over the recipes of the user and of their household,
+    /// the most relevant first.
+    fn search_recipes(
+        &self,
+        user_id: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<RecipeSearchResult>, Box<dyn Error>>;
     fn get_recipe(&self, id: String) -> Result<Recipe, Box<dyn Error>>;
+    /// The recipes having these ids, leaving out the unknown ones.
+    fn get_recipes(&self, ids: &[String]) -> Result<Vec<Recipe>, Box<dyn Error>>;
+    /// Deletes the recipe along with everything referring to it, all at once.
     fn delete_recipe(&self, id: String) -> Result<(), Box<dyn Error>>;
-    fn add_recipe(&self, new_recipe: NewRecipe) -> Result<Recipe, Box<dyn Error>>;
-    fn update_recipe(&self, new_recipe: Recipe) -> Result<Recipe, Box<dyn Error>>;
+    /// Whether users other than this one planned the recipe in their meal plan.
+    fn is_planned_by_other_users(&self, id: &str, user_id: &str) -> Result<bool, Box<dyn Error>>;
+    /// Adds the recipe along with the snapshot of the website it was imported
+    /// from, if any, all at once.
+    fn add_recipe(
+        &self,
+        new_recipe: NewRecipe,
+        import_snapshot: Option<&Recipe>,
+    ) -> Result<Recipe, Box<dyn Error>>;
+    /// Replaces the recipe, all at once, and its import snapshot when given.
+    fn update_recipe(
+        &self,
+        new_recipe: Recipe,
+        import_snapshot: Option<&Recipe>,
+    ) -> Result<Recipe, Box<dyn Error>>;
+    fn add_tag(&self, recipe_id: &str, tag: &str) -> Result<(), Box<dyn Error>>;
+    fn remove_tag(&self, recipe_id: &str, tag: &str) -> Result<(), Box<dyn Error>>;
+    fn set_visibility(
+        &self,
+        recipe_id: &str,
+        visibility: RecipeVisibility,
+    ) -> Result<(), Box<dyn Error>>;
+    fn set_household(
+        &self,
+        recipe_id: &str,
+        household_id: Option<&str>,
+    ) -> Result<(), Box<dyn Error>>;
+    fn get_shares(&self, recipe_id: &str) -> Result<Vec<RecipeShare>, Box<dyn Error>>;
+    fn get_share_role(
+        &self,
+        recipe_id: &str,
+        user_id: &str,
+    ) -> Result<Option<ShareRole>, Box<dyn Error>>;
+    /// The ids among these of the recipes shared with the user.
+    fn get_shared_recipe_ids(
+        &self,
+        recipe_ids: &[String],
+        user_id: &str,
+    ) -> Result<Vec<String>, Box<dyn Error>>;
+    fn share_recipe(
+        &self,
+        recipe_id: &str,
+        user_id: &str,
+        role: ShareRole,
+    ) -> Result<(), Box<dyn Error>>;
+    fn unshare_recipe(&self, recipe_id: &str, user_id: &str) -> Result<(), Box<dyn Error>>;
+    fn get_import_snapshot(&self, recipe_id: String) -> Result<Option<Recipe>, Box<dyn Error>>;
 }
@@ -0,0 +1,5 @@
+use std::error::Error;
+
+pub trait Fetcher {
+    fn fetch(&self, url: &str) -> Result<String, Box<dyn Error>>;
+}
@@ -3,4 +3,10 @@ use std::error::Error;
 
 pub trait Parser {
     fn parse_recipe(&self, url: String, user_id: String) -> Result<Recipe, Box<dyn Error>>;
+    fn parse_recipe_from_html(
+        &self,
+        html: String,
+        url: Option<String>,
+        user_id: String,
+    ) -> Result<Recipe, Box<dyn Error>>;
 }
@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod interactors;
+pub mod models;
+pub mod ports;
@@ -0,0 +1,30 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ShoppingListError {
+    #[error("Shopping list is not yours")]
+    ShoppingListDoNotbelongToUser,
+    #[error("Shopping list not found")]
+    ShoppingListNotFound,
+    #[error("Shopping list must have a name")]
+    ShoppingListWithoutName,
+    #[error("Item is not in the shopping list")]
+    ShoppingListItemNotFound,
+    #[error("Servings must be a positive number")]
+    ShoppingListServingsInvalid,
+    #[error("unknown error")]
+    Unknown,
+}
+
+impl ShoppingListError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ShoppingListError::ShoppingListDoNotbelongToUser => "SHOPPING_LIST_NOT_YOURS",
+            ShoppingListError::ShoppingListNotFound => "SHOPPING_LIST_NOT_FOUND",
+            ShoppingListError::ShoppingListWithoutName => "MISSING_NAME",
+            ShoppingListError::ShoppingListItemNotFound => "ITEM_NOT_FOUND",
+            ShoppingListError::ShoppingListServingsInvalid => "INVALID_SERVINGS",
+            ShoppingListError::Unknown => "UNKNOWN",
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::domain::meal_plans::interactors::meal_plan::check_meal_plan_range;
+use crate::domain::meal_plans::models::meal_plan::MealPlan;
+use crate::domain::meal_plans::ports::dao::MealPlanDao;
+use crate::domain::pantries::ports::dao::PantryDao;
+use crate::domain::recipes::errors::RecipeError;
+use crate::domain::recipes::interactors::recipe::can_read_recipe;
+use crate::domain::recipes::models::recipe::Recipe;
+use crate::domain::recipes::ports::dao::RecipeDao;
+use crate::domain::shopping_lists::errors::ShoppingListError;
+use crate::domain::shopping_lists::models::shopping_list::{
+    get_shopping_list_items, subtract_pantry, RecipeIdServings, RecipeServings, ShoppingList,
+};
+use crate::domain::shopping_lists::ports::dao::{NewShoppingList, ShoppingListDao};
+use crate::domain::users::ports::dao::UserDao;
+
+use chrono::NaiveDate;
+use std::error::Error;
+use uuid::Uuid;
+
+pub struct ShoppingListInteractor {
+    pub shopping_list_dao: Box<dyn ShoppingListDao>,
+    pub meal_plan_dao: Box<dyn MealPlanDao>,
+    pub pantry_dao: Box<dyn PantryDao>,
+    pub recipe_dao: Box<dyn RecipeDao>,
+    pub user_dao: Box<dyn UserDao>,
+}
+
+impl ShoppingListInteractor {
+    pub fn get_my_shopping_lists(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<ShoppingList>, Box<dyn Error>> {
+        self.shopping_list_dao.get_my_shopping_lists(&user_id)
+    }
+
+    pub fn get_shopping_list(
+        &self,
+        id: String,
+        user_id: String,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        self.get_own_shopping_list(&id, &user_id)
+    }
+
+    /// Lists what to buy to cook the recipes the user can read, without what
+    /// the pantry has on hand `today` when `from_pantry` is true.
+    pub fn create_shopping_list(
+        &self,
+        user_id: String,
+        name: String,
+        recipes: Vec<RecipeIdServings>,
+        from_pantry: bool,
+        today: NaiveDate,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        let recipes = recipes
+            .into_iter()
+            .map(
+                |RecipeIdServings {
+                     recipe_id,
+                     servings,
+                 }| {
+                    self.get_readable_recipe(recipe_id, &user_id)
+                        .map(|recipe| RecipeServings { recipe, servings })
+                },
+            )
+            .collect::<Result<Vec<RecipeServings>, Box<dyn Error>>>()?;
+        self.add_shopping_list(user_id, name, recipes, from_pantry, today)
+    }
+
+    /// Lists what to buy to cook the meals planned between the two dates, both
+    /// included, skipping the recipes the user can no longer read.
+    pub fn create_shopping_list_from_meal_plan(
+        &self,
+        user_id: String,
+        name: String,
+        start: NaiveDate,
+        end: NaiveDate,
+        from_pantry: bool,
+        today: NaiveDate,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        check_meal_plan_range(start, end)?;
+        let entries = self.meal_plan_dao.get_entries(&user_id, start, end)?;
+        let mut recipes = vec![];
+        for entry in MealPlan::new(start, end, entries).entries {
+            match self.get_readable_recipe(entry.recipe_id, &user_id) {
+                Ok(recipe) => recipes.push(RecipeServings {
+                    recipe,
+                    servings: entry.servings,
+                }),
+                Err(error) => match error.downcast_ref::<RecipeError>() {
+                    Some(RecipeError::RecipeNotFound) => (),
+                    _ => return Err(error),
+                },
+            }
+        }
+        self.add_shopping_list(user_id, name, recipes, from_pantry, today)
+    }
+
+    pub fn check_item(
+        &self,
+        id: String,
+        user_id: String,
+        item_id: String,
+        checked: bool,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        let shopping_list = self.get_own_shopping_list(&id, &user_id)?;
+        if !shopping_list
+            .items
+            .iter()
+            .any(|item| item.id.to_hyphenated().to_string() == item_id)
+        {
+            return Err(Box::new(ShoppingListError::ShoppingListItemNotFound));
+        }
+        self.shopping_list_dao.set_item_checked(&item_id, checked)?;
+        self.shopping_list_dao.get_shopping_list(&id)
+    }
+
+    pub fn delete_shopping_list(&self, id: String, user_id: String) -> Result<(), Box<dyn Error>> {
+        self.get_own_shopping_list(&id, &user_id)?;
+        self.shopping_list_dao.delete_shopping_list(&id)
+    }
+
+    fn add_shopping_list(
+        &self,
+        user_id: String,
+        name: String,
+        recipes: Vec<RecipeServings>,
+        from_pantry: bool,
+        today: NaiveDate,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Box::new(ShoppingListError::ShoppingListWithoutName));
+        }
+        if recipes.iter().any(|r| r.servings.unwrap_or(1) <= 0) {
+            return Err(Box::new(ShoppingListError::ShoppingListServingsInvalid));
+        }
+        let mut items = get_shopping_list_items(&recipes);
+        if from_pantry {
+            let pantry: Vec<_> = self
+                .pantry_dao
+                .get_my_pantry(&user_id)?
+                .into_iter()
+                .filter(|item| !item.is_expired(today))
+                .collect();
+            items = subtract_pantry(items, &pantry);
+        }
+        self.shopping_list_dao.add_shopping_list(
+            NewShoppingList {
+                id: Uuid::new_v4().to_hyphenated().to_string().as_str(),
+                user_id: &user_id,
+                name,
+            },
+            &items,
+        )
+    }
+
+    /// The recipe if the user can read it, else as if it did not exist.
+    fn get_readable_recipe(&self, id: String, user_id: &str) -> Result<Recipe, Box<dyn Error>> {
+        let recipe = self.recipe_dao.get_recipe(id)?;
+        if !can_read_recipe(
+            self.recipe_dao.as_ref(),
+            self.user_dao.as_ref(),
+            &recipe,
+            Some(user_id),
+        )? {
+            return Err(Box::new(RecipeError::RecipeNotFound));
+        }
+        Ok(recipe)
+    }
+
+    fn get_own_shopping_list(
+        &self,
+        id: &str,
+        user_id: &str,
+    ) -> Result<ShoppingList, Box<dyn Error>> {
+        let shopping_list = self.shopping_list_dao.get_shopping_list(id)?;
+        if shopping_list.user_id != user_id {
+            return Err(Box::new(ShoppingListError::ShoppingListDoNotbelongToUser));
+        }
+        Ok(shopping_list)
+    }
+}
@@ -0,0 +1,649 @@
+use crate::domain::pantries::models::pantry_item::PantryItem;
+use crate::domain::recipes::models::conversion::add_quantities;
+use crate::domain::recipes::models::ingredient::{
+    format_quantity, get_words, StructuredIngredient,
+};
+use crate::domain::recipes::models::recipe::Recipe;
+use crate::domain::recipes::models::scaling::scale_recipe;
+use uuid::Uuid;
+
+/// Where an item is found in a shop, in the order of a usual walk through it.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum Aisle {
+    Produce,
+    Bakery,
+    Meat,
+    Fish,
+    Dairy,
+    Grocery,
+    Spices,
+    Frozen,
+    Drinks,
+    Other,
+}
+
+impl Aisle {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Aisle::Produce => "produce",
+            Aisle::Bakery => "bakery",
+            Aisle::Meat => "meat",
+            Aisle::Fish => "fish",
+            Aisle::Dairy => "dairy",
+            Aisle::Grocery => "grocery",
+            Aisle::Spices => "spices",
+            Aisle::Frozen => "frozen",
+            Aisle::Drinks => "drinks",
+            Aisle::Other => "other",
+        }
+    }
+
+    pub fn parse(aisle: &str) -> Option<Aisle> {
+        match aisle {
+            "produce" => Some(Aisle::Produce),
+            "bakery" => Some(Aisle::Bakery),
+            "meat" => Some(Aisle::Meat),
+            "fish" => Some(Aisle::Fish),
+            "dairy" => Some(Aisle::Dairy),
+            "grocery" => Some(Aisle::Grocery),
+            "spices" => Some(Aisle::Spices),
+            "frozen" => Some(Aisle::Frozen),
+            "drinks" => Some(Aisle::Drinks),
+            "other" => Some(Aisle::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Words of ingredient names and their aisle. The longest match wins, so that
+/// "noix de muscade" is spices rather than "noix" grocery, and then the first
+/// one, so that "sucre glace" is grocery rather than "glace" frozen.
+static AISLES: &[(Aisle, &[&str])] = &[
+    (
+        Aisle::Produce,
+        &[
+            "laitue",
+            "salade",
+            "lettuce",
+            "tomate",
+            "tomato",
+            "carotte",
+            "carrot",
+            "oignon",
+            "onion",
+            "ail",
+            "garlic",
+            "echalote",
+            "shallot",
+            "pomme",
+            "apple",
+            "potato",
+            "poireau",
+            "leek",
+            "courgette",
+            "zucchini",
+            "aubergine",
+            "eggplant",
+            "poivron",
+            "bell pepper",
+            "champignon",
+            "mushroom",
+            "citron",
+            "lemon",
+            "lime",
+            "orange",
+            "banane",
+            "banana",
+            "fraise",
+            "strawberry",
+            "persil",
+            "parsley",
+            "basilic",
+            "basil",
+            "coriandre",
+            "cilantro",
+            "menthe",
+            "mint",
+            "epinard",
+            "spinach",
+            "chou",
+            "cabbage",
+            "brocoli",
+            "broccoli",
+            "concombre",
+            "cucumber",
+            "avocat",
+            "avocado",
+            "gingembre",
+            "ginger",
+            "celeri",
+            "celery",
+        ],
+    ),
+    (
+        Aisle::Meat,
+        &[
+            "poulet", "chicken", "boeuf", "beef", "veau", "veal", "porc", "pork", "agneau", "lamb",
+            "jambon", "ham", "lardon", "bacon", "saucisse", "sausage", "dinde", "turkey", "canard",
+            "duck", "viande", "meat", "steak", "chorizo",
+        ],
+    ),
+    (
+        Aisle::Fish,
+        &[
+            "poisson",
+            "fish",
+            "saumon",
+            "salmon",
+            "thon",
+            "tuna",
+            "cabillaud",
+            "cod",
+            "crevette",
+            "shrimp",
+            "prawn",
+            "moule",
+            "mussel",
+            "sardine",
+            "truite",
+            "trout",
+        ],
+    ),
+    (
+        Aisle::Dairy,
+        &[
+            "lait",
+            "milk",
+            "beurre",
+            "butter",
+            "creme",
+            "cream",
+            "fromage",
+            "cheese",
+            "yaourt",
+            "yogurt",
+            "oeuf",
+            "egg",
+            "parmesan",
+            "mozzarella",
+            "gruyere",
+            "emmental",
+            "mascarpone",
+            "ricotta",
+            "feta",
+        ],
+    ),
+    (
+        Aisle::Bakery,
+        &["pain", "bread", "baguette", "brioche", "tortilla"],
+    ),
+    (
+        Aisle::Grocery,
+        &[
+            "farine",
+            "flour",
+            "sucre",
+            "sugar",
+            "riz",
+            "rice",
+            "pate",
+            "pasta",
+            "spaghetti",
+            "lentille",
+            "lentil",
+            "haricot",
+            "bean",
+            "huile",
+            "oil",
+            "olive",
+            "vinaigre",
+            "vinegar",
+            "moutarde",
+            "mustard",
+            "miel",
+            "honey",
+            "levure",
+            "yeast",
+            "chocolat",
+            "chocolate",
+            "noix",
+            "nut",
+            "amande",
+            "almond",
+            "peanut butter",
+            "beurre de cacahuete",
+            "bouillon",
+            "stock",
+        ],
+    ),
+    (
+        Aisle::Spices,
+        &[
+            "sel",
+            "salt",
+            "poivre",
+            "pepper",
+            "cumin",
+            "paprika",
+            "cannelle",
+            "cinnamon",
+            "muscade",
+            "noix de muscade",
+            "nutmeg",
+            "curry",
+            "thym",
+            "laurier",
+            "bay",
+            "origan",
+            "oregano",
+            "vanille",
+            "vanilla",
+            "epice",
+            "spice",
+        ],
+    ),
+    (
+        Aisle::Frozen,
+        &[
+            "surgele",
+            "frozen",
+            "glace",
+            "ice",
+            "ice cream",
+            "creme glacee",
+        ],
+    ),
+    (
+        Aisle::Drinks,
+        &[
+            "vin", "wine", "biere", "beer", "jus", "juice", "cidre", "cider", "rhum", "rum",
+            "cafe", "coffee", "tea",
+        ],
+    ),
+];
+
+pub fn get_aisle(name: &str) -> Aisle {
+    let words = get_words(name);
+    let mut found = (Aisle::Other, 0);
+    for (aisle, names) in AISLES {
+        for name in names.iter() {
+            let name = get_words(name);
+            if name.len() > found.1 && words.windows(name.len()).any(|w| w == &name[..]) {
+                found = (*aisle, name.len());
+            }
+        }
+    }
+    found.0
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ShoppingListItem {
+    pub id: Uuid,
+    /// What to buy, e.g. "1 1/2 kg tomates", or the ingredient line as
+    /// written when it has no quantity.
+    pub text: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub aisle: Aisle,
+    pub checked: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ShoppingList {
+    pub id: Uuid,
+    pub user_id: String,
+    pub name: String,
+    /// Items by aisle, in the order of a walk through the shop.
+    pub items: Vec<ShoppingListItem>,
+}
+
+impl ShoppingList {
+    pub fn get_aisles(&self) -> Vec<(Aisle, Vec<&ShoppingListItem>)> {
+        let mut aisles: Vec<(Aisle, Vec<&ShoppingListItem>)> = vec![];
+        for item in &self.items {
+            match aisles.last_mut() {
+                Some((aisle, items)) if *aisle == item.aisle => items.push(item),
+                _ => aisles.push((item.aisle, vec![item])),
+            }
+        }
+        aisles
+    }
+}
+
+/// A recipe to shop for and the servings to cook, its yield when None.
+#[derive(PartialEq, Debug)]
+pub struct RecipeServings {
+    pub recipe: Recipe,
+    pub servings: Option<i32>,
+}
+
+/// The id of a recipe to shop for and the servings to cook, its yield when
+/// None.
+#[derive(PartialEq, Debug)]
+pub struct RecipeIdServings {
+    pub recipe_id: String,
+    pub servings: Option<i32>,
+}
+
+/// Adds up the ingredients of the recipes, scaled to the servings when their
+/// yield is known. Quantities of the same ingredient are merged when their
+/// units can be added, and lines without quantity are listed as written.
+pub fn get_shopping_list_items(recipes: &[RecipeServings]) -> Vec<ShoppingListItem> {
+    let mut items: Vec<ShoppingListItem> = vec![];
+    for RecipeServings { recipe, servings } in recipes {
+        let scaling = servings.and_then(|servings| scale_recipe(recipe, servings).ok());
+        let lines: Vec<(String, StructuredIngredient)> = match scaling {
+            Some(scaling) => scaling
+                .ingredients
+                .into_iter()
+                .map(|i| (i.text, i.ingredient))
+                .collect(),
+            None => recipe
+                .ingredients
+                .iter()
+                .cloned()
+                .zip(recipe.structured_ingredients.iter().cloned())
+                .collect(),
+        };
+        for (text, ingredient) in lines {
+            add_item(&mut items, &text, &ingredient);
+        }
+    }
+    items.sort_by(|a, b| {
+        a.aisle
+            .cmp(&b.aisle)
+            .then(a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    items
+}
+
+fn add_item(items: &mut Vec<ShoppingListItem>, text: &str, ingredient: &StructuredIngredient) {
+    let key = get_words(&ingredient.name);
+    // Ranges such as "2-3 eggs" are bought at their upper bound.
+    let quantity = match ingredient.max_quantity.or(ingredient.quantity) {
+        Some(quantity) if !key.is_empty() => quantity,
+        _ => {
+            let name = if key.is_empty() {
+                text.trim()
+            } else {
+                &ingredient.name
+            };
+            let key = get_words(name);
+            if !items
+                .iter()
+                .any(|i| i.quantity.is_none() && get_words(&i.name) == key)
+            {
+                items.push(ShoppingListItem {
+                    id: Uuid::new_v4(),
+                    text: String::from(text.trim()),
+                    name: String::from(name),
+                    quantity: None,
+                    unit: None,
+                    aisle: get_aisle(name),
+                    checked: false,
+                });
+            }
+            return;
+        }
+    };
+    for item in items.iter_mut() {
+        let item_quantity = match item.quantity {
+            Some(item_quantity) if get_words(&item.name) == key => item_quantity,
+            _ => continue,
+        };
+        let sum = match (&item.unit, &ingredient.unit) {
+            (None, None) => Some((item_quantity + quantity, None)),
+            (Some(item_unit), Some(unit)) => {
+                add_quantities(item_quantity, item_unit, quantity, unit)
+                    .map(|(quantity, unit)| (quantity, Some(unit)))
+            }
+            _ => None,
+        };
+        if let Some((quantity, unit)) = sum {
+            item.text = get_text(quantity, unit.as_deref(), &item.name);
+            item.quantity = Some(quantity);
+            item.unit = unit;
+            return;
+        }
+    }
+    items.push(ShoppingListItem {
+        id: Uuid::new_v4(),
+        text: get_text(quantity, ingredient.unit.as_deref(), &ingredient.name),
+        name: ingredient.name.clone(),
+        quantity: Some(quantity),
+        unit: ingredient.unit.clone(),
+        aisle: get_aisle(&ingredient.name),
+        checked: false,
+    });
+}
+
+/// Removes what the pantry already has from the items. Quantities are
+/// subtracted when their units can be, and items are dropped when the pantry
+/// has enough of them or has some without tracking the quantity.
+pub fn subtract_pantry(
+    items: Vec<ShoppingListItem>,
+    pantry: &[PantryItem],
+) -> Vec<ShoppingListItem> {
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let key = get_words(&item.name);
+            pantry
+                .iter()
+                .filter(|stock| get_words(&stock.name) == key)
+                .try_fold(item, subtract_stock)
+        })
+        .collect()
+}
+
+fn subtract_stock(mut item: ShoppingListItem, stock: &PantryItem) -> Option<ShoppingListItem> {
+    let (quantity, stock_quantity) = match (item.quantity, stock.quantity) {
+        (Some(quantity), Some(stock_quantity)) => (quantity, stock_quantity),
+        _ => return None,
+    };
+    let rest = match (&item.unit, &stock.unit) {
+        (None, None) => Some((quantity - stock_quantity, None)),
+        (Some(unit), Some(stock_unit)) => {
+            add_quantities(quantity, unit, -stock_quantity, stock_unit)
+                .map(|(quantity, unit)| (quantity, Some(unit)))
+        }
+        _ => None,
+    };
+    match rest {
+        Some((rest, _)) if rest <= 0.0 => None,
+        Some((rest, unit)) => {
+            item.text = get_text(rest, unit.as_deref(), &item.name);
+            item.quantity = Some(rest);
+            item.unit = unit;
+            Some(item)
+        }
+        None => Some(item),
+    }
+}
+
+/// Units written in full, and their plural.
+static PLURAL_UNITS: &[(&str, &str)] = &[
+    ("cup", "cups"),
+    ("pinch", "pinches"),
+    ("clove", "cloves"),
+    ("slice", "slices"),
+    ("can", "cans"),
+    ("sachet", "sachets"),
+    ("bunch", "bunches"),
+];
+
+fn get_text(quantity: f64, unit: Option<&str>, name: &str) -> String {
+    let quantity_text = format_quantity(quantity);
+    let is_plural = quantity > 1.0 && quantity_text != "1";
+    match unit {
+        Some(unit) => {
+            let unit = PLURAL_UNITS
+                .iter()
+                .find(|(singular, _)| is_plural && *singular == unit)
+                .map_or(unit, |(_, plural)| *plural);
+            format!("{} {} {}", quantity_text, unit, name)
+        }
+        None => format!("{} {}", quantity_text, name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::recipes::models::recipe::RecipeBuilder;
+
+    fn recipe(recipe_yield: Option<&str>, ingredients: &[&str]) -> Recipe {
+        RecipeBuilder::new("recipe")
+            .recipe_yield(recipe_yield)
+            .ingredients(ingredients)
+            .build()
+    }
+
+    fn texts(items: &[ShoppingListItem]) -> Vec<&str> {
+        items.iter().map(|i| i.text.as_str()).collect()
+    }
+
+    #[test]
+    fn merging_ingredients_by_aisle() {
+        // given
+        let recipes = vec![
+            RecipeServings {
+                recipe: recipe(None, &["500 g de tomates", "2 oeufs", "Sel"]),
+                servings: None,
+            },
+            RecipeServings {
+                recipe: recipe(None, &["1 kg tomates", "3 Œufs", "sel", "1 cup flour"]),
+                servings: None,
+            },
+        ];
+
+        // when
+        let items = get_shopping_list_items(&recipes);
+
+        // then
+        assert_eq!(
+            texts(&items),
+            vec!["1 1/2 kg tomates", "5 oeufs", "1 cup flour", "Sel"]
+        );
+        let aisles: Vec<Aisle> = items.iter().map(|i| i.aisle).collect();
+        assert_eq!(
+            aisles,
+            vec![Aisle::Produce, Aisle::Dairy, Aisle::Grocery, Aisle::Spices]
+        );
+    }
+
+    #[test]
+    fn scaling_and_keeping_incompatible_units_apart() {
+        // given
+        let recipes = vec![
+            RecipeServings {
+                recipe: recipe(Some("2 personnes"), &["100 g de farine", "Un peu d'amour"]),
+                servings: Some(4),
+            },
+            RecipeServings {
+                recipe: recipe(None, &["1 cup farine"]),
+                servings: Some(4),
+            },
+        ];
+
+        // when
+        let items = get_shopping_list_items(&recipes);
+
+        // then
+        assert_eq!(
+            texts(&items),
+            vec!["200 g farine", "1 cup farine", "Un peu d'amour"]
+        );
+    }
+
+    #[test]
+    fn finding_aisles_of_whole_words() {
+        assert_eq!(get_aisle("nutmeg"), Aisle::Spices);
+        assert_eq!(get_aisle("noix de muscade"), Aisle::Spices);
+        assert_eq!(get_aisle("noix de pécan"), Aisle::Grocery);
+        assert_eq!(get_aisle("ailes de poulet"), Aisle::Meat);
+        assert_eq!(get_aisle("gousses d'ail"), Aisle::Produce);
+        assert_eq!(get_aisle("teaspoon leftovers"), Aisle::Other);
+        assert_eq!(get_aisle("green tea"), Aisle::Drinks);
+        assert_eq!(get_aisle("laitue"), Aisle::Produce);
+        assert_eq!(get_aisle("vinaigre balsamique"), Aisle::Grocery);
+        assert_eq!(get_aisle("peanut butter"), Aisle::Grocery);
+        assert_eq!(get_aisle("Strawberries"), Aisle::Produce);
+    }
+
+    #[test]
+    fn writing_units_in_the_plural() {
+        // given
+        let recipes = vec![RecipeServings {
+            recipe: recipe(None, &["1 cup flour", "1 cup flour", "1/2 cup milk"]),
+            servings: None,
+        }];
+
+        // when
+        let items = get_shopping_list_items(&recipes);
+
+        // then
+        assert_eq!(texts(&items), vec!["1/2 cup milk", "2 cups flour"]);
+    }
+
+    #[test]
+    fn grouping_items_by_aisle() {
+        // given
+        let shopping_list = ShoppingList {
+            id: Uuid::new_v4(),
+            user_id: String::from("user"),
+            name: String::from("Week"),
+            items: get_shopping_list_items(&[RecipeServings {
+                recipe: recipe(None, &["2 carottes", "1 oignon", "1 l de lait"]),
+                servings: None,
+            }]),
+        };
+
+        // when
+        let aisles = shopping_list.get_aisles();
+
+        // then
+        let sizes: Vec<(Aisle, usize)> = aisles.iter().map(|(a, i)| (*a, i.len())).collect();
+        assert_eq!(sizes, vec![(Aisle::Produce, 2), (Aisle::Dairy, 1)]);
+    }
+
+    #[test]
+    fn subtracting_pantry_stock() {
+        // given
+        let items = get_shopping_list_items(&[RecipeServings {
+            recipe: recipe(
+                None,
+                &[
+                    "1 kg de farine",
+                    "6 oeufs",
+                    "1 l de lait",
+                    "Sel",
+                    "2 cups sugar",
+                ],
+            ),
+            servings: None,
+        }]);
+        let stock = |name: &str, quantity: Option<f64>, unit: Option<&str>| PantryItem {
+            id: Uuid::new_v4(),
+            user_id: String::from("user"),
+            name: String::from(name),
+            quantity,
+            unit: unit.map(String::from),
+            expires_on: None,
+        };
+        let pantry = vec![
+            stock("farine", Some(250.0), Some("g")),
+            stock("Œufs", Some(6.0), None),
+            stock("lait", None, None),
+            stock("sel", None, None),
+            stock("sugar", Some(100.0), Some("g")),
+        ];
+
+        // when
+        let items = subtract_pantry(items, &pantry);
+
+        // then
+        assert_eq!(texts(&items), vec!["750 g farine", "2 cups sugar"]);
+    }
+}
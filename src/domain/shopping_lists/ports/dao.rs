@@ -0,0 +1,22 @@
+use crate::domain::shopping_lists::models::shopping_list::{ShoppingList, ShoppingListItem};
+use std::error::Error;
+
+#[derive(PartialEq, Debug)]
+pub struct NewShoppingList<'a> {
+    pub id: &'a str,
+    pub user_id: &'a str,
+    pub name: &'a str,
+}
+
+pub trait ShoppingListDao {
+    fn get_my_shopping_lists(&self, user_id: &str) -> Result<Vec<ShoppingList>, Box<dyn Error>>;
+    fn get_shopping_list(&self, id: &str) -> Result<ShoppingList, Box<dyn Error>>;
+    /// Saves the list with its items, keeping their order.
+    fn add_shopping_list(
+        &self,
+        new_shopping_list: NewShoppingList,
+        items: &[ShoppingListItem],
+    ) -> Result<ShoppingList, Box<dyn Error>>;
+    fn set_item_checked(&self, item_id: &str, checked: bool) -> Result<(), Box<dyn Error>>;
+    fn delete_shopping_list(&self, id: &str) -> Result<(), Box<dyn Error>>;
+}
@@ -10,6 +10,33 @@ pub enum UserError {
     UserNotFound,
     #[error("User already exists")]
     UserAlreadyExists,
+    #[error("User already belongs to a household")]
+    UserAlreadyInHousehold,
+    #[error("User does not belong to a household")]
+    UserNotInHousehold,
+    #[error("Household has no name")]
+    HouseholdWithoutName,
+    #[error("Household not found")]
+    HouseholdNotFound,
+    #[error("Invitation not found")]
+    InvitationNotFound,
     #[error("unknown error")]
     Unknown,
 }
+
+impl UserError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            UserError::MustBeLogged => "MUST_BE_LOGGED",
+            UserError::BadCredentials => "BAD_CREDENTIALS",
+            UserError::UserNotFound => "USER_NOT_FOUND",
+            UserError::UserAlreadyExists => "USER_ALREADY_EXISTS",
+            UserError::UserAlreadyInHousehold => "ALREADY_IN_HOUSEHOLD",
+            UserError::UserNotInHousehold => "NOT_IN_HOUSEHOLD",
+            UserError::HouseholdWithoutName => "MISSING_NAME",
+            UserError::HouseholdNotFound => "HOUSEHOLD_NOT_FOUND",
+            UserError::InvitationNotFound => "INVITATION_NOT_FOUND",
+            UserError::Unknown => "UNKNOWN",
+        }
+    }
+}
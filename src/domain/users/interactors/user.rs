@@ -1,5 +1,9 @@
+use crate::domain::recipes::models::conversion::UnitSystem;
+use crate::domain::users::errors::UserError;
+use crate::domain::users::models::household::Household;
 use crate::domain::users::models::user::User;
 use crate::domain::users::ports::dao::UserDao;
+use crate::domain::users::ports::household_dao::HouseholdDao;
 use ring::{digest, pbkdf2};
 use std::error::Error;
 use std::num::NonZeroU32;
@@ -12,6 +16,7 @@ type PasswordHash = [u8; HASH_LEN];
 
 pub struct UserInteractor {
     pub dao: Box<dyn UserDao>,
+    pub household_dao: Box<dyn HouseholdDao>,
 }
 
 impl UserInteractor {
@@ -32,6 +37,129 @@ impl UserInteractor {
             .signin(email, UserInteractor::hash_password(&password))
     }
 
+    pub fn get_unit_system(&self, user_id: &str) -> Result<Option<UnitSystem>, Box<dyn Error>> {
+        self.dao.get_unit_system(user_id)
+    }
+
+    pub fn set_unit_system(
+        &self,
+        user_id: &str,
+        unit_system: Option<UnitSystem>,
+    ) -> Result<Option<UnitSystem>, Box<dyn Error>> {
+        self.dao.set_unit_system(user_id, unit_system)?;
+        Ok(unit_system)
+    }
+
+    pub fn get_calendar_token(&self, user_id: &str) -> Result<Option<String>, Box<dyn Error>> {
+        self.dao.get_calendar_token(user_id)
+    }
+
+    /// Enables the calendar feed of the user with a new secret token, so that
+    /// the previous feed URL stops working, or disables it.
+    pub fn set_calendar_feed(
+        &self,
+        user_id: &str,
+        enabled: bool,
+    ) -> Result<Option<String>, Box<dyn Error>> {
+        let calendar_token = if enabled {
+            Some(Uuid::new_v4().to_simple().to_string())
+        } else {
+            None
+        };
+        self.dao
+            .set_calendar_token(user_id, calendar_token.as_deref())?;
+        Ok(calendar_token)
+    }
+
+    pub fn get_my_household(&self, user_id: &str) -> Result<Option<Household>, Box<dyn Error>> {
+        match self.dao.get_household_id(user_id)? {
+            Some(household_id) => Ok(Some(self.household_dao.get_household(&household_id)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn create_household(&self, user_id: &str, name: &str) -> Result<Household, Box<dyn Error>> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Box::new(UserError::HouseholdWithoutName));
+        }
+        if self.dao.get_household_id(user_id)?.is_some() {
+            return Err(Box::new(UserError::UserAlreadyInHousehold));
+        }
+        let id = Uuid::new_v4().to_hyphenated().to_string();
+        self.household_dao.add_household(&id, name)?;
+        self.dao.set_household_id(user_id, Some(&id))?;
+        self.household_dao.get_household(&id)
+    }
+
+    /// Invites the user having this email to join the household of the user.
+    pub fn invite_to_household(
+        &self,
+        user_id: &str,
+        email: &str,
+    ) -> Result<Household, Box<dyn Error>> {
+        let household_id = self
+            .dao
+            .get_household_id(user_id)?
+            .ok_or(UserError::UserNotInHousehold)?;
+        let household = self.household_dao.get_household(&household_id)?;
+        if household.members.iter().any(|member| member.email == email) {
+            return Err(Box::new(UserError::UserAlreadyInHousehold));
+        }
+        self.household_dao.add_invitation(&household_id, email)?;
+        Ok(household)
+    }
+
+    pub fn get_my_invitations(&self, user_id: &str) -> Result<Vec<Household>, Box<dyn Error>> {
+        let user = self.dao.get_user(user_id)?;
+        self.household_dao.get_invited_households(&user.email)
+    }
+
+    pub fn accept_invitation(
+        &self,
+        user_id: &str,
+        household_id: &str,
+    ) -> Result<Household, Box<dyn Error>> {
+        if self.dao.get_household_id(user_id)?.is_some() {
+            return Err(Box::new(UserError::UserAlreadyInHousehold));
+        }
+        let user = self.dao.get_user(user_id)?;
+        if !self
+            .household_dao
+            .delete_invitation(household_id, &user.email)?
+        {
+            return Err(Box::new(UserError::InvitationNotFound));
+        }
+        self.dao.set_household_id(user_id, Some(household_id))?;
+        self.household_dao.get_household(household_id)
+    }
+
+    pub fn decline_invitation(
+        &self,
+        user_id: &str,
+        household_id: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let user = self.dao.get_user(user_id)?;
+        if !self
+            .household_dao
+            .delete_invitation(household_id, &user.email)?
+        {
+            return Err(Box::new(UserError::InvitationNotFound));
+        }
+        Ok(())
+    }
+
+    /// Returns the id of the household left. The recipes the user moved to the
+    /// household stay in it.
+    pub fn leave_household(&self, user_id: &str) -> Result<String, Box<dyn Error>> {
+        let household_id = self
+            .dao
+            .get_household_id(user_id)?
+            .ok_or(UserError::UserNotInHousehold)?;
+        self.dao.set_household_id(user_id, None)?;
+        Ok(household_id)
+    }
+
     fn hash_password(password: &str) -> String {
         let mut to_store: PasswordHash = [0u8; HASH_LEN];
         pbkdf2::derive(
@@ -1 +1,2 @@
+pub mod household;
 pub mod user;
@@ -0,0 +1,11 @@
+use crate::domain::users::models::user::User;
+use uuid::Uuid;
+
+/// A group of users sharing a recipe library. A user belongs to at most one
+/// household.
+#[derive(PartialEq, Debug)]
+pub struct Household {
+    pub id: Uuid,
+    pub name: String,
+    pub members: Vec<User>,
+}
@@ -1 +1,2 @@
 pub mod dao;
+pub mod household_dao;
@@ -1,3 +1,4 @@
+use crate::domain::recipes::models::conversion::UnitSystem;
 use crate::domain::users::models::user::User;
 use std::error::Error;
 
@@ -12,4 +13,28 @@ pub trait UserDao {
     fn signin(&self, email: String, password_hash: String) -> Result<User, Box<dyn Error>>;
 
     fn get_user(&self, id: &str) -> Result<User, Box<dyn Error>>;
+
+    fn get_user_by_email(&self, email: &str) -> Result<User, Box<dyn Error>>;
+
+    fn get_household_id(&self, id: &str) -> Result<Option<String>, Box<dyn Error>>;
+
+    fn set_household_id(&self, id: &str, household_id: Option<&str>) -> Result<(), Box<dyn Error>>;
+
+    fn get_unit_system(&self, id: &str) -> Result<Option<UnitSystem>, Box<dyn Error>>;
+
+    fn set_unit_system(
+        &self,
+        id: &str,
+        unit_system: Option<UnitSystem>,
+    ) -> Result<(), Box<dyn Error>>;
+
+    fn get_user_by_calendar_token(&self, calendar_token: &str) -> Result<User, Box<dyn Error>>;
+
+    fn get_calendar_token(&self, id: &str) -> Result<Option<String>, Box<dyn Error>>;
+
+    fn set_calendar_token(
+        &self,
+        id: &str,
+        calendar_token: Option<&str>,
+    ) -> Result<(), Box<dyn Error>>;
 }
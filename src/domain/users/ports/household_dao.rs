@@ -0,0 +1,11 @@
+use crate::domain::users::models::household::Household;
+use std::error::Error;
+
+pub trait HouseholdDao {
+    fn get_household(&self, id: &str) -> Result<Household, Box<dyn Error>>;
+    fn add_household(&self, id: &str, name: &str) -> Result<(), Box<dyn Error>>;
+    fn get_invited_households(&self, email: &str) -> Result<Vec<Household>, Box<dyn Error>>;
+    fn add_invitation(&self, id: &str, email: &str) -> Result<(), Box<dyn Error>>;
+    /// Returns false when there was no such invitation.
+    fn delete_invitation(&self, id: &str, email: &str) -> Result<bool, Box<dyn Error>>;
+}
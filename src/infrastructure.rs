@@ -1,3 +1,4 @@
+pub mod http;
 pub mod parser;
 pub mod s3;
 pub mod sql;
@@ -0,0 +1,557 @@
+use crate::domain::recipes::errors::RecipeError;
+use crate::domain::recipes::ports::fetcher::Fetcher;
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{CONTENT_TYPE, LOCATION, RETRY_AFTER};
+use reqwest::redirect::Policy;
+use reqwest::{StatusCode, Url};
+use std::env;
+use std::error::Error;
+use std::io::Read;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::thread;
+use std::time::Duration;
+
+const CHARSET_SNIFFING_SIZE: usize = 1024;
+
+pub struct FetcherConfig {
+    pub timeout: Duration,
+    pub user_agent: String,
+    pub max_body_size: u64,
+    pub max_redirects: usize,
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on every other one.
+    pub retry_delay: Duration,
+    /// Longest wait between two tries, whatever the backoff.
+    pub max_retry_delay: Duration,
+    /// Longest wait over all the retries of a fetch, which holds a server
+    /// worker meanwhile. Retrying gives up rather than waiting longer.
+    pub max_total_retry_delay: Duration,
+    /// Lets the server fetch loopback and private addresses, for tests only.
+    pub allow_private_networks: bool,
+}
+
+pub struct ReqwestFetcher {
+    config: FetcherConfig,
+}
+
+impl Fetcher for ReqwestFetcher {
+    fn fetch(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        let mut url = Url::parse(url).map_err(|_| RecipeError::RecipeImportedWebsiteForbidden)?;
+        let mut retries = 0;
+        let mut retried_for = Duration::from_secs(0);
+        let mut redirects = 0;
+        loop {
+            // Redirects are followed by hand so that every target is checked.
+            let client = self.get_client(&url)?;
+            let response = client
+                .get(url.clone())
+                .send()
+                .map_err(|_| RecipeError::RecipeImportedWebsiteUnreachable)?;
+            let status = response.status();
+            if status == StatusCode::OK {
+                return self.read_body(response);
+            }
+            if status.is_redirection() && redirects < self.config.max_redirects {
+                let location = response
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|l| url.join(l).ok())
+                    .ok_or_else(|| RecipeError::RecipeImportedWebsiteBadStatus(status.as_u16()))?;
+                url = location;
+                redirects += 1;
+                continue;
+            }
+            let is_retryable = status == StatusCode::TOO_MANY_REQUESTS
+                || status == StatusCode::SERVICE_UNAVAILABLE;
+            let delay = self.get_retry_delay(&response, retries);
+            if !is_retryable
+                || retries >= self.config.max_retries
+                || retried_for + delay > self.config.max_total_retry_delay
+            {
+                return Err(Box::new(RecipeError::RecipeImportedWebsiteBadStatus(
+                    status.as_u16(),
+                )));
+            }
+            thread::sleep(delay);
+            retried_for += delay;
+            retries += 1;
+        }
+    }
+}
+
+impl ReqwestFetcher {
+    pub fn new(config: FetcherConfig) -> Self {
+        ReqwestFetcher { config }
+    }
+
+    /// A client connecting the url host to its checked addresses only, so that
+    /// a DNS answer changing after the check cannot lead to another address.
+    fn get_client(&self, url: &Url) -> Result<Client, Box<dyn Error>> {
+        let addresses = self.check_url(url)?;
+        let mut builder = Client::builder()
+            .timeout(self.config.timeout)
+            .user_agent(self.config.user_agent.as_str())
+            .redirect(Policy::none())
+            .no_proxy();
+        if let Some(domain) = url.domain() {
+            builder = builder.resolve_to_addrs(domain, &addresses);
+        }
+        let client = builder
+            .build()
+            .map_err(|_| RecipeError::RecipeImportedWebsiteUnreachable)?;
+        Ok(client)
+    }
+
+    fn check_url(&self, url: &Url) -> Result<Vec<SocketAddr>, Box<dyn Error>> {
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(Box::new(RecipeError::RecipeImportedWebsiteForbidden));
+        }
+        let addresses = url
+            .socket_addrs(|| None)
+            .map_err(|_| RecipeError::RecipeImportedWebsiteUnreachable)?;
+        let is_forbidden =
+            !self.config.allow_private_networks && addresses.iter().any(|a| is_private_ip(a.ip()));
+        if addresses.is_empty() || is_forbidden {
+            return Err(Box::new(RecipeError::RecipeImportedWebsiteForbidden));
+        }
+        Ok(addresses)
+    }
+
+    fn get_retry_delay(&self, response: &Response, retries: u32) -> Duration {
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        match retry_after {
+            Some(seconds) => Duration::from_secs(seconds),
+            None => self.get_backoff_delay(retries),
+        }
+    }
+
+    /// The retry delay doubled on every retry, up to the maximum delay.
+    fn get_backoff_delay(&self, retries: u32) -> Duration {
+        let max_delay = self.config.max_retry_delay;
+        2u32.checked_pow(retries)
+            .and_then(|factor| self.config.retry_delay.checked_mul(factor))
+            .map_or(max_delay, |delay| delay.min(max_delay))
+    }
+
+    fn read_body(&self, response: Response) -> Result<String, Box<dyn Error>> {
+        let max_body_size = self.config.max_body_size;
+        if response.content_length().unwrap_or(0) > max_body_size {
+            return Err(Box::new(RecipeError::RecipeImportedWebsiteTooLarge));
+        }
+        let charset = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(get_charset);
+
+        let mut body = vec![];
+        response
+            .take(max_body_size + 1)
+            .read_to_end(&mut body)
+            .map_err(|_| RecipeError::RecipeImportedWebsiteUnreachable)?;
+        if body.len() as u64 > max_body_size {
+            return Err(Box::new(RecipeError::RecipeImportedWebsiteTooLarge));
+        }
+
+        let encoding = charset
+            .or_else(|| sniff_charset(&body))
+            .and_then(|c| Encoding::for_label(c.as_bytes()))
+            .unwrap_or(UTF_8);
+        let (text, _, _) = encoding.decode(&body);
+        Ok(text.into_owned())
+    }
+}
+
+impl Default for FetcherConfig {
+    fn default() -> Self {
+        let timeout = env::var("IMPORT_TIMEOUT_IN_SECOND")
+            .ok()
+            .and_then(|t| t.parse().ok())
+            .unwrap_or(10);
+        let user_agent = match env::var("IMPORT_USER_AGENT") {
+            Ok(user_agent) => user_agent,
+            Err(_e) => String::from("Mozilla/5.0 (compatible; Rustipe/1.0)"),
+        };
+        let max_body_size = env::var("IMPORT_MAX_BODY_SIZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(5 * 1024 * 1024);
+        let max_retries = env::var("IMPORT_MAX_RETRIES")
+            .ok()
+            .and_then(|r| r.parse().ok())
+            .unwrap_or(2);
+        FetcherConfig {
+            timeout: Duration::from_secs(timeout),
+            user_agent,
+            max_body_size,
+            max_redirects: 5,
+            max_retries,
+            retry_delay: Duration::from_millis(250),
+            max_retry_delay: Duration::from_secs(1),
+            max_total_retry_delay: Duration::from_secs(2),
+            allow_private_networks: false,
+        }
+    }
+}
+
+impl Default for ReqwestFetcher {
+    fn default() -> Self {
+        ReqwestFetcher::new(FetcherConfig::default())
+    }
+}
+
+fn is_private_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_ipv4(ip),
+        IpAddr::V6(ip) => is_private_ipv6(ip),
+    }
+}
+
+fn is_private_ipv4(ip: Ipv4Addr) -> bool {
+    let octets = ip.octets();
+    ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // 0.0.0.0/8 ("this network") and 100.64.0.0/10 (carrier-grade NAT)
+        || octets[0] == 0
+        || (octets[0] == 100 && (octets[1] & 0xc0) == 64)
+        // 198.18.0.0/15 (benchmarking) and 240.0.0.0/4 (reserved, with broadcast)
+        || (octets[0] == 198 && (octets[1] & 0xfe) == 18)
+        || octets[0] >= 240
+}
+
+fn is_private_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ip) = get_embedded_ipv4(ip) {
+        return is_private_ipv4(ip);
+    }
+    let segments = ip.segments();
+    ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // fc00::/7 (unique local) and fe80::/10 (link local)
+        || (segments[0] & 0xfe00) == 0xfc00
+        || (segments[0] & 0xffc0) == 0xfe80
+}
+
+/// The IPv4 address of an IPv4-mapped (`::ffff:a.b.c.d`), IPv4-compatible
+/// (`::a.b.c.d`) or NAT64 (`64:ff9b::a.b.c.d`) IPv6 address.
+fn get_embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = ip.segments();
+    let is_mapped = segments[..5].iter().all(|s| *s == 0) && segments[5] == 0xffff;
+    let is_compatible = segments[..6].iter().all(|s| *s == 0);
+    let is_nat64 = segments[..2] == [0x64, 0xff9b] && segments[2..6].iter().all(|s| *s == 0);
+    if !is_mapped && !is_compatible && !is_nat64 {
+        return None;
+    }
+    let [a, b] = segments[6].to_be_bytes();
+    let [c, d] = segments[7].to_be_bytes();
+    Some(Ipv4Addr::new(a, b, c, d))
+}
+
+fn get_charset(content_type: &str) -> Option<String> {
+    content_type
+        .split(';')
+        .map(|p| p.trim())
+        .find(|p| p.to_lowercase().starts_with("charset="))
+        .map(|p| {
+            p["charset=".len()..]
+                .trim_matches(|c: char| c == '"' || c == '\'')
+                .to_string()
+        })
+}
+
+/// Looks for a `<meta charset>` (or `http-equiv` content type) at the start of the page.
+fn sniff_charset(body: &[u8]) -> Option<String> {
+    let head = &body[..body.len().min(CHARSET_SNIFFING_SIZE)];
+    let head = String::from_utf8_lossy(head).to_lowercase();
+    let start = head.find("charset=")? + "charset=".len();
+    let charset: String = head[start..]
+        .trim_start_matches(['"', '\''])
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    if charset.is_empty() {
+        return None;
+    }
+    Some(charset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    fn serve(responses: Vec<Vec<u8>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buffer = [0; 4096];
+                let _ = stream.read(&mut buffer);
+                stream.write_all(&response).unwrap();
+            }
+        });
+        format!("http://{}/recipe", address)
+    }
+
+    fn response(status: &str, headers: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n",
+            status,
+            body.len(),
+            headers
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    fn get_fetcher() -> ReqwestFetcher {
+        ReqwestFetcher::new(FetcherConfig {
+            timeout: Duration::from_secs(5),
+            user_agent: String::from("Rustipe/test"),
+            max_body_size: 1024,
+            max_redirects: 1,
+            max_retries: 2,
+            retry_delay: Duration::from_millis(10),
+            max_retry_delay: Duration::from_millis(30),
+            max_total_retry_delay: Duration::from_secs(1),
+            allow_private_networks: true,
+        })
+    }
+
+    #[test]
+    fn fetching_page() {
+        // given
+        let url = serve(vec![response(
+            "200 OK",
+            "Content-Type: text/html; charset=utf-8\r\n",
+            "<html>Crème brûlée</html>".as_bytes(),
+        )]);
+
+        // when
+        let html = get_fetcher().fetch(&url).expect("Can fetch page");
+
+        // then
+        assert_eq!(html, "<html>Crème brûlée</html>");
+    }
+
+    #[test]
+    fn fetching_page_with_meta_charset() {
+        // given
+        let url = serve(vec![response(
+            "200 OK",
+            "Content-Type: text/html\r\n",
+            b"<html><meta charset=\"iso-8859-1\">Cr\xe8me br\xfbl\xe9e</html>",
+        )]);
+
+        // when
+        let html = get_fetcher().fetch(&url).expect("Can fetch page");
+
+        // then
+        assert_eq!(
+            html,
+            "<html><meta charset=\"iso-8859-1\">Crème brûlée</html>"
+        );
+    }
+
+    #[test]
+    fn fetching_page_retries_when_unavailable() {
+        // given
+        let url = serve(vec![
+            response("503 Service Unavailable", "", b""),
+            response("429 Too Many Requests", "Retry-After: 0\r\n", b""),
+            response("200 OK", "", b"<html></html>"),
+        ]);
+
+        // when
+        let html = get_fetcher().fetch(&url).expect("Can fetch page");
+
+        // then
+        assert_eq!(html, "<html></html>");
+    }
+
+    #[test]
+    fn fetching_page_gives_up_retrying_after_the_total_delay() {
+        // given
+        let url = serve(vec![response(
+            "503 Service Unavailable",
+            "Retry-After: 120\r\n",
+            b"",
+        )]);
+
+        // when
+        let error = get_fetcher().fetch(&url).unwrap_err();
+
+        // then
+        assert!(matches!(
+            error.downcast_ref::<RecipeError>(),
+            Some(RecipeError::RecipeImportedWebsiteBadStatus(503))
+        ));
+    }
+
+    #[test]
+    fn fetching_missing_page() {
+        // given
+        let url = serve(vec![response("404 Not Found", "", b"")]);
+
+        // when
+        let error = get_fetcher().fetch(&url).unwrap_err();
+
+        // then
+        assert!(matches!(
+            error.downcast_ref::<RecipeError>(),
+            Some(RecipeError::RecipeImportedWebsiteBadStatus(404))
+        ));
+    }
+
+    #[test]
+    fn fetching_too_large_page() {
+        // given
+        let url = serve(vec![response("200 OK", "", &[b'a'; 2048])]);
+
+        // when
+        let error = get_fetcher().fetch(&url).unwrap_err();
+
+        // then
+        assert!(matches!(
+            error.downcast_ref::<RecipeError>(),
+            Some(RecipeError::RecipeImportedWebsiteTooLarge)
+        ));
+    }
+
+    #[test]
+    fn fetching_page_after_redirect() {
+        // given
+        let url = serve(vec![
+            response("301 Moved Permanently", "Location: /new-recipe\r\n", b""),
+            response("200 OK", "", b"<html></html>"),
+        ]);
+
+        // when
+        let html = get_fetcher().fetch(&url).expect("Can fetch page");
+
+        // then
+        assert_eq!(html, "<html></html>");
+    }
+
+    #[test]
+    fn fetching_private_addresses_is_forbidden() {
+        // given
+        let fetcher = ReqwestFetcher::default();
+        let urls = vec![
+            "http://127.0.0.1:8000/graphql",
+            "http://localhost/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://10.0.0.1/",
+            "http://172.16.0.1/",
+            "http://192.168.1.1/",
+            "http://[::1]/",
+            "http://[fd00::1]/",
+            "http://[::ffff:127.0.0.1]/",
+            "http://[::127.0.0.1]/",
+            "http://[64:ff9b::a9fe:a9fe]/",
+            "file:///etc/passwd",
+            "ftp://example.com/recipe",
+        ];
+
+        for url in urls {
+            // when
+            let error = fetcher.fetch(url).unwrap_err();
+
+            // then
+            assert!(
+                matches!(
+                    error.downcast_ref::<RecipeError>(),
+                    Some(RecipeError::RecipeImportedWebsiteForbidden)
+                ),
+                "{} should be forbidden",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn retry_delay_is_doubled_up_to_the_maximum() {
+        // given
+        let fetcher = get_fetcher();
+
+        // when
+        let delays: Vec<Duration> = vec![0, 1, 2, 12, 40]
+            .into_iter()
+            .map(|retries| fetcher.get_backoff_delay(retries))
+            .collect();
+
+        // then
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(10),
+                Duration::from_millis(20),
+                Duration::from_millis(30),
+                Duration::from_millis(30),
+                Duration::from_millis(30),
+            ]
+        );
+    }
+
+    #[test]
+    fn fetching_page_by_host_name() {
+        // given
+        let url =
+            serve(vec![response("200 OK", "", b"<html></html>")]).replace("127.0.0.1", "localhost");
+
+        // when
+        let html = get_fetcher().fetch(&url).expect("Can fetch page");
+
+        // then
+        assert_eq!(html, "<html></html>");
+    }
+
+    #[test]
+    fn public_addresses_are_not_private() {
+        assert!(!is_private_ip("93.184.216.34".parse().unwrap()));
+        assert!(!is_private_ip("2606:2800:220:1::".parse().unwrap()));
+        assert!(!is_private_ip("::ffff:93.184.216.34".parse().unwrap()));
+        assert!(!is_private_ip("64:ff9b::5db8:d822".parse().unwrap()));
+    }
+
+    #[test]
+    fn reserved_addresses_are_private() {
+        let addresses = vec![
+            "100.64.0.1",
+            "224.0.0.1",
+            "239.255.255.250",
+            "198.18.0.1",
+            "198.19.255.255",
+            "240.0.0.1",
+            "255.255.255.255",
+            "fe80::1",
+            "ff02::1",
+            "ff0e::1",
+            "64:ff9b::7f00:1",
+            "64:ff9b::a00:1",
+            "::7f00:1",
+            "::a00:1",
+            "::ffff:192.168.1.1",
+        ];
+
+        for address in addresses {
+            assert!(
+                is_private_ip(address.parse().unwrap()),
+                "{} should be private",
+                address
+            );
+        }
+    }
+}
@@ -1,83 +1,112 @@
 use crate::domain::recipes::errors::RecipeError;
-use crate::domain::recipes::models::recipe::Recipe;
+use crate::domain::recipes::models::ingredient::StructuredIngredient;
+use crate::domain::recipes::models::recipe::{Recipe, RecipeVisibility};
+use crate::domain::recipes::models::tag::get_tags_from_keywords;
+use crate::domain::recipes::ports::fetcher::Fetcher;
 use crate::domain::recipes::ports::parser::Parser;
+use crate::infrastructure::http::fetcher::ReqwestFetcher;
 
 use iso8601_duration::Duration;
 use select::document::Document;
-use select::node::Data;
+use select::node::{Data, Node};
 use select::predicate::{Attr, Name, Predicate};
 use serde_json::Value;
 use std::error::Error;
 use uuid::Uuid;
 
-pub struct SelectParser {}
+pub struct SelectParser {
+    fetcher: Box<dyn Fetcher + Send + Sync>,
+}
 
 impl Parser for SelectParser {
     fn parse_recipe(&self, url: String, user_id: String) -> Result<Recipe, Box<dyn Error>> {
-        let html = self.get_html(&url)?;
-        self.parse_from_json_ld(&url, html.as_str(), user_id)
+        let html = self.fetcher.fetch(&url)?;
+        self.parse_recipe_from_html(html, Some(url), user_id)
+    }
+
+    fn parse_recipe_from_html(
+        &self,
+        html: String,
+        url: Option<String>,
+        user_id: String,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let url = url.as_deref();
+        match self.parse_from_json_ld(url, html.as_str(), user_id.clone()) {
+            Err(error) if self.is_recipe_missing(&*error) => self
+                .parse_from_microdata(url, html.as_str(), user_id)
+                .map_err(|microdata_error| {
+                    if self.is_recipe_missing(&*microdata_error) {
+                        error
+                    } else {
+                        microdata_error
+                    }
+                }),
+            result => result,
+        }
     }
 }
 
 impl SelectParser {
-    pub fn new() -> Self {
-        SelectParser {}
+    pub fn new(fetcher: Box<dyn Fetcher + Send + Sync>) -> Self {
+        SelectParser { fetcher }
     }
-    fn get_html(&self, url: &str) -> Result<String, Box<dyn Error>> {
-        let response = reqwest::blocking::get(url)?;
-        if response.status() != 200 {
-            return Err(Box::new(RecipeError::RecipeNotImported));
-        }
-        let body = response.text()?;
-        Ok(body)
+
+    fn is_recipe_missing(&self, error: &(dyn Error + 'static)) -> bool {
+        matches!(
+            error.downcast_ref::<RecipeError>(),
+            Some(RecipeError::RecipeImportedWebsiteNotSupported)
+                | Some(RecipeError::RecipeImportedWithMalformedData)
+        )
     }
 
     fn parse_from_json_ld(
         &self,
-        url: &str,
+        url: Option<&str>,
         html: &str,
         user_id: String,
     ) -> Result<Recipe, Box<dyn Error>> {
         let document = Document::from(html);
-        let mut recipe: Value = document
+        let mut has_malformed_data = false;
+        let recipe: Option<Value> = document
             .find(Name("script").and(Attr("type", "application/ld+json")))
             .map(|n| n.text())
-            .map(|t| {
-                let v: Value = serde_json::from_str(&t).unwrap();
-                v
+            .filter_map(|t| match serde_json::from_str::<Value>(&t) {
+                Ok(v) => Some(v),
+                Err(_) => {
+                    has_malformed_data = true;
+                    None
+                }
             })
-            .find(|json| json["@type"] == "Recipe" || json[0]["@type"] == "Recipe")
-            .expect("Website not supported");
+            .find_map(|json| self.find_recipe_node(&json).cloned());
+        let recipe = match recipe {
+            Some(recipe) => recipe,
+            None if has_malformed_data => {
+                return Err(Box::new(RecipeError::RecipeImportedWithMalformedData))
+            }
+            None => return Err(Box::new(RecipeError::RecipeImportedWebsiteNotSupported)),
+        };
 
-        if recipe.is_array() {
-            recipe = recipe[0].clone();
-        }
+        let title = self
+            .get_string_field(&recipe["name"])
+            .ok_or(RecipeError::RecipeImportedWithoutTitle)?;
 
-        let instructions: Vec<String> = recipe["recipeInstructions"]
-            .as_array()
-            .expect("Impossible to retrieve instructions")
-            .iter()
-            .map(|i| {
-                let text = if i.is_object() { &i["text"] } else { i };
-                self.get_string_field(text)
-                    .expect("Impossible to retrieve instructions")
-            })
-            .collect();
+        let (instruction_sections, instructions): (Vec<Option<String>>, Vec<String>) = self
+            .get_sectioned_list(&recipe["recipeInstructions"], None)
+            .into_iter()
+            .unzip();
 
-        let ingredients: Vec<String> = recipe["recipeIngredient"]
-            .as_array()
-            .expect("Impossible to retrieve ingredients")
-            .iter()
-            .map(|text| {
-                self.get_string_field(text)
-                    .expect("Impossible to retrieve ingredients")
-            })
-            .collect();
+        let (ingredient_sections, ingredients): (Vec<Option<String>>, Vec<String>) = self
+            .get_sectioned_list(&recipe["recipeIngredient"], None)
+            .into_iter()
+            .unzip();
+        if ingredients.is_empty() {
+            return Err(Box::new(RecipeError::RecipeImportedWithoutIngredients));
+        }
 
         let r = Recipe {
             id: Uuid::new_v4(),
             user_id,
-            title: self.get_string_field(&recipe["name"]).unwrap(),
+            title,
             description: self.get_string_field(&recipe["description"]),
             recipe_yield: self.get_string_field(&recipe["recipeYield"]),
             category: self.get_string_field(&recipe["recipeCategory"]),
@@ -85,24 +114,275 @@ impl SelectParser {
             prep_time_in_minute: self.get_duration_in_minute(&recipe["prepTime"]),
             cook_time_in_minute: self.get_duration_in_minute(&recipe["cookTime"]),
             instructions,
+            instruction_sections,
+            structured_ingredients: StructuredIngredient::parse_all(&ingredients),
             ingredients,
-            imported_from: Some(String::from(url)),
+            ingredient_sections,
+            tags: get_tags_from_keywords(&self.get_string_list(&recipe["keywords"])),
+            visibility: RecipeVisibility::Private,
+            household_id: None,
+            imported_from: url.map(String::from),
             image_url: self.get_image(&recipe["image"]),
         };
         Ok(r)
     }
 
+    fn parse_from_microdata(
+        &self,
+        url: Option<&str>,
+        html: &str,
+        user_id: String,
+    ) -> Result<Recipe, Box<dyn Error>> {
+        let document = Document::from(html);
+        let recipe = document
+            .find(Attr("itemtype", ()).or(Attr("typeof", ())))
+            .find(|n| self.is_microdata_type(n, "Recipe"))
+            .ok_or(RecipeError::RecipeImportedWebsiteNotSupported)?;
+
+        let title = self
+            .get_microdata_text(&recipe, "name")
+            .ok_or(RecipeError::RecipeImportedWithoutTitle)?;
+
+        let mut ingredients: Vec<String> = self
+            .find_microdata_properties(&recipe, "recipeIngredient")
+            .iter()
+            .filter_map(|n| self.get_microdata_value(n))
+            .collect();
+        if ingredients.is_empty() {
+            ingredients = self
+                .find_microdata_properties(&recipe, "ingredients")
+                .iter()
+                .filter_map(|n| self.get_microdata_value(n))
+                .collect();
+        }
+        if ingredients.is_empty() {
+            return Err(Box::new(RecipeError::RecipeImportedWithoutIngredients));
+        }
+
+        let (instruction_sections, instructions): (Vec<Option<String>>, Vec<String>) = self
+            .find_microdata_properties(&recipe, "recipeInstructions")
+            .iter()
+            .flat_map(|n| self.get_microdata_instructions(n))
+            .unzip();
+        let ingredient_sections = vec![None; ingredients.len()];
+
+        let r = Recipe {
+            id: Uuid::new_v4(),
+            user_id,
+            title,
+            description: self.get_microdata_text(&recipe, "description"),
+            recipe_yield: self.get_microdata_text(&recipe, "recipeYield"),
+            category: self.get_microdata_text(&recipe, "recipeCategory"),
+            cuisine: self.get_microdata_text(&recipe, "recipeCuisine"),
+            prep_time_in_minute: self
+                .get_microdata_text(&recipe, "prepTime")
+                .and_then(|d| self.get_duration_in_minute(&Value::String(d))),
+            cook_time_in_minute: self
+                .get_microdata_text(&recipe, "cookTime")
+                .and_then(|d| self.get_duration_in_minute(&Value::String(d))),
+            instructions,
+            instruction_sections,
+            structured_ingredients: StructuredIngredient::parse_all(&ingredients),
+            ingredients,
+            ingredient_sections,
+            tags: get_tags_from_keywords(
+                &self
+                    .find_microdata_properties(&recipe, "keywords")
+                    .iter()
+                    .filter_map(|n| self.get_microdata_value(n))
+                    .collect::<Vec<String>>(),
+            ),
+            visibility: RecipeVisibility::Private,
+            household_id: None,
+            imported_from: url.map(String::from),
+            image_url: self.get_microdata_image(&recipe),
+        };
+        Ok(r)
+    }
+
+    fn is_microdata_type(&self, node: &Node, item_type: &str) -> bool {
+        let types = node.attr("itemtype").or_else(|| node.attr("typeof"));
+        types
+            .map(|t| {
+                t.split_whitespace()
+                    .any(|t| t.rsplit(['/', ':']).next() == Some(item_type))
+            })
+            .unwrap_or(false)
+    }
+
+    fn is_microdata_scope(&self, node: &Node) -> bool {
+        node.attr("itemscope").is_some() || node.attr("typeof").is_some()
+    }
+
+    fn has_microdata_property(&self, node: &Node, property: &str) -> bool {
+        node.attr("itemprop")
+            .or_else(|| node.attr("property"))
+            .map(|p| {
+                p.split_whitespace()
+                    .any(|p| p.rsplit(':').next() == Some(property))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Properties of the given scope, without looking into nested items
+    /// (e.g. the `name` of the recipe author).
+    fn find_microdata_properties<'a>(&self, scope: &Node<'a>, property: &str) -> Vec<Node<'a>> {
+        let mut properties = vec![];
+        for child in scope.children() {
+            if self.has_microdata_property(&child, property) {
+                properties.push(child);
+            } else if !self.is_microdata_scope(&child) {
+                properties.append(&mut self.find_microdata_properties(&child, property));
+            }
+        }
+        properties
+    }
+
+    fn get_microdata_text(&self, scope: &Node, property: &str) -> Option<String> {
+        self.find_microdata_properties(scope, property)
+            .iter()
+            .find_map(|n| self.get_microdata_value(n))
+    }
+
+    fn get_microdata_value(&self, node: &Node) -> Option<String> {
+        let value = match node.name() {
+            Some("meta") => node.attr("content").map(String::from),
+            Some("img") | Some("source") => node.attr("src").map(String::from),
+            Some("a") | Some("link") => node.attr("href").map(String::from),
+            Some("time") => node
+                .attr("datetime")
+                .or_else(|| node.attr("content"))
+                .map(String::from),
+            _ => node.attr("content").map(String::from),
+        };
+        let value = value.unwrap_or_else(|| node.text());
+        let value = value.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if value.is_empty() {
+            return None;
+        }
+        Some(value)
+    }
+
+    fn get_microdata_instructions(&self, node: &Node) -> Vec<(Option<String>, String)> {
+        if self.is_microdata_type(node, "HowToSection") {
+            let section = self.get_microdata_text(node, "name");
+            return self
+                .find_microdata_properties(node, "itemListElement")
+                .iter()
+                .flat_map(|n| self.get_microdata_instructions(n))
+                .map(|(_, step)| (section.clone(), step))
+                .collect();
+        }
+        if self.is_microdata_scope(node) {
+            return self
+                .get_microdata_text(node, "text")
+                .map(|step| (None, step))
+                .into_iter()
+                .collect();
+        }
+        let steps: Vec<(Option<String>, String)> = node
+            .find(Name("li"))
+            .filter_map(|n| self.get_microdata_value(&n))
+            .map(|step| (None, step))
+            .collect();
+        if steps.is_empty() {
+            return self
+                .get_microdata_value(node)
+                .map(|step| (None, step))
+                .into_iter()
+                .collect();
+        }
+        steps
+    }
+
+    fn get_microdata_image(&self, scope: &Node) -> Option<String> {
+        let image = self
+            .find_microdata_properties(scope, "image")
+            .into_iter()
+            .next()?;
+        if self.is_microdata_scope(&image) {
+            return self.get_microdata_text(&image, "url");
+        }
+        self.get_microdata_value(&image)
+    }
+
+    /// Flattens lists of `HowToSection`s and `HowToStep`s, keeping the name
+    /// of the section each item comes from.
+    fn get_sectioned_list(
+        &self,
+        value: &Value,
+        section: Option<&str>,
+    ) -> Vec<(Option<String>, String)> {
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .flat_map(|i| self.get_sectioned_list(i, section))
+                .collect(),
+            Value::Object(_) if value["@type"] == "HowToSection" || value["text"].is_null() => {
+                match value.get("itemListElement") {
+                    Some(items) => {
+                        let name = self.get_string_field(&value["name"]);
+                        self.get_sectioned_list(items, name.as_deref().or(section))
+                    }
+                    None => self.get_sectioned_list(&value["name"], section),
+                }
+            }
+            Value::Object(_) => self.get_sectioned_list(&value["text"], section),
+            _ => self
+                .get_string_field(value)
+                .map(|text| (section.map(String::from), text))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn find_recipe_node<'a>(&self, json: &'a Value) -> Option<&'a Value> {
+        match json {
+            Value::Array(array) => array.iter().find_map(|v| self.find_recipe_node(v)),
+            Value::Object(object) => {
+                if self.is_recipe_type(&json["@type"]) {
+                    return Some(json);
+                }
+                object
+                    .get("@graph")
+                    .and_then(|graph| self.find_recipe_node(graph))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_recipe_type(&self, value: &Value) -> bool {
+        match value {
+            Value::String(t) => t == "Recipe",
+            Value::Array(types) => types.iter().any(|t| t == "Recipe"),
+            _ => false,
+        }
+    }
+
     fn get_duration_in_minute(&self, value: &Value) -> Option<i32> {
         let string = value.as_str()?;
         let duration = Duration::parse(string).ok()?;
         Some((duration.minute as i32) + (duration.hour as i32 * 60))
     }
 
+    /// `keywords` is either a comma separated string or an array of strings.
+    fn get_string_list(&self, value: &Value) -> Vec<String> {
+        match value {
+            Value::Array(array) => array
+                .iter()
+                .filter_map(|v| self.get_string_field(v))
+                .collect(),
+            _ => self.get_string_field(value).into_iter().collect(),
+        }
+    }
+
     fn get_string_field(&self, value: &Value) -> Option<String> {
         if value.is_f64() {
             value.as_f64().map(|n| n.to_string())
         } else if value.is_i64() {
             value.as_i64().map(|n| n.to_string())
+        } else if let Value::Array(array) = value {
+            array.iter().find_map(|v| self.get_string_field(v))
         } else {
             let mut text = String::new();
             for node in Document::from(value.as_str()?).nodes {
@@ -136,7 +416,7 @@ impl SelectParser {
 
 impl Default for SelectParser {
     fn default() -> Self {
-        SelectParser::new()
+        SelectParser::new(Box::new(ReqwestFetcher::default()))
     }
 }
 
@@ -150,13 +430,13 @@ mod tests {
         // given
         let html = fs::read_to_string("./src/infrastructure/parser/__data__/marmitton.html")
             .expect("Something went wrong reading the file");
-        let parser = SelectParser::new();
+        let parser = SelectParser::default();
         let user_id = String::from("some_user_id");
         let url = "https://www.marmiton.org/recettes/recette_pate-brisee-vite-faite_31639.aspx";
 
         // when
         let recipe = parser
-            .parse_from_json_ld(url, html.as_str(), user_id.clone())
+            .parse_from_json_ld(Some(url), html.as_str(), user_id.clone())
             .expect("Can parse recipe");
 
         // then
@@ -183,6 +463,20 @@ mod tests {
             ]
         );
         assert_eq!(recipe.category, Some(String::from("pâte à tarte salée")));
+        assert_eq!(
+            recipe.tags,
+            vec![
+                String::from("pâte brisée vite faite"),
+                String::from("pâte à tarte salée"),
+                String::from("farine"),
+                String::from("beurre"),
+                String::from("sel"),
+                String::from("sucre"),
+                String::from("eau"),
+                String::from("très facile"),
+                String::from("bon marché"),
+            ]
+        );
         assert_eq!(
             recipe.description,
             Some(String::from("farine, beurre, sel, sucre, eau"))
@@ -203,13 +497,13 @@ mod tests {
         let html =
             fs::read_to_string("./src/infrastructure/parser/__data__/journal_des_femmes.html")
                 .expect("Something went wrong reading the file");
-        let parser = SelectParser::new();
+        let parser = SelectParser::default();
         let user_id = String::from("some_user_id");
         let url = "https://cuisine.journaldesfemmes.fr/recette/313738-lasagnes-a-la-bolognaise";
 
         // when
         let recipe = parser
-            .parse_from_json_ld(url, html.as_str(), user_id.clone())
+            .parse_from_json_ld(Some(url), html.as_str(), user_id.clone())
             .expect("Can parse recipe");
 
         // then
@@ -226,13 +520,13 @@ mod tests {
         // given
         let html = fs::read_to_string("./src/infrastructure/parser/__data__/taste_com_au.html")
             .expect("Something went wrong reading the file");
-        let parser = SelectParser::new();
+        let parser = SelectParser::default();
         let user_id = String::from("some_user_id");
         let url = "https://www.taste.com.au/recipes/better-you-chicken-cacciatore/47u4vq3q";
 
         // when
         let recipe = parser
-            .parse_from_json_ld(url, html.as_str(), user_id.clone())
+            .parse_from_json_ld(Some(url), html.as_str(), user_id.clone())
             .expect("Can parse recipe");
 
         // then
@@ -251,13 +545,13 @@ mod tests {
         // given
         let html = fs::read_to_string("./src/infrastructure/parser/__data__/bbc_good_food.html")
             .expect("Something went wrong reading the file");
-        let parser = SelectParser::new();
+        let parser = SelectParser::default();
         let user_id = String::from("some_user_id");
         let url = "https://www.bbcgoodfood.com/recipes/brilliant-banana-loaf";
 
         // when
         let recipe = parser
-            .parse_from_json_ld(url, html.as_str(), user_id.clone())
+            .parse_from_json_ld(Some(url), html.as_str(), user_id.clone())
             .expect("Can parse recipe");
 
         // then
@@ -279,4 +573,351 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn parsing_yoast_graph() {
+        // given
+        let html = fs::read_to_string("./src/infrastructure/parser/__data__/yoast_graph.html")
+            .expect("Something went wrong reading the file");
+        let parser = SelectParser::default();
+        let user_id = String::from("some_user_id");
+        let url = "https://www.example-wordpress-blog.com/chocolate-chip-cookies/";
+
+        // when
+        let recipe = parser
+            .parse_from_json_ld(Some(url), html.as_str(), user_id.clone())
+            .expect("Can parse recipe");
+
+        // then
+        assert_eq!(recipe.user_id, user_id);
+        assert_eq!(recipe.imported_from, Some(String::from(url)));
+        assert_eq!(recipe.title, String::from("Easy Chocolate Chip Cookies"));
+        assert_eq!(recipe.prep_time_in_minute, Some(15));
+        assert_eq!(recipe.cook_time_in_minute, Some(12));
+        assert_eq!(recipe.recipe_yield, Some(String::from("24")));
+        assert_eq!(recipe.category, Some(String::from("Dessert")));
+        assert_eq!(recipe.cuisine, Some(String::from("American")));
+        assert_eq!(recipe.ingredients.len(), 6);
+        assert_eq!(
+            recipe.ingredients.get(0).unwrap(),
+            &String::from("2 1/4 cups all-purpose flour")
+        );
+        assert_eq!(recipe.instructions.len(), 4);
+        assert_eq!(
+            recipe.instructions.get(1).unwrap(),
+            &String::from("Whisk the flour and baking soda together in a bowl.")
+        );
+        assert_eq!(
+            recipe.image_url,
+            Some(String::from(
+                "https://www.example-wordpress-blog.com/wp-content/uploads/2020/05/cookies.jpg"
+            ))
+        );
+    }
+
+    #[test]
+    fn parsing_recipe_not_first_in_array() {
+        // given
+        let html = r#"<html><head><script type="application/ld+json">[
+            {"@type": "BreadcrumbList", "name": "Breadcrumb"},
+            {"@type": ["NewsArticle", "Recipe"], "name": "Soupe", "recipeIngredient": ["1 poireau"], "recipeInstructions": ["Cuire"]}
+        ]</script></head><body></body></html>"#;
+        let parser = SelectParser::default();
+
+        // when
+        let recipe = parser
+            .parse_from_json_ld(
+                Some("https://example.com"),
+                html,
+                String::from("some_user_id"),
+            )
+            .expect("Can parse recipe");
+
+        // then
+        assert_eq!(recipe.title, String::from("Soupe"));
+        assert_eq!(recipe.ingredients, vec![String::from("1 poireau")]);
+        assert_eq!(recipe.instructions, vec![String::from("Cuire")]);
+    }
+
+    #[test]
+    fn parsing_microdata() {
+        // given
+        let html = fs::read_to_string("./src/infrastructure/parser/__data__/microdata.html")
+            .expect("Something went wrong reading the file");
+        let parser = SelectParser::default();
+        let user_id = String::from("some_user_id");
+        let url = "https://www.example-blog.fr/gratin-dauphinois";
+
+        // when
+        let recipe = parser
+            .parse_from_microdata(Some(url), html.as_str(), user_id.clone())
+            .expect("Can parse recipe");
+
+        // then
+        assert_eq!(recipe.user_id, user_id);
+        assert_eq!(recipe.imported_from, Some(String::from(url)));
+        assert_eq!(recipe.title, String::from("Gratin dauphinois de mamie"));
+        assert_eq!(
+            recipe.description,
+            Some(String::from("Le vrai gratin dauphinois, sans fromage."))
+        );
+        assert_eq!(recipe.prep_time_in_minute, Some(20));
+        assert_eq!(recipe.cook_time_in_minute, Some(75));
+        assert_eq!(recipe.recipe_yield, Some(String::from("6 personnes")));
+        assert_eq!(recipe.category, Some(String::from("Accompagnement")));
+        assert_eq!(recipe.cuisine, Some(String::from("Française")));
+        assert_eq!(
+            recipe.image_url,
+            Some(String::from(
+                "https://www.example-blog.fr/images/gratin-dauphinois.jpg"
+            ))
+        );
+        assert_eq!(
+            recipe.ingredients,
+            vec![
+                String::from("1 kg de pommes de terre"),
+                String::from("50 cl de lait entier"),
+                String::from("20 cl de crème fraîche"),
+                String::from("2 gousses d'ail"),
+                String::from("1 pincée de noix de muscade"),
+            ]
+        );
+        assert_eq!(recipe.instructions.len(), 4);
+        assert_eq!(
+            recipe.instructions.get(0).unwrap(),
+            &String::from("Préchauffer le four à 180°C.")
+        );
+    }
+
+    #[test]
+    fn parsing_rdfa() {
+        // given
+        let html = r#"<html><body>
+            <div vocab="http://schema.org/" typeof="Recipe">
+                <h1 property="name">Crêpes</h1>
+                <div property="author" typeof="Person"><span property="name">Jean</span></div>
+                <meta property="prepTime" content="PT10M">
+                <span property="recipeIngredient">250 g de farine</span>
+                <span property="recipeIngredient">4 oeufs</span>
+                <div property="recipeInstructions">Mélanger la farine et les oeufs.</div>
+            </div>
+        </body></html>"#;
+        let parser = SelectParser::default();
+
+        // when
+        let recipe = parser
+            .parse_from_microdata(
+                Some("https://example.com"),
+                html,
+                String::from("some_user_id"),
+            )
+            .expect("Can parse recipe");
+
+        // then
+        assert_eq!(recipe.title, String::from("Crêpes"));
+        assert_eq!(recipe.prep_time_in_minute, Some(10));
+        assert_eq!(
+            recipe.ingredients,
+            vec![String::from("250 g de farine"), String::from("4 oeufs")]
+        );
+        assert_eq!(
+            recipe.instructions,
+            vec![String::from("Mélanger la farine et les oeufs.")]
+        );
+    }
+
+    #[test]
+    fn parsing_without_structured_data() {
+        // given
+        let html = "<html><head><title>No recipe here</title></head><body></body></html>";
+        let parser = SelectParser::default();
+
+        // when
+        let error = parser
+            .parse_from_json_ld(
+                Some("https://example.com"),
+                html,
+                String::from("some_user_id"),
+            )
+            .unwrap_err();
+
+        // then
+        assert!(matches!(
+            error.downcast_ref::<RecipeError>(),
+            Some(RecipeError::RecipeImportedWebsiteNotSupported)
+        ));
+    }
+
+    #[test]
+    fn parsing_malformed_json_ld() {
+        // given
+        let html = r#"<html><head><script type="application/ld+json">{"@type": "Recipe", "name": </script></head></html>"#;
+        let parser = SelectParser::default();
+
+        // when
+        let error = parser
+            .parse_from_json_ld(
+                Some("https://example.com"),
+                html,
+                String::from("some_user_id"),
+            )
+            .unwrap_err();
+
+        // then
+        assert!(matches!(
+            error.downcast_ref::<RecipeError>(),
+            Some(RecipeError::RecipeImportedWithMalformedData)
+        ));
+    }
+
+    #[test]
+    fn parsing_recipe_without_title() {
+        // given
+        let html = r#"<html><head><script type="application/ld+json">{"@type": "Recipe", "recipeIngredient": ["1 oeuf"]}</script></head></html>"#;
+        let parser = SelectParser::default();
+
+        // when
+        let error = parser
+            .parse_from_json_ld(
+                Some("https://example.com"),
+                html,
+                String::from("some_user_id"),
+            )
+            .unwrap_err();
+
+        // then
+        assert!(matches!(
+            error.downcast_ref::<RecipeError>(),
+            Some(RecipeError::RecipeImportedWithoutTitle)
+        ));
+    }
+
+    #[test]
+    fn parsing_recipe_without_ingredients() {
+        // given
+        let html = r#"<html><head><script type="application/ld+json">{"@type": "Recipe", "name": "Oeuf dur"}</script></head></html>"#;
+        let parser = SelectParser::default();
+
+        // when
+        let error = parser
+            .parse_from_json_ld(
+                Some("https://example.com"),
+                html,
+                String::from("some_user_id"),
+            )
+            .unwrap_err();
+
+        // then
+        assert!(matches!(
+            error.downcast_ref::<RecipeError>(),
+            Some(RecipeError::RecipeImportedWithoutIngredients)
+        ));
+    }
+
+    #[test]
+    fn parsing_how_to_sections() {
+        // given
+        let html = r#"<html><head><script type="application/ld+json">{
+            "@type": "Recipe",
+            "name": "Lasagnes",
+            "recipeIngredient": [
+                {"@type": "HowToSection", "name": "Pour la sauce", "itemListElement": ["500 g de boeuf haché", "1 oignon"]},
+                {"@type": "HowToSection", "name": "Pour la béchamel", "itemListElement": ["50 g de beurre"]}
+            ],
+            "recipeInstructions": [
+                {"@type": "HowToSection", "name": "Pour la sauce", "itemListElement": [
+                    {"@type": "HowToStep", "text": "Faire revenir l'oignon."},
+                    {"@type": "HowToStep", "text": "Ajouter le boeuf."}
+                ]},
+                {"@type": "HowToSection", "name": "Pour la béchamel", "itemListElement": [
+                    {"@type": "HowToStep", "text": "Faire fondre le beurre."}
+                ]},
+                {"@type": "HowToStep", "text": "Monter les lasagnes."}
+            ]
+        }</script></head></html>"#;
+        let parser = SelectParser::default();
+
+        // when
+        let recipe = parser
+            .parse_from_json_ld(
+                Some("https://example.com"),
+                html,
+                String::from("some_user_id"),
+            )
+            .expect("Can parse recipe");
+
+        // then
+        assert_eq!(
+            recipe.instructions,
+            vec![
+                String::from("Faire revenir l'oignon."),
+                String::from("Ajouter le boeuf."),
+                String::from("Faire fondre le beurre."),
+                String::from("Monter les lasagnes."),
+            ]
+        );
+        assert_eq!(
+            recipe.instruction_sections,
+            vec![
+                Some(String::from("Pour la sauce")),
+                Some(String::from("Pour la sauce")),
+                Some(String::from("Pour la béchamel")),
+                None,
+            ]
+        );
+        assert_eq!(
+            recipe.ingredients,
+            vec![
+                String::from("500 g de boeuf haché"),
+                String::from("1 oignon"),
+                String::from("50 g de beurre"),
+            ]
+        );
+        assert_eq!(
+            recipe.ingredient_sections,
+            vec![
+                Some(String::from("Pour la sauce")),
+                Some(String::from("Pour la sauce")),
+                Some(String::from("Pour la béchamel")),
+            ]
+        );
+        assert_eq!(
+            recipe.structured_ingredients[0],
+            StructuredIngredient {
+                quantity: Some(500.0),
+                max_quantity: None,
+                unit: Some(String::from("g")),
+                name: String::from("boeuf haché"),
+                note: None,
+            }
+        );
+    }
+
+    struct StubFetcher {
+        html: String,
+    }
+
+    impl Fetcher for StubFetcher {
+        fn fetch(&self, _url: &str) -> Result<String, Box<dyn Error>> {
+            Ok(self.html.clone())
+        }
+    }
+
+    #[test]
+    fn parsing_fetched_page() {
+        // given
+        let html = fs::read_to_string("./src/infrastructure/parser/__data__/microdata.html")
+            .expect("Something went wrong reading the file");
+        let parser = SelectParser::new(Box::new(StubFetcher { html }));
+        let url = "https://www.example-blog.fr/gratin-dauphinois";
+
+        // when
+        let recipe = parser
+            .parse_recipe(String::from(url), String::from("some_user_id"))
+            .expect("Can parse recipe");
+
+        // then
+        assert_eq!(recipe.title, String::from("Gratin dauphinois de mamie"));
+        assert_eq!(recipe.imported_from, Some(String::from(url)));
+    }
 }
@@ -1,4 +1,15 @@
+use diesel::pg::PgConnection;
+use diesel::r2d2::{ConnectionManager, PooledConnection};
+use std::rc::Rc;
+
+pub mod collections;
+pub mod meal_plans;
 pub mod models;
+pub mod pantries;
 pub mod recipes;
 pub mod schema;
+pub mod shopping_lists;
 pub mod users;
+
+/// The database connection of a request, shared by all the DAOs serving it.
+pub type SharedConnection = Rc<PooledConnection<ConnectionManager<PgConnection>>>;
@@ -0,0 +1,153 @@
+use crate::diesel::prelude::*;
+use crate::domain::collections::errors::CollectionError;
+use crate::domain::collections::models::collection::Collection as DomainCollection;
+use crate::domain::collections::ports::dao::{CollectionDao, NewCollection as DomainNewCollection};
+use crate::infrastructure::sql::models::*;
+use crate::infrastructure::sql::SharedConnection;
+
+use std::error::Error;
+use uuid::Uuid;
+
+pub struct DieselCollectionDao {
+    connection: SharedConnection,
+}
+
+impl CollectionDao for DieselCollectionDao {
+    fn get_my_collections(&self, user_id: &str) -> Result<Vec<DomainCollection>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::collection_recipes::dsl::position;
+        use crate::infrastructure::sql::schema::collections::dsl::{
+            collections, name, user_id as collections_user_id,
+        };
+
+        let collections_results = collections
+            .filter(collections_user_id.eq(user_id))
+            .order_by(name.asc())
+            .load::<Collection>(&*self.connection)?;
+
+        let recipes_results = CollectionRecipe::belonging_to(&collections_results)
+            .order_by(position.asc())
+            .load::<CollectionRecipe>(&*self.connection)?
+            .grouped_by(&collections_results);
+
+        Ok(collections_results
+            .iter()
+            .zip(recipes_results)
+            .map(|(collection, recipes)| DomainCollection::from(collection, recipes))
+            .collect())
+    }
+
+    fn get_collection(&self, id: &str) -> Result<DomainCollection, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::collection_recipes::dsl::position;
+        use crate::infrastructure::sql::schema::collections::dsl::{
+            collections, id as collection_id,
+        };
+
+        let collection = collections
+            .filter(collection_id.eq(id))
+            .first::<Collection>(&*self.connection)
+            .optional()?
+            .ok_or(CollectionError::CollectionNotFound)?;
+
+        let recipes = CollectionRecipe::belonging_to(&collection)
+            .order_by(position.asc())
+            .load::<CollectionRecipe>(&*self.connection)?;
+
+        Ok(DomainCollection::from(&collection, recipes))
+    }
+
+    fn add_collection(
+        &self,
+        new_collection: DomainNewCollection,
+    ) -> Result<DomainCollection, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::collections;
+
+        let inserted_collection: Collection = diesel::insert_into(collections::table)
+            .values(&NewCollection {
+                id: new_collection.id,
+                user_id: new_collection.user_id,
+                name: new_collection.name,
+                description: new_collection.description,
+            })
+            .get_result(&*self.connection)?;
+
+        Ok(DomainCollection::from(&inserted_collection, vec![]))
+    }
+
+    fn update_collection(
+        &self,
+        id: &str,
+        new_name: &str,
+        new_description: Option<&str>,
+    ) -> Result<DomainCollection, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::collections::dsl::{
+            collections, description, id as collection_id, name,
+        };
+
+        diesel::update(collections.filter(collection_id.eq(id)))
+            .set((name.eq(new_name), description.eq(new_description)))
+            .execute(&*self.connection)?;
+        self.get_collection(id)
+    }
+
+    fn delete_collection(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::collection_recipes::dsl::{
+            collection_id as recipes_collection_id, collection_recipes,
+        };
+        use crate::infrastructure::sql::schema::collections::dsl::{
+            collections, id as collection_id,
+        };
+
+        diesel::delete(collection_recipes.filter(recipes_collection_id.eq(id)))
+            .execute(&*self.connection)?;
+        diesel::delete(collections.filter(collection_id.eq(id))).execute(&*self.connection)?;
+        Ok(())
+    }
+
+    fn set_recipes(
+        &self,
+        id: &str,
+        recipe_ids: &[String],
+    ) -> Result<DomainCollection, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::collection_recipes;
+        use crate::infrastructure::sql::schema::collection_recipes::dsl::{
+            collection_id, collection_recipes as all_collection_recipes,
+        };
+
+        self.connection.transaction::<_, Box<dyn Error>, _>(|| {
+            diesel::delete(all_collection_recipes.filter(collection_id.eq(id)))
+                .execute(&*self.connection)?;
+            let new_recipes: Vec<NewCollectionRecipe> = recipe_ids
+                .iter()
+                .enumerate()
+                .map(|(i, recipe_id)| NewCollectionRecipe {
+                    collection_id: id,
+                    recipe_id,
+                    position: i as i32 + 1,
+                })
+                .collect();
+            diesel::insert_into(collection_recipes::table)
+                .values(&new_recipes)
+                .execute(&*self.connection)?;
+            Ok(())
+        })?;
+        self.get_collection(id)
+    }
+}
+
+impl DieselCollectionDao {
+    pub fn new(connection: SharedConnection) -> DieselCollectionDao {
+        DieselCollectionDao { connection }
+    }
+}
+
+impl DomainCollection {
+    fn from(collection: &Collection, recipes: Vec<CollectionRecipe>) -> Self {
+        DomainCollection {
+            id: Uuid::parse_str(collection.id.as_str()).expect("Cannot parse UUID"),
+            user_id: collection.user_id.clone(),
+            name: collection.name.clone(),
+            description: collection.description.clone(),
+            recipe_ids: recipes.into_iter().map(|r| r.recipe_id).collect(),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::diesel::prelude::*;
+use crate::domain::meal_plans::errors::MealPlanError;
+use crate::domain::meal_plans::models::meal_plan::{
+    MealPlanEntry as DomainMealPlanEntry, MealSlot,
+};
+use crate::domain::meal_plans::ports::dao::{
+    MealPlanDao, NewMealPlanEntry as DomainNewMealPlanEntry,
+};
+use crate::infrastructure::sql::models::*;
+use crate::infrastructure::sql::SharedConnection;
+
+use chrono::NaiveDate;
+use std::error::Error;
+use uuid::Uuid;
+
+pub struct DieselMealPlanDao {
+    connection: SharedConnection,
+}
+
+impl MealPlanDao for DieselMealPlanDao {
+    fn get_entries(
+        &self,
+        user_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DomainMealPlanEntry>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::meal_plan_entries::dsl::{
+            created_at, date, meal_plan_entries, user_id as entries_user_id,
+        };
+
+        let entries = meal_plan_entries
+            .filter(entries_user_id.eq(user_id))
+            .filter(date.between(start, end))
+            .order_by((date.asc(), created_at.asc()))
+            .load::<MealPlanEntry>(&*self.connection)?;
+
+        Ok(entries.iter().map(DomainMealPlanEntry::from).collect())
+    }
+
+    fn get_entry(&self, id: &str) -> Result<DomainMealPlanEntry, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::meal_plan_entries::dsl::{
+            id as entry_id, meal_plan_entries,
+        };
+
+        let entry = meal_plan_entries
+            .filter(entry_id.eq(id))
+            .first::<MealPlanEntry>(&*self.connection)
+            .optional()?
+            .ok_or(MealPlanError::MealPlanEntryNotFound)?;
+
+        Ok(DomainMealPlanEntry::from(&entry))
+    }
+
+    fn add_entry(
+        &self,
+        new_entry: DomainNewMealPlanEntry,
+    ) -> Result<DomainMealPlanEntry, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::meal_plan_entries;
+
+        let inserted_entry: MealPlanEntry = diesel::insert_into(meal_plan_entries::table)
+            .values(&NewMealPlanEntry {
+                id: new_entry.id,
+                user_id: new_entry.user_id,
+                recipe_id: new_entry.recipe_id,
+                date: new_entry.date,
+                slot: new_entry.slot.as_str(),
+                servings: new_entry.servings,
+            })
+            .get_result(&*self.connection)?;
+
+        Ok(DomainMealPlanEntry::from(&inserted_entry))
+    }
+
+    fn move_entry(
+        &self,
+        id: &str,
+        new_date: NaiveDate,
+        new_slot: MealSlot,
+    ) -> Result<DomainMealPlanEntry, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::meal_plan_entries::dsl::{
+            date, id as entry_id, meal_plan_entries, slot,
+        };
+
+        diesel::update(meal_plan_entries.filter(entry_id.eq(id)))
+            .set((date.eq(new_date), slot.eq(new_slot.as_str())))
+            .execute(&*self.connection)?;
+        self.get_entry(id)
+    }
+
+    fn delete_entry(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::meal_plan_entries::dsl::{
+            id as entry_id, meal_plan_entries,
+        };
+
+        diesel::delete(meal_plan_entries.filter(entry_id.eq(id))).execute(&*self.connection)?;
+        Ok(())
+    }
+}
+
+impl DieselMealPlanDao {
+    pub fn new(connection: SharedConnection) -> DieselMealPlanDao {
+        DieselMealPlanDao { connection }
+    }
+}
+
+impl DomainMealPlanEntry {
+    fn from(entry: &MealPlanEntry) -> Self {
+        DomainMealPlanEntry {
+            id: Uuid::parse_str(entry.id.as_str()).expect("Cannot parse UUID"),
+            user_id: entry.user_id.clone(),
+            date: entry.date,
+            slot: MealSlot::parse(&entry.slot).unwrap_or(MealSlot::Dinner),
+            recipe_id: entry.recipe_id.clone(),
+            servings: entry.servings,
+        }
+    }
+}
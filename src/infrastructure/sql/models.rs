@@ -1,4 +1,9 @@
-use crate::infrastructure::sql::schema::{ingredients, instructions, recipes, users};
+use crate::infrastructure::sql::schema::{
+    collection_recipes, collections, household_invitations, households, ingredients, instructions,
+    meal_plan_entries, pantry_items, recipe_import_snapshots, recipe_shares, recipe_tags, recipes,
+    shopping_list_items, shopping_lists, users,
+};
+use chrono::NaiveDate;
 use std::time::SystemTime;
 
 #[derive(Identifiable, Queryable, PartialEq, Debug)]
@@ -9,6 +14,9 @@ pub struct User {
     pub password_hash: String,
     pub created_at: SystemTime,
     pub updated_at: SystemTime,
+    pub unit_system: Option<String>,
+    pub household_id: Option<String>,
+    pub calendar_token: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -36,6 +44,8 @@ pub struct Recipe {
     pub imported_from: Option<String>,
     pub created_at: SystemTime,
     pub updated_at: SystemTime,
+    pub visibility: String,
+    pub household_id: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -62,6 +72,12 @@ pub struct Ingredient {
     pub step_number: i32,
     pub recipe_id: String,
     pub ingredient: String,
+    pub section: Option<String>,
+    pub quantity: Option<f64>,
+    pub max_quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub name: Option<String>,
+    pub note: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -70,6 +86,12 @@ pub struct NewIngredient<'a> {
     pub step_number: i32,
     pub recipe_id: &'a str,
     pub ingredient: &'a str,
+    pub section: Option<&'a str>,
+    pub quantity: Option<f64>,
+    pub max_quantity: Option<f64>,
+    pub unit: Option<&'a str>,
+    pub name: Option<&'a str>,
+    pub note: Option<&'a str>,
 }
 
 #[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
@@ -80,6 +102,7 @@ pub struct Instruction {
     pub step_number: i32,
     pub recipe_id: String,
     pub instruction: String,
+    pub section: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -88,4 +111,225 @@ pub struct NewInstruction<'a> {
     pub step_number: i32,
     pub recipe_id: &'a str,
     pub instruction: &'a str,
+    pub section: Option<&'a str>,
+}
+
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[belongs_to(Recipe)]
+#[primary_key(recipe_id)]
+#[table_name = "recipe_import_snapshots"]
+pub struct RecipeImportSnapshot {
+    pub recipe_id: String,
+    pub snapshot: String,
+    pub created_at: SystemTime,
+    pub updated_at: SystemTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "recipe_import_snapshots"]
+pub struct NewRecipeImportSnapshot<'a> {
+    pub recipe_id: &'a str,
+    pub snapshot: &'a str,
+}
+
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[belongs_to(Recipe)]
+#[belongs_to(User)]
+#[primary_key(recipe_id, user_id)]
+#[table_name = "recipe_shares"]
+pub struct RecipeShare {
+    pub recipe_id: String,
+    pub user_id: String,
+    pub role: String,
+}
+
+#[derive(Insertable)]
+#[table_name = "recipe_shares"]
+pub struct NewRecipeShare<'a> {
+    pub recipe_id: &'a str,
+    pub user_id: &'a str,
+    pub role: &'a str,
+}
+
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[belongs_to(Recipe)]
+#[primary_key(recipe_id, tag)]
+#[table_name = "recipe_tags"]
+pub struct RecipeTag {
+    pub recipe_id: String,
+    pub tag: String,
+}
+
+#[derive(Insertable)]
+#[table_name = "recipe_tags"]
+pub struct NewRecipeTag<'a> {
+    pub recipe_id: &'a str,
+    pub tag: &'a str,
+}
+
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[table_name = "collections"]
+#[belongs_to(User)]
+pub struct Collection {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: SystemTime,
+    pub updated_at: SystemTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "collections"]
+pub struct NewCollection<'a> {
+    pub id: &'a str,
+    pub user_id: &'a str,
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+}
+
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[belongs_to(Collection)]
+#[primary_key(collection_id, recipe_id)]
+#[table_name = "collection_recipes"]
+pub struct CollectionRecipe {
+    pub collection_id: String,
+    pub recipe_id: String,
+    pub position: i32,
+}
+
+#[derive(Insertable)]
+#[table_name = "collection_recipes"]
+pub struct NewCollectionRecipe<'a> {
+    pub collection_id: &'a str,
+    pub recipe_id: &'a str,
+    pub position: i32,
+}
+
+#[derive(Identifiable, Queryable, PartialEq, Debug)]
+#[table_name = "households"]
+pub struct Household {
+    pub id: String,
+    pub name: String,
+    pub created_at: SystemTime,
+    pub updated_at: SystemTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "households"]
+pub struct NewHousehold<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+}
+
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[belongs_to(Household)]
+#[primary_key(household_id, email)]
+#[table_name = "household_invitations"]
+pub struct HouseholdInvitation {
+    pub household_id: String,
+    pub email: String,
+}
+
+#[derive(Insertable)]
+#[table_name = "household_invitations"]
+pub struct NewHouseholdInvitation<'a> {
+    pub household_id: &'a str,
+    pub email: &'a str,
+}
+
+#[derive(Identifiable, Queryable, PartialEq, Debug)]
+#[table_name = "meal_plan_entries"]
+pub struct MealPlanEntry {
+    pub id: String,
+    pub user_id: String,
+    pub recipe_id: String,
+    pub date: NaiveDate,
+    pub slot: String,
+    pub servings: Option<i32>,
+    pub created_at: SystemTime,
+    pub updated_at: SystemTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "meal_plan_entries"]
+pub struct NewMealPlanEntry<'a> {
+    pub id: &'a str,
+    pub user_id: &'a str,
+    pub recipe_id: &'a str,
+    pub date: NaiveDate,
+    pub slot: &'a str,
+    pub servings: Option<i32>,
+}
+
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[table_name = "shopping_lists"]
+#[belongs_to(User)]
+pub struct ShoppingList {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub created_at: SystemTime,
+    pub updated_at: SystemTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "shopping_lists"]
+pub struct NewShoppingList<'a> {
+    pub id: &'a str,
+    pub user_id: &'a str,
+    pub name: &'a str,
+}
+
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[belongs_to(ShoppingList)]
+#[table_name = "shopping_list_items"]
+pub struct ShoppingListItem {
+    pub id: String,
+    pub shopping_list_id: String,
+    pub position: i32,
+    pub text: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub aisle: String,
+    pub checked: bool,
+}
+
+#[derive(Insertable)]
+#[table_name = "shopping_list_items"]
+pub struct NewShoppingListItem<'a> {
+    pub id: &'a str,
+    pub shopping_list_id: &'a str,
+    pub position: i32,
+    pub text: &'a str,
+    pub name: &'a str,
+    pub quantity: Option<f64>,
+    pub unit: Option<&'a str>,
+    pub aisle: &'a str,
+    pub checked: bool,
+}
+
+#[derive(Identifiable, Queryable, PartialEq, Debug)]
+#[table_name = "pantry_items"]
+pub struct PantryItem {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+    pub created_at: SystemTime,
+    pub updated_at: SystemTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "pantry_items"]
+pub struct NewPantryItem<'a> {
+    pub id: &'a str,
+    pub user_id: &'a str,
+    pub name: &'a str,
+    pub quantity: Option<f64>,
+    pub unit: Option<&'a str>,
+    pub expires_on: Option<NaiveDate>,
 }
@@ -0,0 +1,105 @@
+use crate::diesel::prelude::*;
+use crate::domain::pantries::errors::PantryError;
+use crate::domain::pantries::models::pantry_item::PantryItem as DomainPantryItem;
+use crate::domain::pantries::ports::dao::{NewPantryItem as DomainNewPantryItem, PantryDao};
+use crate::infrastructure::sql::models::*;
+use crate::infrastructure::sql::SharedConnection;
+
+use chrono::NaiveDate;
+use std::error::Error;
+use uuid::Uuid;
+
+pub struct DieselPantryDao {
+    connection: SharedConnection,
+}
+
+impl PantryDao for DieselPantryDao {
+    fn get_my_pantry(&self, user_id: &str) -> Result<Vec<DomainPantryItem>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::pantry_items::dsl::{
+            name, pantry_items, user_id as items_user_id,
+        };
+
+        let items = pantry_items
+            .filter(items_user_id.eq(user_id))
+            .order_by(name.asc())
+            .load::<PantryItem>(&*self.connection)?;
+
+        Ok(items.iter().map(DomainPantryItem::from).collect())
+    }
+
+    fn get_item(&self, id: &str) -> Result<DomainPantryItem, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::pantry_items::dsl::{id as item_id, pantry_items};
+
+        let item = pantry_items
+            .filter(item_id.eq(id))
+            .first::<PantryItem>(&*self.connection)
+            .optional()?
+            .ok_or(PantryError::PantryItemNotFound)?;
+
+        Ok(DomainPantryItem::from(&item))
+    }
+
+    fn add_item(&self, new_item: DomainNewPantryItem) -> Result<DomainPantryItem, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::pantry_items;
+
+        let inserted_item: PantryItem = diesel::insert_into(pantry_items::table)
+            .values(&NewPantryItem {
+                id: new_item.id,
+                user_id: new_item.user_id,
+                name: new_item.name,
+                quantity: new_item.quantity,
+                unit: new_item.unit,
+                expires_on: new_item.expires_on,
+            })
+            .get_result(&*self.connection)?;
+
+        Ok(DomainPantryItem::from(&inserted_item))
+    }
+
+    fn update_item(
+        &self,
+        id: &str,
+        new_quantity: Option<f64>,
+        new_unit: Option<&str>,
+        new_expires_on: Option<NaiveDate>,
+    ) -> Result<DomainPantryItem, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::pantry_items::dsl::{
+            expires_on, id as item_id, pantry_items, quantity, unit,
+        };
+
+        diesel::update(pantry_items.filter(item_id.eq(id)))
+            .set((
+                quantity.eq(new_quantity),
+                unit.eq(new_unit),
+                expires_on.eq(new_expires_on),
+            ))
+            .execute(&*self.connection)?;
+        self.get_item(id)
+    }
+
+    fn delete_item(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::pantry_items::dsl::{id as item_id, pantry_items};
+
+        diesel::delete(pantry_items.filter(item_id.eq(id))).execute(&*self.connection)?;
+        Ok(())
+    }
+}
+
+impl DieselPantryDao {
+    pub fn new(connection: SharedConnection) -> DieselPantryDao {
+        DieselPantryDao { connection }
+    }
+}
+
+impl DomainPantryItem {
+    fn from(item: &PantryItem) -> Self {
+        DomainPantryItem {
+            id: Uuid::parse_str(item.id.as_str()).expect("Cannot parse UUID"),
+            user_id: item.user_id.clone(),
+            name: item.name.clone(),
+            quantity: item.quantity,
+            unit: item.unit.clone(),
+            expires_on: item.expires_on,
+        }
+    }
+}
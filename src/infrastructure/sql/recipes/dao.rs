@@ -1,152 +1,323 @@
 use crate::diesel::prelude::*;
 use crate::domain::recipes::errors::RecipeError;
-use crate::domain::recipes::models::recipe::Recipe as DomainRecipe;
-use crate::domain::recipes::ports::dao::{NewRecipe as DomainNewRecipe, RecipeDao};
+use crate::domain::recipes::models::facet::{FacetValue, RecipeFacets};
+use crate::domain::recipes::models::ingredient::StructuredIngredient;
+use crate::domain::recipes::models::page::{
+    RecipeCursor, RecipeSort, RecipeSortField, RecipeSortKey, SortDirection,
+};
+use crate::domain::recipes::models::recipe::{Recipe as DomainRecipe, RecipeVisibility};
+use crate::domain::recipes::models::search::RecipeSearchResult;
+use crate::domain::recipes::models::share::{RecipeShare as DomainRecipeShare, ShareRole};
+use crate::domain::recipes::ports::dao::{NewRecipe as DomainNewRecipe, RecipeDao, RecipeFilter};
 use crate::infrastructure::sql::models::*;
+use crate::infrastructure::sql::SharedConnection;
 
 use itertools::izip;
+use serde::{Deserialize, Serialize};
 use std::error::Error;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
-use diesel::pg::PgConnection;
-use diesel::r2d2::{ConnectionManager, PooledConnection};
+use diesel::dsl::sql;
+use diesel::pg::Pg;
+use diesel::sql_types::{BigInt, Float4, Int4, Nullable, Text, Varchar};
+
+sql_function!(fn coalesce(x: Nullable<Int4>, y: Int4) -> Int4);
+sql_function!(fn lower(x: Varchar) -> Varchar);
+sql_function!(fn btrim(x: Nullable<Varchar>) -> Nullable<Varchar>);
 
 pub struct DieselRecipeDao {
-    connection: PooledConnection<ConnectionManager<PgConnection>>,
+    connection: SharedConnection,
+}
+
+/// Imported fields of a recipe, serialized in `recipe_import_snapshots`.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct RecipeSnapshot {
+    user_id: String,
+    title: String,
+    description: Option<String>,
+    cook_time_in_minute: Option<i32>,
+    prep_time_in_minute: Option<i32>,
+    image_url: Option<String>,
+    recipe_yield: Option<String>,
+    category: Option<String>,
+    cuisine: Option<String>,
+    instructions: Vec<String>,
+    instruction_sections: Vec<Option<String>>,
+    ingredients: Vec<String>,
+    ingredient_sections: Vec<Option<String>>,
+    imported_from: Option<String>,
+}
+
+/// `recipes.search_vector` is a tsvector, which diesel does not support: it is
+/// left out of the schema and only used through raw SQL.
+/// The document is HTML escaped before ts_headline so that the only tags of
+/// the snippet are the `<mark>` it adds.
+static SEARCH_QUERY: &str = "
+SELECT id, rank,
+  ts_headline('french', document, query, $4) AS french_snippet,
+  ts_headline('english', document, query, $4) AS english_snippet
+FROM (
+  SELECT recipes.id, recipes.title, ts_rank(recipes.search_vector, query) AS rank, query,
+    replace(replace(replace(concat_ws(' ', recipes.title, recipes.description,
+      (SELECT string_agg(ingredient, ' ' ORDER BY step_number)
+        FROM ingredients WHERE recipe_id = recipes.id),
+      (SELECT string_agg(instruction, ' ' ORDER BY step_number)
+        FROM instructions WHERE recipe_id = recipes.id)),
+      '&', '&amp;'), '<', '&lt;'), '>', '&gt;') AS document
+  FROM recipes,
+    (SELECT plainto_tsquery('french', $1) || plainto_tsquery('english', $1) AS query) AS q
+  WHERE recipes.search_vector @@ query
+    AND (recipes.user_id = $2
+      OR recipes.household_id IN (SELECT household_id FROM users WHERE users.id = $2))
+  ORDER BY rank DESC, recipes.title
+  LIMIT $3
+) AS results
+ORDER BY rank DESC, title";
+
+static SNIPPET_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=20";
+
+#[derive(QueryableByName)]
+struct RecipeSearchRow {
+    #[sql_type = "Varchar"]
+    id: String,
+    #[sql_type = "Float4"]
+    rank: f32,
+    #[sql_type = "Text"]
+    french_snippet: String,
+    #[sql_type = "Text"]
+    english_snippet: String,
 }
 
 impl RecipeDao for DieselRecipeDao {
     fn get_my_recipes(
         &self,
         user_id: &str,
-        query: Option<String>,
+        filter: &RecipeFilter,
+        sort: RecipeSort,
     ) -> Result<Vec<DomainRecipe>, Box<dyn Error>> {
-        use crate::infrastructure::sql::schema::ingredients::dsl::step_number as ingredient_step_number;
-        use crate::infrastructure::sql::schema::instructions::dsl::step_number as instructions_step_number;
-        use crate::infrastructure::sql::schema::recipes::dsl::{
-            recipes, title, user_id as recipes_user_id,
+        let recipes_results =
+            Self::sort_my_recipes(user_id, filter, sort).load::<Recipe>(&*self.connection)?;
+
+        self.load_recipes(recipes_results)
+    }
+
+    fn get_my_recipes_after(
+        &self,
+        user_id: &str,
+        filter: &RecipeFilter,
+        sort: RecipeSort,
+        after: Option<&RecipeCursor>,
+        limit: usize,
+    ) -> Result<Vec<(DomainRecipe, RecipeCursor)>, Box<dyn Error>> {
+        let mut recipes_query = Self::sort_my_recipes(user_id, filter, sort);
+        if let Some(after) = after {
+            recipes_query = Self::filter_after(recipes_query, sort.direction, after);
+        }
+        let recipes_results = recipes_query
+            .limit(limit as i64)
+            .load::<Recipe>(&*self.connection)?;
+        let cursors: Vec<RecipeCursor> = recipes_results
+            .iter()
+            .map(|recipe| get_cursor(recipe, sort.field))
+            .collect();
+
+        Ok(self
+            .load_recipes(recipes_results)?
+            .into_iter()
+            .zip(cursors)
+            .collect())
+    }
+
+    fn count_my_recipes(
+        &self,
+        user_id: &str,
+        filter: &RecipeFilter,
+    ) -> Result<usize, Box<dyn Error>> {
+        let count = Self::filter_my_recipes(user_id, filter)
+            .count()
+            .get_result::<i64>(&*self.connection)?;
+        Ok(count as usize)
+    }
+
+    fn get_my_recipe_facets(
+        &self,
+        user_id: &str,
+        filter: &RecipeFilter,
+    ) -> Result<RecipeFacets, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipes::dsl::{category, cuisine};
+
+        // Each facet leaves out its own filter, so that the other values can
+        // still be picked. Diesel cannot select `count_star` along with a
+        // column, hence the literal.
+        let count = sql::<BigInt>("count(*)");
+        let categories_filter = RecipeFilter {
+            categories: vec![],
+            ..filter.clone()
         };
-        let q = match query {
-            Some(mut q) => {
-                q.push('%');
-                q
-            }
-            None => String::from("%"),
+        let categories = Self::filter_my_recipes(user_id, &categories_filter)
+            .select((btrim(category), count.clone()))
+            .filter(btrim(category).ne(""))
+            .group_by(btrim(category))
+            .order_by((count.clone().desc(), btrim(category).asc()))
+            .load::<(Option<String>, i64)>(&*self.connection)?;
+        let cuisines_filter = RecipeFilter {
+            cuisines: vec![],
+            ..filter.clone()
         };
+        let cuisines = Self::filter_my_recipes(user_id, &cuisines_filter)
+            .select((btrim(cuisine), count.clone()))
+            .filter(btrim(cuisine).ne(""))
+            .group_by(btrim(cuisine))
+            .order_by((count.desc(), btrim(cuisine).asc()))
+            .load::<(Option<String>, i64)>(&*self.connection)?;
 
-        let recipes_results = recipes
-            .filter(recipes_user_id.eq(user_id))
-            .filter(title.ilike(&q))
-            .load::<Recipe>(&self.connection)?;
+        Ok(RecipeFacets {
+            categories: get_facet_values(categories),
+            cuisines: get_facet_values(cuisines),
+        })
+    }
 
-        let instructions_results = Instruction::belonging_to(&recipes_results)
-            .order_by(instructions_step_number.asc())
-            .load::<Instruction>(&self.connection)?
-            .grouped_by(&recipes_results);
+    fn search_recipes(
+        &self,
+        user_id: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<RecipeSearchResult>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipes::dsl::{id as recipe_id, recipes};
 
-        let ingredients_results = Ingredient::belonging_to(&recipes_results)
-            .order_by(ingredient_step_number.asc())
-            .load::<Ingredient>(&self.connection)?
-            .grouped_by(&recipes_results);
+        let rows = diesel::sql_query(SEARCH_QUERY)
+            .bind::<Text, _>(query)
+            .bind::<Text, _>(user_id)
+            .bind::<BigInt, _>(limit as i64)
+            .bind::<Text, _>(SNIPPET_OPTIONS)
+            .load::<RecipeSearchRow>(&*self.connection)?;
+        let ids: Vec<&str> = rows.iter().map(|row| row.id.as_str()).collect();
+        let recipes_results = recipes
+            .filter(recipe_id.eq_any(ids))
+            .load::<Recipe>(&*self.connection)?;
+        let mut found_recipes = self.load_recipes(recipes_results)?;
 
-        let data = izip!(
-            &recipes_results,
-            &ingredients_results,
-            &instructions_results
-        );
-        Ok(data
-            .map(|(recipe, ingredients, instructions)| {
-                let id = Uuid::parse_str(recipe.id.as_str()).expect("Cannot parse UUID");
-                DomainRecipe {
-                    id,
-                    // TODO: remove those unecessary clone (maybe move that in the diesel models)
-                    user_id: recipe.user_id.clone(),
-                    title: recipe.title.clone(),
-                    description: recipe.description.clone(),
-                    image_url: recipe.image_url.clone(),
-                    recipe_yield: recipe.recipe_yield.clone(),
-                    category: recipe.category.clone(),
-                    cuisine: recipe.cuisine.clone(),
-                    imported_from: recipe.imported_from.clone(),
-                    cook_time_in_minute: recipe.cook_time_in_minute,
-                    prep_time_in_minute: recipe.prep_time_in_minute,
-                    instructions: instructions.iter().map(|i| i.instruction.clone()).collect(),
-                    ingredients: ingredients.iter().map(|i| i.ingredient.clone()).collect(),
-                }
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let index = found_recipes
+                    .iter()
+                    .position(|r| r.id.to_hyphenated().to_string() == row.id)?;
+                // The query words are stemmed in both languages but a headline
+                // only highlights the words stemmed in its own.
+                let snippet = if row.french_snippet.contains("<mark>")
+                    || !row.english_snippet.contains("<mark>")
+                {
+                    row.french_snippet
+                } else {
+                    row.english_snippet
+                };
+                Some(RecipeSearchResult {
+                    recipe: found_recipes.swap_remove(index),
+                    rank: row.rank,
+                    snippet,
+                })
             })
             .collect())
     }
 
     fn get_recipe(&self, id: String) -> Result<DomainRecipe, Box<dyn Error>> {
-        use crate::infrastructure::sql::schema::ingredients::dsl::step_number as ingredient_step_number;
-        use crate::infrastructure::sql::schema::instructions::dsl::step_number as instructions_step_number;
         use crate::infrastructure::sql::schema::recipes::dsl::{id as recipe_id, recipes};
 
         let recipes_results = recipes
             .filter(recipe_id.eq(id))
-            .load::<Recipe>(&self.connection)?;
+            .load::<Recipe>(&*self.connection)?;
 
-        let instructions_results = Instruction::belonging_to(&recipes_results)
-            .order_by(instructions_step_number.asc())
-            .load::<Instruction>(&self.connection)?
-            .grouped_by(&recipes_results);
-
-        let ingredients_results = Ingredient::belonging_to(&recipes_results)
-            .order_by(ingredient_step_number.asc())
-            .load::<Ingredient>(&self.connection)?
-            .grouped_by(&recipes_results);
-
-        let data = izip!(
-            &recipes_results,
-            &ingredients_results,
-            &instructions_results
-        );
-        let results: Vec<DomainRecipe> = data
-            .map(|(recipe, ingredients, instructions)| {
-                let id = Uuid::parse_str(recipe.id.as_str()).expect("Cannot parse UUID");
-                DomainRecipe {
-                    id,
-                    // TODO: remove those unecessary clone (maybe move that in the diesel models)
-                    user_id: recipe.user_id.clone(),
-                    title: recipe.title.clone(),
-                    description: recipe.description.clone(),
-                    image_url: recipe.image_url.clone(),
-                    recipe_yield: recipe.recipe_yield.clone(),
-                    category: recipe.category.clone(),
-                    cuisine: recipe.cuisine.clone(),
-                    imported_from: recipe.imported_from.clone(),
-                    cook_time_in_minute: recipe.cook_time_in_minute,
-                    prep_time_in_minute: recipe.prep_time_in_minute,
-                    instructions: instructions.iter().map(|i| i.instruction.clone()).collect(),
-                    ingredients: ingredients.iter().map(|i| i.ingredient.clone()).collect(),
-                }
-            })
-            .collect();
-        let r = results
+        let r = self
+            .load_recipes(recipes_results)?
             .into_iter()
             .last()
             .ok_or(RecipeError::RecipeNotFound)?;
         Ok(r)
     }
 
+    fn get_recipes(&self, ids: &[String]) -> Result<Vec<DomainRecipe>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipes::dsl::{id as recipe_id, recipes};
+
+        let recipes_results = recipes
+            .filter(recipe_id.eq_any(ids))
+            .load::<Recipe>(&*self.connection)?;
+        self.load_recipes(recipes_results)
+    }
+
     fn delete_recipe(&self, id: String) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::collection_recipes::dsl::{
+            collection_recipes, recipe_id as collection_recipes_recipe_id,
+        };
         use crate::infrastructure::sql::schema::ingredients::dsl::{
             ingredients, recipe_id as ingredients_recipe_id,
         };
         use crate::infrastructure::sql::schema::instructions::dsl::{
             instructions, recipe_id as instructions_recipe_id,
         };
-        use crate::infrastructure::sql::schema::recipes::dsl::{id as recipe_id, recipes};
+        use crate::infrastructure::sql::schema::meal_plan_entries::dsl::{
+            meal_plan_entries, recipe_id as meal_plan_entries_recipe_id,
+            user_id as meal_plan_entries_user_id,
+        };
+        use crate::infrastructure::sql::schema::recipe_import_snapshots::dsl::{
+            recipe_id as snapshots_recipe_id, recipe_import_snapshots,
+        };
+        use crate::infrastructure::sql::schema::recipe_shares::dsl::{
+            recipe_id as shares_recipe_id, recipe_shares,
+        };
+        use crate::infrastructure::sql::schema::recipe_tags::dsl::{
+            recipe_id as tags_recipe_id, recipe_tags,
+        };
+        use crate::infrastructure::sql::schema::recipes::dsl::{
+            id as recipe_id, recipes, user_id as recipes_user_id,
+        };
 
-        diesel::delete(ingredients.filter(ingredients_recipe_id.eq(id.clone())))
-            .execute(&self.connection)?;
-        diesel::delete(instructions.filter(instructions_recipe_id.eq(id.clone())))
-            .execute(&self.connection)?;
-        diesel::delete(recipes.filter(recipe_id.eq(id))).execute(&self.connection)?;
-        Ok(())
+        self.connection.transaction::<_, Box<dyn Error>, _>(|| {
+            let owner_id = recipes.filter(recipe_id.eq(&id)).select(recipes_user_id);
+            diesel::delete(recipe_import_snapshots.filter(snapshots_recipe_id.eq(&id)))
+                .execute(&*self.connection)?;
+            diesel::delete(recipe_shares.filter(shares_recipe_id.eq(&id)))
+                .execute(&*self.connection)?;
+            diesel::delete(recipe_tags.filter(tags_recipe_id.eq(&id)))
+                .execute(&*self.connection)?;
+            diesel::delete(collection_recipes.filter(collection_recipes_recipe_id.eq(&id)))
+                .execute(&*self.connection)?;
+            // The entries of the other users are kept and make the deletion fail.
+            diesel::delete(
+                meal_plan_entries
+                    .filter(meal_plan_entries_recipe_id.eq(&id))
+                    .filter(meal_plan_entries_user_id.eq_any(owner_id)),
+            )
+            .execute(&*self.connection)?;
+            diesel::delete(ingredients.filter(ingredients_recipe_id.eq(&id)))
+                .execute(&*self.connection)?;
+            diesel::delete(instructions.filter(instructions_recipe_id.eq(&id)))
+                .execute(&*self.connection)?;
+            diesel::delete(recipes.filter(recipe_id.eq(&id))).execute(&*self.connection)?;
+            Ok(())
+        })
+    }
+
+    fn is_planned_by_other_users(&self, id: &str, user_id: &str) -> Result<bool, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::meal_plan_entries::dsl::{
+            meal_plan_entries, recipe_id, user_id as entry_user_id,
+        };
+
+        let planned_count: i64 = meal_plan_entries
+            .filter(recipe_id.eq(id))
+            .filter(entry_user_id.ne(user_id))
+            .count()
+            .get_result(&*self.connection)?;
+        Ok(planned_count > 0)
     }
 
-    fn update_recipe(&self, recipe: DomainRecipe) -> Result<DomainRecipe, Box<dyn Error>> {
+    fn update_recipe(
+        &self,
+        recipe: DomainRecipe,
+        import_snapshot: Option<&DomainRecipe>,
+    ) -> Result<DomainRecipe, Box<dyn Error>> {
         use crate::infrastructure::sql::schema::ingredients::dsl::{
             ingredients, recipe_id as ingredients_recipe_id,
         };
@@ -158,49 +329,74 @@ impl RecipeDao for DieselRecipeDao {
             imported_from, prep_time_in_minute, recipe_yield, recipes, title,
         };
         let id: String = recipe.id.to_hyphenated().to_string();
-        let inserted_recipe = diesel::update(recipes.filter(recipe_id.eq(&id)))
-            .set((
-                title.eq(recipe.title),
-                description.eq(recipe.description),
-                cook_time_in_minute.eq(recipe.cook_time_in_minute),
-                prep_time_in_minute.eq(recipe.prep_time_in_minute),
-                image_url.eq(recipe.image_url),
-                recipe_yield.eq(recipe.recipe_yield),
-                category.eq(recipe.category),
-                cuisine.eq(recipe.cuisine),
-                imported_from.eq(recipe.imported_from),
+        self.connection.transaction::<_, Box<dyn Error>, _>(|| {
+            let inserted_recipe: Recipe = diesel::update(recipes.filter(recipe_id.eq(&id)))
+                .set((
+                    title.eq(&recipe.title),
+                    description.eq(&recipe.description),
+                    cook_time_in_minute.eq(recipe.cook_time_in_minute),
+                    prep_time_in_minute.eq(recipe.prep_time_in_minute),
+                    image_url.eq(&recipe.image_url),
+                    recipe_yield.eq(&recipe.recipe_yield),
+                    category.eq(&recipe.category),
+                    cuisine.eq(&recipe.cuisine),
+                    imported_from.eq(&recipe.imported_from),
+                ))
+                .get_result(&*self.connection)?;
+
+            diesel::delete(ingredients.filter(ingredients_recipe_id.eq(&id)))
+                .execute(&*self.connection)?;
+            diesel::delete(instructions.filter(instructions_recipe_id.eq(&id)))
+                .execute(&*self.connection)?;
+
+            let inserted_instructions = self.insert_instructions(
+                &id,
+                &recipe
+                    .instructions
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<&str>>(),
+                &recipe
+                    .instruction_sections
+                    .iter()
+                    .map(|s| s.as_deref())
+                    .collect::<Vec<Option<&str>>>(),
+            )?;
+
+            let inserted_ingredients = self.insert_ingredients(
+                &id,
+                &recipe
+                    .ingredients
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<&str>>(),
+                &recipe
+                    .ingredient_sections
+                    .iter()
+                    .map(|s| s.as_deref())
+                    .collect::<Vec<Option<&str>>>(),
+                &recipe.structured_ingredients,
+            )?;
+            self.refresh_search_vector(&id)?;
+            if let Some(import_snapshot) = import_snapshot {
+                self.save_import_snapshot(&id, import_snapshot)?;
+            }
+            let tags =
+                RecipeTag::belonging_to(&inserted_recipe).load::<RecipeTag>(&*self.connection)?;
+            Ok(DomainRecipe::from(
+                &inserted_recipe,
+                inserted_instructions,
+                inserted_ingredients,
+                tags,
             ))
-            .get_result(&self.connection)?;
-
-        diesel::delete(ingredients.filter(ingredients_recipe_id.eq(&id)))
-            .execute(&self.connection)?;
-        diesel::delete(instructions.filter(instructions_recipe_id.eq(&id)))
-            .execute(&self.connection)?;
-
-        let inserted_instructions = self.insert_instructions(
-            recipe.id.to_hyphenated().to_string().as_str(),
-            &recipe
-                .instructions
-                .iter()
-                .map(|s| s.as_str())
-                .collect::<Vec<&str>>(),
-        )?;
-
-        let inserted_ingredients = self.insert_ingredients(
-            recipe.id.to_hyphenated().to_string().as_str(),
-            &recipe
-                .ingredients
-                .iter()
-                .map(|s| s.as_str())
-                .collect::<Vec<&str>>(),
-        )?;
-        Ok(DomainRecipe::from(
-            &inserted_recipe,
-            inserted_instructions,
-            inserted_ingredients,
-        ))
-    }
-    fn add_recipe(&self, new_recipe: DomainNewRecipe) -> Result<DomainRecipe, Box<dyn Error>> {
+        })
+    }
+
+    fn add_recipe(
+        &self,
+        new_recipe: DomainNewRecipe,
+        import_snapshot: Option<&DomainRecipe>,
+    ) -> Result<DomainRecipe, Box<dyn Error>> {
         use crate::infrastructure::sql::schema::recipes;
         let new_recipe_sql = NewRecipe {
             id: new_recipe.id,
@@ -216,36 +412,478 @@ impl RecipeDao for DieselRecipeDao {
             imported_from: new_recipe.imported_from,
         };
 
-        let inserted_recipe: Result<Recipe, diesel::result::Error> =
-            diesel::insert_into(recipes::table)
+        self.connection.transaction::<_, Box<dyn Error>, _>(|| {
+            let inserted_recipe: Recipe = diesel::insert_into(recipes::table)
                 .values(&new_recipe_sql)
-                .get_result(&self.connection);
+                .get_result(&*self.connection)?;
+
+            let inserted_instructions = self.insert_instructions(
+                &new_recipe.id,
+                &new_recipe.instructions,
+                &new_recipe.instruction_sections,
+            )?;
+
+            let inserted_ingredients = self.insert_ingredients(
+                &new_recipe.id,
+                &new_recipe.ingredients,
+                &new_recipe.ingredient_sections,
+                new_recipe.structured_ingredients,
+            )?;
+
+            let inserted_tags = self.insert_tags(&new_recipe.id, &new_recipe.tags)?;
+            self.refresh_search_vector(&new_recipe.id)?;
+            if let Some(import_snapshot) = import_snapshot {
+                self.save_import_snapshot(&new_recipe.id, import_snapshot)?;
+            }
+
+            Ok(DomainRecipe::from(
+                &inserted_recipe,
+                inserted_instructions,
+                inserted_ingredients,
+                inserted_tags,
+            ))
+        })
+    }
+
+    fn add_tag(&self, id: &str, new_tag: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_tags;
+
+        diesel::insert_into(recipe_tags::table)
+            .values(&NewRecipeTag {
+                recipe_id: id,
+                tag: new_tag,
+            })
+            .on_conflict_do_nothing()
+            .execute(&*self.connection)?;
+        Ok(())
+    }
 
-        let inserted_recipe = inserted_recipe?;
+    fn remove_tag(&self, id: &str, old_tag: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_tags::dsl::{recipe_id, recipe_tags, tag};
 
-        let inserted_instructions =
-            self.insert_instructions(&new_recipe.id, &new_recipe.instructions)?;
+        diesel::delete(recipe_tags.filter(recipe_id.eq(id)).filter(tag.eq(old_tag)))
+            .execute(&*self.connection)?;
+        Ok(())
+    }
 
-        let inserted_ingredients =
-            self.insert_ingredients(&new_recipe.id, &new_recipe.ingredients)?;
+    fn set_visibility(
+        &self,
+        id: &str,
+        new_visibility: RecipeVisibility,
+    ) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipes::dsl::{
+            id as recipe_id, recipes, visibility,
+        };
 
-        Ok(DomainRecipe::from(
-            &inserted_recipe,
-            inserted_instructions,
-            inserted_ingredients,
-        ))
+        diesel::update(recipes.filter(recipe_id.eq(id)))
+            .set(visibility.eq(new_visibility.as_str()))
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+
+    fn set_household(
+        &self,
+        id: &str,
+        new_household_id: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipes::dsl::{
+            household_id, id as recipe_id, recipes,
+        };
+
+        diesel::update(recipes.filter(recipe_id.eq(id)))
+            .set(household_id.eq(new_household_id))
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+
+    fn get_shares(&self, id: &str) -> Result<Vec<DomainRecipeShare>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_shares::dsl::{
+            recipe_id, recipe_shares, role, user_id,
+        };
+        use crate::infrastructure::sql::schema::users::dsl::{email, users};
+
+        let shares = recipe_shares
+            .inner_join(users)
+            .filter(recipe_id.eq(id))
+            .select((user_id, email, role))
+            .order_by(email.asc())
+            .load::<(String, String, String)>(&*self.connection)?;
+        Ok(shares
+            .into_iter()
+            .map(
+                |(share_user_id, share_email, share_role)| DomainRecipeShare {
+                    user_id: share_user_id,
+                    email: share_email,
+                    role: ShareRole::parse(&share_role).unwrap_or(ShareRole::Viewer),
+                },
+            )
+            .collect())
+    }
+
+    fn get_share_role(
+        &self,
+        id: &str,
+        shared_with: &str,
+    ) -> Result<Option<ShareRole>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_shares::dsl::{
+            recipe_id, recipe_shares, role, user_id,
+        };
+
+        let share_role = recipe_shares
+            .filter(recipe_id.eq(id))
+            .filter(user_id.eq(shared_with))
+            .select(role)
+            .first::<String>(&*self.connection)
+            .optional()?;
+        Ok(share_role.as_deref().and_then(ShareRole::parse))
+    }
+
+    fn get_shared_recipe_ids(
+        &self,
+        ids: &[String],
+        shared_with: &str,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_shares::dsl::{
+            recipe_id, recipe_shares, user_id,
+        };
+
+        let shared_ids = recipe_shares
+            .filter(recipe_id.eq_any(ids))
+            .filter(user_id.eq(shared_with))
+            .select(recipe_id)
+            .load::<String>(&*self.connection)?;
+        Ok(shared_ids)
+    }
+
+    fn share_recipe(
+        &self,
+        id: &str,
+        shared_with: &str,
+        new_role: ShareRole,
+    ) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_shares::dsl::{
+            recipe_id, recipe_shares, role, user_id,
+        };
+
+        diesel::insert_into(recipe_shares)
+            .values(&NewRecipeShare {
+                recipe_id: id,
+                user_id: shared_with,
+                role: new_role.as_str(),
+            })
+            .on_conflict((recipe_id, user_id))
+            .do_update()
+            .set(role.eq(new_role.as_str()))
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+
+    fn unshare_recipe(&self, id: &str, shared_with: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_shares::dsl::{
+            recipe_id, recipe_shares, user_id,
+        };
+
+        diesel::delete(
+            recipe_shares
+                .filter(recipe_id.eq(id))
+                .filter(user_id.eq(shared_with)),
+        )
+        .execute(&*self.connection)?;
+        Ok(())
+    }
+
+    fn get_import_snapshot(&self, id: String) -> Result<Option<DomainRecipe>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_import_snapshots::dsl::{
+            recipe_id, recipe_import_snapshots,
+        };
+
+        let snapshot = recipe_import_snapshots
+            .filter(recipe_id.eq(&id))
+            .first::<RecipeImportSnapshot>(&*self.connection)
+            .optional()?;
+        match snapshot {
+            Some(snapshot) => {
+                let snapshot: RecipeSnapshot = serde_json::from_str(&snapshot.snapshot)?;
+                Ok(Some(snapshot.into_recipe(&id)))
+            }
+            None => Ok(None),
+        }
     }
 }
 
 impl DieselRecipeDao {
-    pub fn new(connection: PooledConnection<ConnectionManager<PgConnection>>) -> DieselRecipeDao {
+    pub fn new(connection: SharedConnection) -> DieselRecipeDao {
         DieselRecipeDao { connection }
     }
 
+    fn save_import_snapshot(&self, id: &str, recipe: &DomainRecipe) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_import_snapshots::dsl::{
+            recipe_id, recipe_import_snapshots, snapshot,
+        };
+
+        let serialized = serde_json::to_string(&RecipeSnapshot::from(recipe))?;
+        diesel::insert_into(recipe_import_snapshots)
+            .values(&NewRecipeImportSnapshot {
+                recipe_id: id,
+                snapshot: &serialized,
+            })
+            .on_conflict(recipe_id)
+            .do_update()
+            .set(snapshot.eq(&serialized))
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+
+    /// The recipes matching the filter in the given order, the ones sorted the
+    /// same way being ordered by id.
+    fn sort_my_recipes<'a>(
+        user_id: &'a str,
+        filter: &'a RecipeFilter,
+        sort: RecipeSort,
+    ) -> crate::infrastructure::sql::schema::recipes::BoxedQuery<'a, Pg> {
+        use crate::infrastructure::sql::schema::recipes::dsl::{
+            cook_time_in_minute, created_at, id as recipe_id, prep_time_in_minute, title,
+            updated_at,
+        };
+
+        let recipes_query = Self::filter_my_recipes(user_id, filter);
+        let no_time = prep_time_in_minute
+            .is_null()
+            .and(cook_time_in_minute.is_null());
+        let total_time = coalesce(prep_time_in_minute, 0) + coalesce(cook_time_in_minute, 0);
+        match (sort.field, sort.direction) {
+            (RecipeSortField::CreatedAt, SortDirection::Asc) => {
+                recipes_query.order_by(created_at.asc())
+            }
+            (RecipeSortField::CreatedAt, SortDirection::Desc) => {
+                recipes_query.order_by(created_at.desc())
+            }
+            (RecipeSortField::UpdatedAt, SortDirection::Asc) => {
+                recipes_query.order_by(updated_at.asc())
+            }
+            (RecipeSortField::UpdatedAt, SortDirection::Desc) => {
+                recipes_query.order_by(updated_at.desc())
+            }
+            (RecipeSortField::Title, SortDirection::Asc) => {
+                recipes_query.order_by(lower(title).asc())
+            }
+            (RecipeSortField::Title, SortDirection::Desc) => {
+                recipes_query.order_by(lower(title).desc())
+            }
+            (RecipeSortField::TotalTime, SortDirection::Asc) => recipes_query
+                .order_by(no_time.asc())
+                .then_order_by(total_time.asc()),
+            (RecipeSortField::TotalTime, SortDirection::Desc) => recipes_query
+                .order_by(no_time.asc())
+                .then_order_by(total_time.desc()),
+        }
+        .then_order_by(recipe_id.asc())
+    }
+
+    /// Keeps the recipes following the cursor in the order of `sort_my_recipes`.
+    fn filter_after<'a>(
+        recipes_query: crate::infrastructure::sql::schema::recipes::BoxedQuery<'a, Pg>,
+        direction: SortDirection,
+        after: &RecipeCursor,
+    ) -> crate::infrastructure::sql::schema::recipes::BoxedQuery<'a, Pg> {
+        use crate::infrastructure::sql::schema::recipes::dsl::{
+            cook_time_in_minute, created_at, id as recipe_id, prep_time_in_minute, title,
+            updated_at,
+        };
+
+        let id = after.id.clone();
+        let no_time = prep_time_in_minute
+            .is_null()
+            .and(cook_time_in_minute.is_null());
+        let total_time = coalesce(prep_time_in_minute, 0) + coalesce(cook_time_in_minute, 0);
+        match (&after.key, direction) {
+            (RecipeSortKey::CreatedAt(micros), SortDirection::Asc) => {
+                let time = get_time(*micros);
+                recipes_query.filter(
+                    created_at
+                        .gt(time)
+                        .or(created_at.eq(time).and(recipe_id.gt(id))),
+                )
+            }
+            (RecipeSortKey::CreatedAt(micros), SortDirection::Desc) => {
+                let time = get_time(*micros);
+                recipes_query.filter(
+                    created_at
+                        .lt(time)
+                        .or(created_at.eq(time).and(recipe_id.gt(id))),
+                )
+            }
+            (RecipeSortKey::UpdatedAt(micros), SortDirection::Asc) => {
+                let time = get_time(*micros);
+                recipes_query.filter(
+                    updated_at
+                        .gt(time)
+                        .or(updated_at.eq(time).and(recipe_id.gt(id))),
+                )
+            }
+            (RecipeSortKey::UpdatedAt(micros), SortDirection::Desc) => {
+                let time = get_time(*micros);
+                recipes_query.filter(
+                    updated_at
+                        .lt(time)
+                        .or(updated_at.eq(time).and(recipe_id.gt(id))),
+                )
+            }
+            (RecipeSortKey::Title(key), SortDirection::Asc) => recipes_query.filter(
+                lower(title)
+                    .gt(lower(key.clone()))
+                    .or(lower(title).eq(lower(key.clone())).and(recipe_id.gt(id))),
+            ),
+            (RecipeSortKey::Title(key), SortDirection::Desc) => recipes_query.filter(
+                lower(title)
+                    .lt(lower(key.clone()))
+                    .or(lower(title).eq(lower(key.clone())).and(recipe_id.gt(id))),
+            ),
+            // The recipes without any time come last whatever the direction.
+            (RecipeSortKey::TotalTime(None), _) => {
+                recipes_query.filter(no_time.and(recipe_id.gt(id)))
+            }
+            (RecipeSortKey::TotalTime(Some(minutes)), SortDirection::Asc) => recipes_query.filter(
+                no_time
+                    .or(total_time.gt(*minutes))
+                    .or(total_time.eq(*minutes).and(recipe_id.gt(id))),
+            ),
+            (RecipeSortKey::TotalTime(Some(minutes)), SortDirection::Desc) => recipes_query.filter(
+                no_time
+                    .or(total_time.lt(*minutes))
+                    .or(total_time.eq(*minutes).and(recipe_id.gt(id))),
+            ),
+        }
+    }
+
+    /// The recipes of the user and of their household, or the ones shared with
+    /// them, matching the filter.
+    fn filter_my_recipes<'a>(
+        user_id: &'a str,
+        filter: &'a RecipeFilter,
+    ) -> crate::infrastructure::sql::schema::recipes::BoxedQuery<'a, Pg> {
+        use crate::infrastructure::sql::schema::recipe_shares::dsl::{
+            recipe_id as shares_recipe_id, recipe_shares, user_id as shares_user_id,
+        };
+        use crate::infrastructure::sql::schema::recipe_tags::dsl::{
+            recipe_id as tags_recipe_id, recipe_tags, tag,
+        };
+        use crate::infrastructure::sql::schema::recipes::dsl::{
+            category, cook_time_in_minute, cuisine, household_id as recipes_household_id,
+            id as recipe_id, image_url, prep_time_in_minute, recipes, title,
+            user_id as recipes_user_id,
+        };
+        use crate::infrastructure::sql::schema::users::dsl::{
+            household_id as users_household_id, id as users_id, users,
+        };
+        let q = match &filter.query {
+            Some(q) => format!("{}%", q),
+            None => String::from("%"),
+        };
+
+        let mut recipes_query = recipes.filter(title.ilike(q)).into_boxed();
+        if filter.shared_with_me {
+            recipes_query = recipes_query.filter(
+                recipe_id.eq_any(
+                    recipe_shares
+                        .filter(shares_user_id.eq(user_id))
+                        .select(shares_recipe_id),
+                ),
+            );
+        } else {
+            recipes_query = recipes_query.filter(
+                recipes_user_id.eq(user_id).or(recipes_household_id.eq_any(
+                    users
+                        .filter(users_id.eq(user_id))
+                        .select(users_household_id),
+                )),
+            );
+        }
+        for t in &filter.tags {
+            recipes_query = recipes_query
+                .filter(recipe_id.eq_any(recipe_tags.filter(tag.eq(t)).select(tags_recipe_id)));
+        }
+        if !filter.categories.is_empty() {
+            recipes_query = recipes_query.filter(btrim(category).eq_any(filter.categories.clone()));
+        }
+        if !filter.cuisines.is_empty() {
+            recipes_query = recipes_query.filter(btrim(cuisine).eq_any(filter.cuisines.clone()));
+        }
+        if let Some(max_total_time) = filter.max_total_time_in_minute {
+            recipes_query = recipes_query
+                .filter(
+                    prep_time_in_minute
+                        .is_not_null()
+                        .or(cook_time_in_minute.is_not_null()),
+                )
+                .filter(
+                    (coalesce(prep_time_in_minute, 0) + coalesce(cook_time_in_minute, 0))
+                        .le(max_total_time),
+                );
+        }
+        match filter.has_image {
+            Some(true) => {
+                recipes_query = recipes_query.filter(image_url.is_not_null().and(image_url.ne("")))
+            }
+            Some(false) => {
+                recipes_query = recipes_query.filter(image_url.is_null().or(image_url.eq("")))
+            }
+            None => (),
+        }
+        recipes_query
+    }
+
+    /// Loads the instructions, ingredients and tags of the recipes.
+    fn load_recipes(
+        &self,
+        recipes_results: Vec<Recipe>,
+    ) -> Result<Vec<DomainRecipe>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::ingredients::dsl::step_number as ingredient_step_number;
+        use crate::infrastructure::sql::schema::instructions::dsl::step_number as instructions_step_number;
+        use crate::infrastructure::sql::schema::recipe_tags::dsl::tag;
+
+        let instructions_results = Instruction::belonging_to(&recipes_results)
+            .order_by(instructions_step_number.asc())
+            .load::<Instruction>(&*self.connection)?
+            .grouped_by(&recipes_results);
+
+        let ingredients_results = Ingredient::belonging_to(&recipes_results)
+            .order_by(ingredient_step_number.asc())
+            .load::<Ingredient>(&*self.connection)?
+            .grouped_by(&recipes_results);
+
+        let tags_results = RecipeTag::belonging_to(&recipes_results)
+            .order_by(tag.asc())
+            .load::<RecipeTag>(&*self.connection)?
+            .grouped_by(&recipes_results);
+
+        let data = izip!(
+            &recipes_results,
+            ingredients_results,
+            instructions_results,
+            tags_results
+        );
+        Ok(data
+            .map(|(recipe, ingredients, instructions, tags)| {
+                DomainRecipe::from(recipe, instructions, ingredients, tags)
+            })
+            .collect())
+    }
+
+    /// Indexes the title, description, ingredients and instructions of the
+    /// recipe for `search_recipes`.
+    fn refresh_search_vector(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        diesel::sql_query("SELECT refresh_recipe_search_vector($1)")
+            .bind::<Varchar, _>(id)
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+
     fn insert_instructions<'a>(
         &self,
         recipe_id: &'a str,
         instructions_to_insert: &[&'a str],
+        sections: &[Option<&'a str>],
     ) -> Result<Vec<Instruction>, Box<dyn Error>> {
         use crate::infrastructure::sql::schema::instructions;
 
@@ -256,12 +894,13 @@ impl DieselRecipeDao {
                 recipe_id,
                 step_number: i as i32 + 1,
                 instruction: instuction,
+                section: sections.get(i).cloned().flatten(),
             })
             .collect();
 
         let inserted_instructions: Vec<Instruction> = diesel::insert_into(instructions::table)
             .values(&new_instructions)
-            .get_results(&self.connection)?;
+            .get_results(&*self.connection)?;
         Ok(inserted_instructions)
     }
 
@@ -269,27 +908,59 @@ impl DieselRecipeDao {
         &self,
         recipe_id: &'a str,
         ingredients_to_insert: &[&'a str],
+        sections: &[Option<&'a str>],
+        structured_ingredients: &'a [StructuredIngredient],
     ) -> Result<Vec<Ingredient>, Box<dyn Error>> {
         use crate::infrastructure::sql::schema::ingredients;
         let new_ingredients: Vec<NewIngredient> = ingredients_to_insert
             .iter()
+            .zip(structured_ingredients.iter())
             .enumerate()
-            .map(|(i, ingredient)| NewIngredient {
+            .map(|(i, (ingredient, structured))| NewIngredient {
                 recipe_id,
                 step_number: i as i32 + 1,
                 ingredient,
+                section: sections.get(i).cloned().flatten(),
+                quantity: structured.quantity,
+                max_quantity: structured.max_quantity,
+                unit: structured.unit.as_deref(),
+                name: Some(structured.name.as_str()),
+                note: structured.note.as_deref(),
             })
             .collect();
 
         let inserted_ingredients: Vec<Ingredient> = diesel::insert_into(ingredients::table)
             .values(&new_ingredients)
-            .get_results(&self.connection)?;
+            .get_results(&*self.connection)?;
         Ok(inserted_ingredients)
     }
+
+    fn insert_tags(
+        &self,
+        recipe_id: &str,
+        tags_to_insert: &[&str],
+    ) -> Result<Vec<RecipeTag>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::recipe_tags;
+        let new_tags: Vec<NewRecipeTag> = tags_to_insert
+            .iter()
+            .map(|tag| NewRecipeTag { recipe_id, tag })
+            .collect();
+
+        let inserted_tags: Vec<RecipeTag> = diesel::insert_into(recipe_tags::table)
+            .values(&new_tags)
+            .on_conflict_do_nothing()
+            .get_results(&*self.connection)?;
+        Ok(inserted_tags)
+    }
 }
 
 impl DomainRecipe {
-    fn from(recipe: &Recipe, instructions: Vec<Instruction>, ingredients: Vec<Ingredient>) -> Self {
+    fn from(
+        recipe: &Recipe,
+        instructions: Vec<Instruction>,
+        ingredients: Vec<Ingredient>,
+        tags: Vec<RecipeTag>,
+    ) -> Self {
         let id = Uuid::parse_str(recipe.id.as_str()).expect("Cannot parse UUID");
         DomainRecipe {
             id,
@@ -305,7 +976,119 @@ impl DomainRecipe {
             cook_time_in_minute: recipe.cook_time_in_minute,
             prep_time_in_minute: recipe.prep_time_in_minute,
             instructions: instructions.iter().map(|i| i.instruction.clone()).collect(),
+            instruction_sections: instructions.iter().map(|i| i.section.clone()).collect(),
             ingredients: ingredients.iter().map(|i| i.ingredient.clone()).collect(),
+            ingredient_sections: ingredients.iter().map(|i| i.section.clone()).collect(),
+            structured_ingredients: ingredients.iter().map(get_structured_ingredient).collect(),
+            tags: tags.into_iter().map(|t| t.tag).collect(),
+            visibility: RecipeVisibility::parse(&recipe.visibility)
+                .unwrap_or(RecipeVisibility::Private),
+            household_id: recipe.household_id.clone(),
+        }
+    }
+}
+
+/// Ingredients saved before their structured columns existed are parsed on read.
+fn get_structured_ingredient(ingredient: &Ingredient) -> StructuredIngredient {
+    match &ingredient.name {
+        Some(name) => StructuredIngredient {
+            quantity: ingredient.quantity,
+            max_quantity: ingredient.max_quantity,
+            unit: ingredient.unit.clone(),
+            name: name.clone(),
+            note: ingredient.note.clone(),
+        },
+        None => StructuredIngredient::parse(&ingredient.ingredient),
+    }
+}
+
+fn get_facet_values(values: Vec<(Option<String>, i64)>) -> Vec<FacetValue> {
+    values
+        .into_iter()
+        .filter_map(|(value, count)| {
+            value.map(|value| FacetValue {
+                value,
+                count: count as usize,
+            })
+        })
+        .collect()
+}
+
+/// Where the recipe stands when sorted by this field.
+fn get_cursor(recipe: &Recipe, field: RecipeSortField) -> RecipeCursor {
+    let key = match field {
+        RecipeSortField::CreatedAt => RecipeSortKey::CreatedAt(get_micros(recipe.created_at)),
+        RecipeSortField::UpdatedAt => RecipeSortKey::UpdatedAt(get_micros(recipe.updated_at)),
+        RecipeSortField::Title => RecipeSortKey::Title(recipe.title.clone()),
+        RecipeSortField::TotalTime => {
+            match (recipe.prep_time_in_minute, recipe.cook_time_in_minute) {
+                (None, None) => RecipeSortKey::TotalTime(None),
+                (prep, cook) => RecipeSortKey::TotalTime(Some(
+                    prep.unwrap_or(0).saturating_add(cook.unwrap_or(0)),
+                )),
+            }
+        }
+    };
+    RecipeCursor {
+        key,
+        id: recipe.id.clone(),
+    }
+}
+
+/// Microseconds since the epoch, the precision of postgres timestamps.
+fn get_micros(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+fn get_time(micros: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_micros(micros)
+}
+
+impl From<&DomainRecipe> for RecipeSnapshot {
+    fn from(recipe: &DomainRecipe) -> Self {
+        RecipeSnapshot {
+            user_id: recipe.user_id.clone(),
+            title: recipe.title.clone(),
+            description: recipe.description.clone(),
+            cook_time_in_minute: recipe.cook_time_in_minute,
+            prep_time_in_minute: recipe.prep_time_in_minute,
+            image_url: recipe.image_url.clone(),
+            recipe_yield: recipe.recipe_yield.clone(),
+            category: recipe.category.clone(),
+            cuisine: recipe.cuisine.clone(),
+            instructions: recipe.instructions.clone(),
+            instruction_sections: recipe.instruction_sections.clone(),
+            ingredients: recipe.ingredients.clone(),
+            ingredient_sections: recipe.ingredient_sections.clone(),
+            imported_from: recipe.imported_from.clone(),
+        }
+    }
+}
+
+impl RecipeSnapshot {
+    fn into_recipe(self, id: &str) -> DomainRecipe {
+        DomainRecipe {
+            id: Uuid::parse_str(id).expect("Cannot parse UUID"),
+            user_id: self.user_id,
+            title: self.title,
+            description: self.description,
+            cook_time_in_minute: self.cook_time_in_minute,
+            prep_time_in_minute: self.prep_time_in_minute,
+            image_url: self.image_url,
+            recipe_yield: self.recipe_yield,
+            category: self.category,
+            cuisine: self.cuisine,
+            instructions: self.instructions,
+            instruction_sections: self.instruction_sections,
+            structured_ingredients: StructuredIngredient::parse_all(&self.ingredients),
+            ingredients: self.ingredients,
+            ingredient_sections: self.ingredient_sections,
+            tags: vec![],
+            visibility: RecipeVisibility::Private,
+            household_id: None,
+            imported_from: self.imported_from,
         }
     }
 }
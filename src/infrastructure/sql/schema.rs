@@ -1,8 +1,49 @@
+table! {
+    collection_recipes (collection_id, recipe_id) {
+        collection_id -> Varchar,
+        recipe_id -> Varchar,
+        position -> Int4,
+    }
+}
+
+table! {
+    collections (id) {
+        id -> Varchar,
+        user_id -> Varchar,
+        name -> Varchar,
+        description -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    household_invitations (household_id, email) {
+        household_id -> Varchar,
+        email -> Varchar,
+    }
+}
+
+table! {
+    households (id) {
+        id -> Varchar,
+        name -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     ingredients (recipe_id, step_number) {
         step_number -> Int4,
         recipe_id -> Varchar,
         ingredient -> Text,
+        section -> Nullable<Varchar>,
+        quantity -> Nullable<Float8>,
+        max_quantity -> Nullable<Float8>,
+        unit -> Nullable<Varchar>,
+        name -> Nullable<Varchar>,
+        note -> Nullable<Varchar>,
     }
 }
 
@@ -11,6 +52,57 @@ table! {
         step_number -> Int4,
         recipe_id -> Varchar,
         instruction -> Text,
+        section -> Nullable<Varchar>,
+    }
+}
+
+table! {
+    meal_plan_entries (id) {
+        id -> Varchar,
+        user_id -> Varchar,
+        recipe_id -> Varchar,
+        date -> Date,
+        slot -> Varchar,
+        servings -> Nullable<Int4>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    pantry_items (id) {
+        id -> Varchar,
+        user_id -> Varchar,
+        name -> Varchar,
+        quantity -> Nullable<Float8>,
+        unit -> Nullable<Varchar>,
+        expires_on -> Nullable<Date>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    recipe_import_snapshots (recipe_id) {
+        recipe_id -> Varchar,
+        snapshot -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    recipe_shares (recipe_id, user_id) {
+        recipe_id -> Varchar,
+        user_id -> Varchar,
+        role -> Varchar,
+    }
+}
+
+table! {
+    recipe_tags (recipe_id, tag) {
+        recipe_id -> Varchar,
+        tag -> Varchar,
     }
 }
 
@@ -29,6 +121,32 @@ table! {
         imported_from -> Nullable<Varchar>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        visibility -> Varchar,
+        household_id -> Nullable<Varchar>,
+    }
+}
+
+table! {
+    shopping_list_items (id) {
+        id -> Varchar,
+        shopping_list_id -> Varchar,
+        position -> Int4,
+        text -> Varchar,
+        name -> Varchar,
+        quantity -> Nullable<Float8>,
+        unit -> Nullable<Varchar>,
+        aisle -> Varchar,
+        checked -> Bool,
+    }
+}
+
+table! {
+    shopping_lists (id) {
+        id -> Varchar,
+        user_id -> Varchar,
+        name -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -39,11 +157,45 @@ table! {
         password_hash -> Varchar,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        unit_system -> Nullable<Varchar>,
+        household_id -> Nullable<Varchar>,
+        calendar_token -> Nullable<Varchar>,
     }
 }
 
+joinable!(collection_recipes -> collections (collection_id));
+joinable!(collection_recipes -> recipes (recipe_id));
+joinable!(collections -> users (user_id));
+joinable!(household_invitations -> households (household_id));
 joinable!(ingredients -> recipes (recipe_id));
 joinable!(instructions -> recipes (recipe_id));
+joinable!(meal_plan_entries -> recipes (recipe_id));
+joinable!(meal_plan_entries -> users (user_id));
+joinable!(pantry_items -> users (user_id));
+joinable!(recipe_import_snapshots -> recipes (recipe_id));
+joinable!(recipe_shares -> recipes (recipe_id));
+joinable!(recipe_shares -> users (user_id));
+joinable!(recipe_tags -> recipes (recipe_id));
+joinable!(recipes -> households (household_id));
 joinable!(recipes -> users (user_id));
+joinable!(shopping_list_items -> shopping_lists (shopping_list_id));
+joinable!(shopping_lists -> users (user_id));
+joinable!(users -> households (household_id));
 
-allow_tables_to_appear_in_same_query!(ingredients, instructions, recipes, users,);
+allow_tables_to_appear_in_same_query!(
+    collection_recipes,
+    collections,
+    household_invitations,
+    households,
+    ingredients,
+    instructions,
+    meal_plan_entries,
+    pantry_items,
+    recipe_import_snapshots,
+    recipe_shares,
+    recipe_tags,
+    recipes,
+    shopping_list_items,
+    shopping_lists,
+    users,
+);
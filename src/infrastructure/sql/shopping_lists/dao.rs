@@ -0,0 +1,161 @@
+use crate::diesel::prelude::*;
+use crate::domain::shopping_lists::errors::ShoppingListError;
+use crate::domain::shopping_lists::models::shopping_list::{
+    Aisle, ShoppingList as DomainShoppingList, ShoppingListItem as DomainShoppingListItem,
+};
+use crate::domain::shopping_lists::ports::dao::{
+    NewShoppingList as DomainNewShoppingList, ShoppingListDao,
+};
+use crate::infrastructure::sql::models::*;
+use crate::infrastructure::sql::SharedConnection;
+
+use std::error::Error;
+use uuid::Uuid;
+
+pub struct DieselShoppingListDao {
+    connection: SharedConnection,
+}
+
+impl ShoppingListDao for DieselShoppingListDao {
+    fn get_my_shopping_lists(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<DomainShoppingList>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::shopping_list_items::dsl::position;
+        use crate::infrastructure::sql::schema::shopping_lists::dsl::{
+            created_at, shopping_lists, user_id as shopping_lists_user_id,
+        };
+
+        let shopping_lists_results = shopping_lists
+            .filter(shopping_lists_user_id.eq(user_id))
+            .order_by(created_at.desc())
+            .load::<ShoppingList>(&*self.connection)?;
+
+        let items_results = ShoppingListItem::belonging_to(&shopping_lists_results)
+            .order_by(position.asc())
+            .load::<ShoppingListItem>(&*self.connection)?
+            .grouped_by(&shopping_lists_results);
+
+        Ok(shopping_lists_results
+            .iter()
+            .zip(items_results)
+            .map(|(shopping_list, items)| DomainShoppingList::from(shopping_list, items))
+            .collect())
+    }
+
+    fn get_shopping_list(&self, id: &str) -> Result<DomainShoppingList, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::shopping_list_items::dsl::position;
+        use crate::infrastructure::sql::schema::shopping_lists::dsl::{
+            id as shopping_list_id, shopping_lists,
+        };
+
+        let shopping_list = shopping_lists
+            .filter(shopping_list_id.eq(id))
+            .first::<ShoppingList>(&*self.connection)
+            .optional()?
+            .ok_or(ShoppingListError::ShoppingListNotFound)?;
+
+        let items = ShoppingListItem::belonging_to(&shopping_list)
+            .order_by(position.asc())
+            .load::<ShoppingListItem>(&*self.connection)?;
+
+        Ok(DomainShoppingList::from(&shopping_list, items))
+    }
+
+    fn add_shopping_list(
+        &self,
+        new_shopping_list: DomainNewShoppingList,
+        items: &[DomainShoppingListItem],
+    ) -> Result<DomainShoppingList, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::{shopping_list_items, shopping_lists};
+
+        self.connection.transaction::<_, Box<dyn Error>, _>(|| {
+            diesel::insert_into(shopping_lists::table)
+                .values(&NewShoppingList {
+                    id: new_shopping_list.id,
+                    user_id: new_shopping_list.user_id,
+                    name: new_shopping_list.name,
+                })
+                .execute(&*self.connection)?;
+            let item_ids: Vec<String> = items
+                .iter()
+                .map(|item| item.id.to_hyphenated().to_string())
+                .collect();
+            let new_items: Vec<NewShoppingListItem> = items
+                .iter()
+                .zip(&item_ids)
+                .enumerate()
+                .map(|(i, (item, item_id))| NewShoppingListItem {
+                    id: item_id,
+                    shopping_list_id: new_shopping_list.id,
+                    position: i as i32 + 1,
+                    text: &item.text,
+                    name: &item.name,
+                    quantity: item.quantity,
+                    unit: item.unit.as_deref(),
+                    aisle: item.aisle.as_str(),
+                    checked: item.checked,
+                })
+                .collect();
+            diesel::insert_into(shopping_list_items::table)
+                .values(&new_items)
+                .execute(&*self.connection)?;
+            Ok(())
+        })?;
+        self.get_shopping_list(new_shopping_list.id)
+    }
+
+    fn set_item_checked(&self, item_id: &str, is_checked: bool) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::shopping_list_items::dsl::{
+            checked, id, shopping_list_items,
+        };
+
+        diesel::update(shopping_list_items.filter(id.eq(item_id)))
+            .set(checked.eq(is_checked))
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+
+    fn delete_shopping_list(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::shopping_list_items::dsl::{
+            shopping_list_id as items_shopping_list_id, shopping_list_items,
+        };
+        use crate::infrastructure::sql::schema::shopping_lists::dsl::{
+            id as shopping_list_id, shopping_lists,
+        };
+
+        diesel::delete(shopping_list_items.filter(items_shopping_list_id.eq(id)))
+            .execute(&*self.connection)?;
+        diesel::delete(shopping_lists.filter(shopping_list_id.eq(id)))
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+}
+
+impl DieselShoppingListDao {
+    pub fn new(connection: SharedConnection) -> DieselShoppingListDao {
+        DieselShoppingListDao { connection }
+    }
+}
+
+impl DomainShoppingList {
+    fn from(shopping_list: &ShoppingList, items: Vec<ShoppingListItem>) -> Self {
+        DomainShoppingList {
+            id: Uuid::parse_str(shopping_list.id.as_str()).expect("Cannot parse UUID"),
+            user_id: shopping_list.user_id.clone(),
+            name: shopping_list.name.clone(),
+            items: items
+                .into_iter()
+                .map(|item| DomainShoppingListItem {
+                    id: Uuid::parse_str(item.id.as_str()).expect("Cannot parse UUID"),
+                    text: item.text,
+                    name: item.name,
+                    quantity: item.quantity,
+                    unit: item.unit,
+                    aisle: Aisle::parse(&item.aisle).unwrap_or(Aisle::Other),
+                    checked: item.checked,
+                })
+                .collect(),
+        }
+    }
+}
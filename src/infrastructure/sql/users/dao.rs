@@ -1,19 +1,18 @@
 use crate::diesel::prelude::*;
 use crate::diesel::result::DatabaseErrorKind;
 use crate::diesel::result::Error as DieselError;
+use crate::domain::recipes::models::conversion::UnitSystem;
 use crate::domain::users::errors::UserError;
 use crate::domain::users::models::user::User as DomainUser;
 use crate::domain::users::ports::dao::UserDao;
 use crate::infrastructure::sql::models::*;
+use crate::infrastructure::sql::SharedConnection;
 
 use std::error::Error;
 use uuid::Uuid;
 
-use diesel::pg::PgConnection;
-use diesel::r2d2::{ConnectionManager, PooledConnection};
-
 pub struct DieselUserDao {
-    connection: PooledConnection<ConnectionManager<PgConnection>>,
+    connection: SharedConnection,
 }
 
 impl UserDao for DieselUserDao {
@@ -33,7 +32,7 @@ impl UserDao for DieselUserDao {
 
         let inserted_user: User = diesel::insert_into(users::table)
             .values(&new_user_sql)
-            .get_result(&self.connection)
+            .get_result(&*self.connection)
             .map_err(|err| match err {
                 DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
                     UserError::UserAlreadyExists
@@ -54,7 +53,7 @@ impl UserDao for DieselUserDao {
         let users_results = users
             .filter(db_email.eq(email))
             .filter(db_password_hash.eq(password_hash))
-            .load::<User>(&self.connection)?;
+            .load::<User>(&*self.connection)?;
 
         let user: &User = users_results.first().ok_or(UserError::BadCredentials)?;
         Ok(DomainUser {
@@ -66,7 +65,91 @@ impl UserDao for DieselUserDao {
     fn get_user(&self, id: &str) -> Result<DomainUser, Box<dyn Error>> {
         use crate::infrastructure::sql::schema::users::dsl::{id as db_id, users};
 
-        let users_results = users.filter(db_id.eq(id)).load::<User>(&self.connection)?;
+        let users_results = users.filter(db_id.eq(id)).load::<User>(&*self.connection)?;
+
+        let user: &User = users_results.first().ok_or(UserError::UserNotFound)?;
+        Ok(DomainUser {
+            id: Uuid::parse_str(user.id.as_str()).expect("Cannot parse UUID"),
+            email: user.email.clone(),
+        })
+    }
+
+    fn get_user_by_email(&self, email: &str) -> Result<DomainUser, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::users::dsl::{email as db_email, users};
+
+        let users_results = users
+            .filter(db_email.eq(email))
+            .load::<User>(&*self.connection)?;
+
+        let user: &User = users_results.first().ok_or(UserError::UserNotFound)?;
+        Ok(DomainUser {
+            id: Uuid::parse_str(user.id.as_str()).expect("Cannot parse UUID"),
+            email: user.email.clone(),
+        })
+    }
+
+    fn get_household_id(&self, id: &str) -> Result<Option<String>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::users::dsl::{household_id, id as db_id, users};
+
+        let household_ids = users
+            .filter(db_id.eq(id))
+            .select(household_id)
+            .load::<Option<String>>(&*self.connection)?;
+
+        let user_household_id = household_ids.first().ok_or(UserError::UserNotFound)?;
+        Ok(user_household_id.clone())
+    }
+
+    fn set_household_id(
+        &self,
+        id: &str,
+        new_household_id: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::users::dsl::{household_id, id as db_id, users};
+
+        let updated = diesel::update(users.filter(db_id.eq(id)))
+            .set(household_id.eq(new_household_id))
+            .execute(&*self.connection)?;
+        if updated == 0 {
+            return Err(Box::new(UserError::UserNotFound));
+        }
+        Ok(())
+    }
+
+    fn get_unit_system(&self, id: &str) -> Result<Option<UnitSystem>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::users::dsl::{id as db_id, unit_system, users};
+
+        let unit_systems = users
+            .filter(db_id.eq(id))
+            .select(unit_system)
+            .load::<Option<String>>(&*self.connection)?;
+
+        let user_unit_system = unit_systems.first().ok_or(UserError::UserNotFound)?;
+        Ok(user_unit_system.as_deref().and_then(UnitSystem::parse))
+    }
+
+    fn set_unit_system(
+        &self,
+        id: &str,
+        new_unit_system: Option<UnitSystem>,
+    ) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::users::dsl::{id as db_id, unit_system, users};
+
+        let updated = diesel::update(users.filter(db_id.eq(id)))
+            .set(unit_system.eq(new_unit_system.map(UnitSystem::as_str)))
+            .execute(&*self.connection)?;
+        if updated == 0 {
+            return Err(Box::new(UserError::UserNotFound));
+        }
+        Ok(())
+    }
+
+    fn get_user_by_calendar_token(&self, token: &str) -> Result<DomainUser, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::users::dsl::{calendar_token, users};
+
+        let users_results = users
+            .filter(calendar_token.eq(token))
+            .load::<User>(&*self.connection)?;
 
         let user: &User = users_results.first().ok_or(UserError::UserNotFound)?;
         Ok(DomainUser {
@@ -74,10 +157,38 @@ impl UserDao for DieselUserDao {
             email: user.email.clone(),
         })
     }
+
+    fn get_calendar_token(&self, id: &str) -> Result<Option<String>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::users::dsl::{calendar_token, id as db_id, users};
+
+        let calendar_tokens = users
+            .filter(db_id.eq(id))
+            .select(calendar_token)
+            .load::<Option<String>>(&*self.connection)?;
+
+        let user_calendar_token = calendar_tokens.first().ok_or(UserError::UserNotFound)?;
+        Ok(user_calendar_token.clone())
+    }
+
+    fn set_calendar_token(
+        &self,
+        id: &str,
+        new_calendar_token: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::users::dsl::{calendar_token, id as db_id, users};
+
+        let updated = diesel::update(users.filter(db_id.eq(id)))
+            .set(calendar_token.eq(new_calendar_token))
+            .execute(&*self.connection)?;
+        if updated == 0 {
+            return Err(Box::new(UserError::UserNotFound));
+        }
+        Ok(())
+    }
 }
 
 impl DieselUserDao {
-    pub fn new(connection: PooledConnection<ConnectionManager<PgConnection>>) -> DieselUserDao {
+    pub fn new(connection: SharedConnection) -> DieselUserDao {
         DieselUserDao { connection }
     }
 }
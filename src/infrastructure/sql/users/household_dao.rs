@@ -0,0 +1,100 @@
+use crate::diesel::prelude::*;
+use crate::domain::users::errors::UserError;
+use crate::domain::users::models::household::Household as DomainHousehold;
+use crate::domain::users::models::user::User as DomainUser;
+use crate::domain::users::ports::household_dao::HouseholdDao;
+use crate::infrastructure::sql::models::*;
+use crate::infrastructure::sql::SharedConnection;
+
+use std::error::Error;
+use uuid::Uuid;
+
+pub struct DieselHouseholdDao {
+    connection: SharedConnection,
+}
+
+impl HouseholdDao for DieselHouseholdDao {
+    fn get_household(&self, id: &str) -> Result<DomainHousehold, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::households::dsl::households;
+        use crate::infrastructure::sql::schema::users::dsl::{email, household_id, users};
+
+        let household = households
+            .find(id)
+            .first::<Household>(&*self.connection)
+            .optional()?
+            .ok_or(UserError::HouseholdNotFound)?;
+        let members = users
+            .filter(household_id.eq(id))
+            .order_by(email.asc())
+            .load::<User>(&*self.connection)?;
+        Ok(DomainHousehold {
+            id: Uuid::parse_str(household.id.as_str()).expect("Cannot parse UUID"),
+            name: household.name,
+            members: members
+                .into_iter()
+                .map(|member| DomainUser {
+                    id: Uuid::parse_str(member.id.as_str()).expect("Cannot parse UUID"),
+                    email: member.email,
+                })
+                .collect(),
+        })
+    }
+
+    fn add_household(&self, id: &str, name: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::households;
+
+        diesel::insert_into(households::table)
+            .values(&NewHousehold { id, name })
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+
+    fn get_invited_households(&self, email: &str) -> Result<Vec<DomainHousehold>, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::household_invitations::dsl::{
+            email as invitations_email, household_id, household_invitations,
+        };
+
+        let household_ids = household_invitations
+            .filter(invitations_email.eq(email))
+            .select(household_id)
+            .order_by(household_id.asc())
+            .load::<String>(&*self.connection)?;
+        household_ids
+            .iter()
+            .map(|id| self.get_household(id))
+            .collect()
+    }
+
+    fn add_invitation(&self, id: &str, email: &str) -> Result<(), Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::household_invitations;
+
+        diesel::insert_into(household_invitations::table)
+            .values(&NewHouseholdInvitation {
+                household_id: id,
+                email,
+            })
+            .on_conflict_do_nothing()
+            .execute(&*self.connection)?;
+        Ok(())
+    }
+
+    fn delete_invitation(&self, id: &str, email: &str) -> Result<bool, Box<dyn Error>> {
+        use crate::infrastructure::sql::schema::household_invitations::dsl::{
+            email as invitations_email, household_id, household_invitations,
+        };
+
+        let deleted = diesel::delete(
+            household_invitations
+                .filter(household_id.eq(id))
+                .filter(invitations_email.eq(email)),
+        )
+        .execute(&*self.connection)?;
+        Ok(deleted > 0)
+    }
+}
+
+impl DieselHouseholdDao {
+    pub fn new(connection: SharedConnection) -> DieselHouseholdDao {
+        DieselHouseholdDao { connection }
+    }
+}
@@ -1,22 +1,332 @@
+use crate::domain::collections::errors::CollectionError;
+use crate::domain::collections::interactors::collection::CollectionInteractor;
+use crate::domain::collections::models::collection::Collection;
+use crate::domain::meal_plans::errors::MealPlanError;
+use crate::domain::meal_plans::interactors::meal_plan::MealPlanInteractor;
+use crate::domain::meal_plans::models::meal_plan::{MealPlan, MealPlanEntry, MealSlot};
+use crate::domain::pantries::errors::PantryError;
+use crate::domain::pantries::interactors::pantry::PantryInteractor;
+use crate::domain::pantries::models::pantry_item::PantryItem;
+use crate::domain::recipes::errors::RecipeError;
 use crate::domain::recipes::interactors::recipe::RecipeInteractor;
-use crate::domain::recipes::models::recipe::Recipe;
+use crate::domain::recipes::models::conversion::{convert_recipe, RecipeConversion, UnitSystem};
+use crate::domain::recipes::models::cookable::{
+    get_ingredient_words, uses_ingredient, CookableRecipe,
+};
+use crate::domain::recipes::models::facet::{FacetValue, RecipeFacets};
+use crate::domain::recipes::models::ingredient::StructuredIngredient;
+use crate::domain::recipes::models::page::{
+    encode_cursor, RecipePage, RecipeSort, RecipeSortField, SortDirection,
+};
+use crate::domain::recipes::models::recipe::{Recipe, RecipeVisibility};
+use crate::domain::recipes::models::resync::RecipeResync;
+use crate::domain::recipes::models::scaling::{scale_recipe, RecipeScaling};
+use crate::domain::recipes::models::search::RecipeSearchResult;
+use crate::domain::recipes::models::share::{RecipeShare, ShareRole};
+use crate::domain::recipes::ports::dao::RecipeFilter;
+use crate::domain::shopping_lists::errors::ShoppingListError;
+use crate::domain::shopping_lists::interactors::shopping_list::ShoppingListInteractor;
+use crate::domain::shopping_lists::models::shopping_list::{
+    Aisle, RecipeIdServings, ShoppingList, ShoppingListItem,
+};
 use crate::domain::users::errors::UserError;
 use crate::domain::users::interactors::user::UserInteractor;
-use crate::infrastructure::parser::html::SelectParser;
+use crate::domain::users::models::household::Household;
 use crate::infrastructure::s3::image_store::S3ImageStore;
+use crate::infrastructure::sql::collections::dao::DieselCollectionDao;
+use crate::infrastructure::sql::meal_plans::dao::DieselMealPlanDao;
+use crate::infrastructure::sql::pantries::dao::DieselPantryDao;
 use crate::infrastructure::sql::recipes::dao::DieselRecipeDao;
+use crate::infrastructure::sql::shopping_lists::dao::DieselShoppingListDao;
 use crate::infrastructure::sql::users::dao::DieselUserDao;
+use crate::infrastructure::sql::users::household_dao::DieselHouseholdDao;
 use crate::infrastructure::web::jwt::generate_header;
 use crate::infrastructure::web::security::LoggedUser;
-use crate::infrastructure::web::server::DbCon;
+use crate::infrastructure::web::server::{DbCon, SharedParser};
 use diesel::pg::PgConnection;
 use diesel::r2d2::{ConnectionManager, PooledConnection};
 use rocket::Outcome;
 
-use juniper::FieldResult;
+use chrono::{NaiveDate, Utc};
+use juniper::{graphql_value, FieldError, FieldResult};
 use rocket::request::{self, FromRequest, Request};
+use rocket::State;
+use std::error::Error;
+use std::rc::Rc;
+use std::slice;
 use uuid::Uuid;
 
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "An ingredient line split into quantity, unit and name")]
+struct IngredientGraphQL {
+    text: String,
+    quantity: Option<f64>,
+    max_quantity: Option<f64>,
+    unit: Option<String>,
+    name: String,
+    note: Option<String>,
+}
+
+impl IngredientGraphQL {
+    fn from(text: &str, ingredient: &StructuredIngredient) -> IngredientGraphQL {
+        IngredientGraphQL {
+            text: String::from(text),
+            quantity: ingredient.quantity,
+            max_quantity: ingredient.max_quantity,
+            unit: ingredient.unit.clone(),
+            name: ingredient.name.clone(),
+            note: ingredient.note.clone(),
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "An ingredient line rescaled to another number of servings")]
+struct ScaledIngredientGraphQL {
+    text: String,
+    quantity: Option<f64>,
+    max_quantity: Option<f64>,
+    unit: Option<String>,
+    name: String,
+    note: Option<String>,
+    scaled: bool,
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "The ingredients of a recipe rescaled to another number of servings")]
+struct RecipeScalingGraphQL {
+    servings: i32,
+    factor: f64,
+    ingredients: Vec<ScaledIngredientGraphQL>,
+}
+
+impl RecipeScalingGraphQL {
+    fn from(scaling: RecipeScaling) -> RecipeScalingGraphQL {
+        RecipeScalingGraphQL {
+            servings: scaling.servings,
+            factor: scaling.factor,
+            ingredients: scaling
+                .ingredients
+                .into_iter()
+                .map(|i| ScaledIngredientGraphQL {
+                    text: i.text,
+                    quantity: i.ingredient.quantity,
+                    max_quantity: i.ingredient.max_quantity,
+                    unit: i.ingredient.unit,
+                    name: i.ingredient.name,
+                    note: i.ingredient.note,
+                    scaled: i.scaled,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(juniper::GraphQLEnum, Clone, Copy)]
+#[graphql(description = "A system of measurement units")]
+enum UnitSystemGraphQL {
+    Metric,
+    Imperial,
+}
+
+impl UnitSystemGraphQL {
+    fn from(unit_system: UnitSystem) -> UnitSystemGraphQL {
+        match unit_system {
+            UnitSystem::Metric => UnitSystemGraphQL::Metric,
+            UnitSystem::Imperial => UnitSystemGraphQL::Imperial,
+        }
+    }
+
+    fn into_unit_system(self) -> UnitSystem {
+        match self {
+            UnitSystemGraphQL::Metric => UnitSystem::Metric,
+            UnitSystemGraphQL::Imperial => UnitSystem::Imperial,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLEnum, Clone, Copy)]
+#[graphql(description = "Who can read a recipe besides its owner")]
+enum RecipeVisibilityGraphQL {
+    Private,
+    Unlisted,
+    Public,
+}
+
+impl RecipeVisibilityGraphQL {
+    fn from(visibility: RecipeVisibility) -> RecipeVisibilityGraphQL {
+        match visibility {
+            RecipeVisibility::Private => RecipeVisibilityGraphQL::Private,
+            RecipeVisibility::Unlisted => RecipeVisibilityGraphQL::Unlisted,
+            RecipeVisibility::Public => RecipeVisibilityGraphQL::Public,
+        }
+    }
+
+    fn into_visibility(self) -> RecipeVisibility {
+        match self {
+            RecipeVisibilityGraphQL::Private => RecipeVisibility::Private,
+            RecipeVisibilityGraphQL::Unlisted => RecipeVisibility::Unlisted,
+            RecipeVisibilityGraphQL::Public => RecipeVisibility::Public,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLEnum, Clone, Copy)]
+#[graphql(description = "What to sort recipes by")]
+enum RecipeSortFieldGraphQL {
+    CreatedAt,
+    UpdatedAt,
+    Title,
+    /// Preparation plus cooking time, recipes without any time last
+    TotalTime,
+}
+
+#[derive(juniper::GraphQLEnum, Clone, Copy)]
+#[graphql(description = "Ascending or descending order")]
+enum SortDirectionGraphQL {
+    Asc,
+    Desc,
+}
+
+#[derive(juniper::GraphQLInputObject)]
+#[graphql(description = "How to sort recipes")]
+struct RecipeSortGraphQL {
+    field: RecipeSortFieldGraphQL,
+    /// Ascending by default.
+    direction: Option<SortDirectionGraphQL>,
+}
+
+impl RecipeSortGraphQL {
+    fn into_sort(self) -> RecipeSort {
+        RecipeSort {
+            field: match self.field {
+                RecipeSortFieldGraphQL::CreatedAt => RecipeSortField::CreatedAt,
+                RecipeSortFieldGraphQL::UpdatedAt => RecipeSortField::UpdatedAt,
+                RecipeSortFieldGraphQL::Title => RecipeSortField::Title,
+                RecipeSortFieldGraphQL::TotalTime => RecipeSortField::TotalTime,
+            },
+            direction: match self.direction {
+                Some(SortDirectionGraphQL::Desc) => SortDirection::Desc,
+                Some(SortDirectionGraphQL::Asc) | None => SortDirection::Asc,
+            },
+        }
+    }
+}
+
+#[derive(juniper::GraphQLInputObject, Default)]
+#[graphql(description = "Which recipes to list, all of them by default")]
+struct RecipeFilterGraphQL {
+    /// Recipes of any of these categories.
+    categories: Option<Vec<String>>,
+    /// Recipes of any of these cuisines.
+    cuisines: Option<Vec<String>>,
+    /// Recipes with a preparation plus cooking time of at most this many
+    /// minutes.
+    max_total_time_in_minute: Option<i32>,
+    has_image: Option<bool>,
+}
+
+/// The filter of the recipes queries, out of their flat arguments and of the
+/// `filter` input object.
+fn recipe_filter(
+    query: Option<String>,
+    tags: Option<Vec<String>>,
+    shared_with_me: Option<bool>,
+    filter: Option<RecipeFilterGraphQL>,
+) -> RecipeFilter {
+    let filter = filter.unwrap_or_default();
+    RecipeFilter {
+        query,
+        tags: tags.unwrap_or_default(),
+        shared_with_me: shared_with_me.unwrap_or(false),
+        categories: filter.categories.unwrap_or_default(),
+        cuisines: filter.cuisines.unwrap_or_default(),
+        max_total_time_in_minute: filter.max_total_time_in_minute,
+        has_image: filter.has_image,
+    }
+}
+
+#[derive(juniper::GraphQLEnum, Clone, Copy)]
+#[graphql(description = "What a user a recipe is shared with can do besides reading it")]
+enum ShareRoleGraphQL {
+    Viewer,
+    Editor,
+}
+
+impl ShareRoleGraphQL {
+    fn from(role: ShareRole) -> ShareRoleGraphQL {
+        match role {
+            ShareRole::Viewer => ShareRoleGraphQL::Viewer,
+            ShareRole::Editor => ShareRoleGraphQL::Editor,
+        }
+    }
+
+    fn into_role(self) -> ShareRole {
+        match self {
+            ShareRoleGraphQL::Viewer => ShareRole::Viewer,
+            ShareRoleGraphQL::Editor => ShareRole::Editor,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A user a recipe is shared with")]
+struct RecipeShareGraphQL {
+    email: String,
+    role: ShareRoleGraphQL,
+}
+
+impl RecipeShareGraphQL {
+    fn from(share: &RecipeShare) -> RecipeShareGraphQL {
+        RecipeShareGraphQL {
+            email: share.email.clone(),
+            role: ShareRoleGraphQL::from(share.role),
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "An ingredient line expressed in another unit system")]
+struct ConvertedIngredientGraphQL {
+    text: String,
+    quantity: Option<f64>,
+    max_quantity: Option<f64>,
+    unit: Option<String>,
+    name: String,
+    note: Option<String>,
+    converted: bool,
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "The ingredients and instructions of a recipe in another unit system")]
+struct RecipeConversionGraphQL {
+    unit_system: UnitSystemGraphQL,
+    ingredients: Vec<ConvertedIngredientGraphQL>,
+    instructions: Vec<String>,
+}
+
+impl RecipeConversionGraphQL {
+    fn from(conversion: RecipeConversion) -> RecipeConversionGraphQL {
+        RecipeConversionGraphQL {
+            unit_system: UnitSystemGraphQL::from(conversion.unit_system),
+            ingredients: conversion
+                .ingredients
+                .into_iter()
+                .map(|i| ConvertedIngredientGraphQL {
+                    text: i.text,
+                    quantity: i.ingredient.quantity,
+                    max_quantity: i.ingredient.max_quantity,
+                    unit: i.ingredient.unit,
+                    name: i.ingredient.name,
+                    note: i.ingredient.note,
+                    converted: i.converted,
+                })
+                .collect(),
+            instructions: conversion.instructions,
+        }
+    }
+}
+
 #[derive(juniper::GraphQLObject)]
 #[graphql(description = "A Recipe for a delicious dish")]
 struct RecipeGraphQL {
@@ -31,7 +341,17 @@ struct RecipeGraphQL {
     cook_time_in_minute: Option<i32>,
     prep_time_in_minute: Option<i32>,
     instructions: Vec<String>,
+    instruction_sections: Vec<Option<String>>,
     ingredients: Vec<String>,
+    ingredient_sections: Vec<Option<String>>,
+    structured_ingredients: Vec<IngredientGraphQL>,
+    tags: Vec<String>,
+    visibility: RecipeVisibilityGraphQL,
+    household_id: Option<String>,
+    /// Only set when `getRecipe` is asked for a number of servings.
+    scaling: Option<RecipeScalingGraphQL>,
+    /// Set when a unit system is requested or preferred by the logged user.
+    conversion: Option<RecipeConversionGraphQL>,
     imported_from: Option<String>,
 }
 
@@ -49,10 +369,278 @@ impl RecipeGraphQL {
             cook_time_in_minute: recipe.cook_time_in_minute,
             prep_time_in_minute: recipe.prep_time_in_minute,
             instructions: recipe.instructions.clone(),
+            instruction_sections: recipe.instruction_sections.clone(),
             ingredients: recipe.ingredients.clone(),
+            ingredient_sections: recipe.ingredient_sections.clone(),
+            structured_ingredients: recipe
+                .ingredients
+                .iter()
+                .zip(recipe.structured_ingredients.iter())
+                .map(|(text, ingredient)| IngredientGraphQL::from(text, ingredient))
+                .collect(),
+            tags: recipe.tags.clone(),
+            visibility: RecipeVisibilityGraphQL::from(recipe.visibility),
+            household_id: recipe.household_id.clone(),
+            scaling: None,
+            conversion: None,
             imported_from: recipe.imported_from.clone(),
         }
     }
+
+    fn with_conversion(mut self, recipe: &Recipe, unit_system: Option<UnitSystem>) -> Self {
+        self.conversion = unit_system
+            .map(|unit_system| RecipeConversionGraphQL::from(convert_recipe(recipe, unit_system)));
+        self
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A recipe in a page of recipes")]
+struct RecipeEdgeGraphQL {
+    /// Pass it as `after` to get the recipes following this one.
+    cursor: String,
+    node: RecipeGraphQL,
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "Where a page of recipes stands in the whole list")]
+struct PageInfoGraphQL {
+    has_next_page: bool,
+    has_previous_page: bool,
+    start_cursor: Option<String>,
+    end_cursor: Option<String>,
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A page of recipes")]
+struct RecipeConnectionGraphQL {
+    edges: Vec<RecipeEdgeGraphQL>,
+    page_info: PageInfoGraphQL,
+    /// Number of recipes in all the pages.
+    total_count: i32,
+}
+
+impl RecipeConnectionGraphQL {
+    fn from(page: RecipePage, unit_system: Option<UnitSystem>) -> RecipeConnectionGraphQL {
+        let cursors: Vec<String> = page.cursors.iter().map(encode_cursor).collect();
+        RecipeConnectionGraphQL {
+            page_info: PageInfoGraphQL {
+                has_next_page: page.has_next_page,
+                has_previous_page: page.has_previous_page,
+                start_cursor: cursors.first().cloned(),
+                end_cursor: cursors.last().cloned(),
+            },
+            total_count: page.total_count as i32,
+            edges: page
+                .recipes
+                .iter()
+                .zip(cursors)
+                .map(|(r, cursor)| RecipeEdgeGraphQL {
+                    cursor,
+                    node: RecipeGraphQL::from(r).with_conversion(r, unit_system),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A value of a recipe field and how many recipes have it")]
+struct FacetValueGraphQL {
+    value: String,
+    count: i32,
+}
+
+impl FacetValueGraphQL {
+    fn from(facet_value: FacetValue) -> FacetValueGraphQL {
+        FacetValueGraphQL {
+            value: facet_value.value,
+            count: facet_value.count as i32,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "Distinct categories and cuisines of recipes, the most frequent first")]
+struct RecipeFacetsGraphQL {
+    categories: Vec<FacetValueGraphQL>,
+    cuisines: Vec<FacetValueGraphQL>,
+}
+
+impl RecipeFacetsGraphQL {
+    fn from(facets: RecipeFacets) -> RecipeFacetsGraphQL {
+        RecipeFacetsGraphQL {
+            categories: facets
+                .categories
+                .into_iter()
+                .map(FacetValueGraphQL::from)
+                .collect(),
+            cuisines: facets
+                .cuisines
+                .into_iter()
+                .map(FacetValueGraphQL::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A recipe that can be cooked with the ingredients on hand")]
+struct CookableRecipeGraphQL {
+    recipe: RecipeGraphQL,
+    /// Share of the ingredients covered, between 0 and 1.
+    coverage: f64,
+    covered_ingredients: Vec<String>,
+    missing_ingredients: Vec<String>,
+}
+
+impl CookableRecipeGraphQL {
+    fn from(cookable: CookableRecipe) -> CookableRecipeGraphQL {
+        CookableRecipeGraphQL {
+            recipe: RecipeGraphQL::from(&cookable.recipe),
+            coverage: cookable.coverage(),
+            covered_ingredients: cookable.covered,
+            missing_ingredients: cookable.missing,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A recipe matching a full-text search")]
+struct RecipeSearchResultGraphQL {
+    recipe: RecipeGraphQL,
+    rank: f64,
+    /// HTML escaped excerpt of the recipe with the matching words wrapped in
+    /// `<mark>`.
+    snippet: String,
+}
+
+impl RecipeSearchResultGraphQL {
+    fn from(result: RecipeSearchResult) -> RecipeSearchResultGraphQL {
+        RecipeSearchResultGraphQL {
+            recipe: RecipeGraphQL::from(&result.recipe),
+            rank: f64::from(result.rank),
+            snippet: result.snippet,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A parsed Recipe that has not been saved yet")]
+struct RecipeDraftGraphQL {
+    title: String,
+    description: Option<String>,
+    image_url: Option<String>,
+    recipe_yield: Option<String>,
+    category: Option<String>,
+    cuisine: Option<String>,
+    cook_time_in_minute: Option<i32>,
+    prep_time_in_minute: Option<i32>,
+    instructions: Vec<String>,
+    instruction_sections: Vec<Option<String>>,
+    ingredients: Vec<String>,
+    ingredient_sections: Vec<Option<String>>,
+    tags: Vec<String>,
+    imported_from: Option<String>,
+}
+
+impl RecipeDraftGraphQL {
+    fn from(recipe: Recipe) -> RecipeDraftGraphQL {
+        RecipeDraftGraphQL {
+            title: recipe.title,
+            description: recipe.description,
+            image_url: recipe.image_url,
+            recipe_yield: recipe.recipe_yield,
+            category: recipe.category,
+            cuisine: recipe.cuisine,
+            cook_time_in_minute: recipe.cook_time_in_minute,
+            prep_time_in_minute: recipe.prep_time_in_minute,
+            instructions: recipe.instructions,
+            instruction_sections: recipe.instruction_sections,
+            ingredients: recipe.ingredients,
+            ingredient_sections: recipe.ingredient_sections,
+            tags: recipe.tags,
+            imported_from: recipe.imported_from,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A field whose value on the website differs from the recipe")]
+struct RecipeFieldChangeGraphQL {
+    field: String,
+    current: Option<String>,
+    imported: Option<String>,
+    locally_edited: bool,
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "The result of the comparison between a recipe and its website")]
+struct RecipeResyncGraphQL {
+    recipe: RecipeGraphQL,
+    changes: Vec<RecipeFieldChangeGraphQL>,
+}
+
+impl RecipeResyncGraphQL {
+    fn from(resync: RecipeResync) -> RecipeResyncGraphQL {
+        RecipeResyncGraphQL {
+            recipe: RecipeGraphQL::from(&resync.recipe),
+            changes: resync
+                .changes
+                .into_iter()
+                .map(|c| RecipeFieldChangeGraphQL {
+                    field: c.field,
+                    current: c.current,
+                    imported: c.imported,
+                    locally_edited: c.locally_edited,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A named group of recipes, in the order chosen by its owner")]
+struct CollectionGraphQL {
+    id: String,
+    user_id: String,
+    name: String,
+    description: Option<String>,
+    recipes: Vec<RecipeGraphQL>,
+}
+
+impl CollectionGraphQL {
+    fn from(collection: Collection, context: &Context) -> FieldResult<CollectionGraphQL> {
+        let recipes = (&context.recipe_interactor)
+            .get_recipes(&collection.recipe_ids, context.user_id.clone())
+            .map_err(coded_field_error)?;
+        Ok(CollectionGraphQL {
+            id: collection.id.to_hyphenated().to_string(),
+            user_id: collection.user_id,
+            name: collection.name,
+            description: collection.description,
+            recipes: recipes.iter().map(RecipeGraphQL::from).collect(),
+        })
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A group of users sharing a recipe library")]
+struct HouseholdGraphQL {
+    id: String,
+    name: String,
+    /// Emails of the members.
+    members: Vec<String>,
+}
+
+impl HouseholdGraphQL {
+    fn from(household: Household) -> HouseholdGraphQL {
+        HouseholdGraphQL {
+            id: household.id.to_hyphenated().to_string(),
+            name: household.name,
+            members: household.members.into_iter().map(|m| m.email).collect(),
+        }
+    }
 }
 
 #[derive(juniper::GraphQLInputObject)]
@@ -67,13 +655,260 @@ struct NewRecipeGraphQL {
     pub category: Option<String>,
     pub cuisine: Option<String>,
     pub instructions: Vec<String>,
+    pub instruction_sections: Option<Vec<Option<String>>>,
     pub ingredients: Vec<String>,
+    pub ingredient_sections: Option<Vec<Option<String>>>,
+    /// Only used on creation, use `addTag` and `removeTag` afterwards.
+    pub tags: Option<Vec<String>>,
     pub imported_from: Option<String>,
 }
 
+#[derive(juniper::GraphQLEnum, Clone, Copy)]
+#[graphql(description = "When in the day a meal is planned")]
+enum MealSlotGraphQL {
+    Breakfast,
+    Lunch,
+    Snack,
+    Dinner,
+}
+
+impl MealSlotGraphQL {
+    fn from(slot: MealSlot) -> MealSlotGraphQL {
+        match slot {
+            MealSlot::Breakfast => MealSlotGraphQL::Breakfast,
+            MealSlot::Lunch => MealSlotGraphQL::Lunch,
+            MealSlot::Snack => MealSlotGraphQL::Snack,
+            MealSlot::Dinner => MealSlotGraphQL::Dinner,
+        }
+    }
+
+    fn into_slot(self) -> MealSlot {
+        match self {
+            MealSlotGraphQL::Breakfast => MealSlot::Breakfast,
+            MealSlotGraphQL::Lunch => MealSlot::Lunch,
+            MealSlotGraphQL::Snack => MealSlot::Snack,
+            MealSlotGraphQL::Dinner => MealSlot::Dinner,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A recipe planned for a meal")]
+struct MealPlanEntryGraphQL {
+    id: String,
+    date: NaiveDate,
+    slot: MealSlotGraphQL,
+    recipe_id: String,
+    /// None when the recipe is no longer readable by the user.
+    recipe: Option<RecipeGraphQL>,
+    servings: Option<i32>,
+}
+
+impl MealPlanEntryGraphQL {
+    fn from(entry: MealPlanEntry, recipe: Option<&Recipe>) -> MealPlanEntryGraphQL {
+        MealPlanEntryGraphQL {
+            id: entry.id.to_hyphenated().to_string(),
+            date: entry.date,
+            slot: MealSlotGraphQL::from(entry.slot),
+            recipe_id: entry.recipe_id,
+            recipe: recipe.map(RecipeGraphQL::from),
+            servings: entry.servings,
+        }
+    }
+
+    fn load(entry: MealPlanEntry, context: &Context) -> FieldResult<MealPlanEntryGraphQL> {
+        let recipes = (&context.recipe_interactor)
+            .get_recipes(slice::from_ref(&entry.recipe_id), context.user_id.clone())
+            .map_err(coded_field_error)?;
+        Ok(MealPlanEntryGraphQL::from(entry, recipes.first()))
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "The meals planned between two dates, both included")]
+struct MealPlanGraphQL {
+    start: NaiveDate,
+    end: NaiveDate,
+    /// Entries by date then by slot.
+    entries: Vec<MealPlanEntryGraphQL>,
+}
+
+impl MealPlanGraphQL {
+    fn from(meal_plan: MealPlan, context: &Context) -> FieldResult<MealPlanGraphQL> {
+        let mut recipe_ids: Vec<String> = meal_plan
+            .entries
+            .iter()
+            .map(|e| e.recipe_id.clone())
+            .collect();
+        recipe_ids.sort();
+        recipe_ids.dedup();
+        let recipes = (&context.recipe_interactor)
+            .get_recipes(&recipe_ids, context.user_id.clone())
+            .map_err(coded_field_error)?;
+        Ok(MealPlanGraphQL {
+            start: meal_plan.start,
+            end: meal_plan.end,
+            entries: meal_plan
+                .entries
+                .into_iter()
+                .map(|e| {
+                    let recipe = recipes
+                        .iter()
+                        .find(|r| r.id.to_hyphenated().to_string() == e.recipe_id);
+                    MealPlanEntryGraphQL::from(e, recipe)
+                })
+                .collect(),
+        })
+    }
+}
+
+#[derive(juniper::GraphQLEnum, Clone, Copy)]
+#[graphql(description = "Where an item is found in a shop")]
+enum AisleGraphQL {
+    Produce,
+    Bakery,
+    Meat,
+    Fish,
+    Dairy,
+    Grocery,
+    Spices,
+    Frozen,
+    Drinks,
+    Other,
+}
+
+impl AisleGraphQL {
+    fn from(aisle: Aisle) -> AisleGraphQL {
+        match aisle {
+            Aisle::Produce => AisleGraphQL::Produce,
+            Aisle::Bakery => AisleGraphQL::Bakery,
+            Aisle::Meat => AisleGraphQL::Meat,
+            Aisle::Fish => AisleGraphQL::Fish,
+            Aisle::Dairy => AisleGraphQL::Dairy,
+            Aisle::Grocery => AisleGraphQL::Grocery,
+            Aisle::Spices => AisleGraphQL::Spices,
+            Aisle::Frozen => AisleGraphQL::Frozen,
+            Aisle::Drinks => AisleGraphQL::Drinks,
+            Aisle::Other => AisleGraphQL::Other,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLInputObject)]
+#[graphql(description = "A recipe to shop for")]
+struct RecipeServingsGraphQL {
+    recipe_id: String,
+    /// The servings to cook, the yield of the recipe when missing.
+    servings: Option<i32>,
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "An ingredient to buy")]
+struct ShoppingListItemGraphQL {
+    id: String,
+    /// What to buy, or the ingredient line as written when it has no quantity.
+    text: String,
+    name: String,
+    quantity: Option<f64>,
+    unit: Option<String>,
+    aisle: AisleGraphQL,
+    checked: bool,
+}
+
+impl ShoppingListItemGraphQL {
+    fn from(item: &ShoppingListItem) -> ShoppingListItemGraphQL {
+        ShoppingListItemGraphQL {
+            id: item.id.to_hyphenated().to_string(),
+            text: item.text.clone(),
+            name: item.name.clone(),
+            quantity: item.quantity,
+            unit: item.unit.clone(),
+            aisle: AisleGraphQL::from(item.aisle),
+            checked: item.checked,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "The items of a shopping list found in an aisle")]
+struct ShoppingListAisleGraphQL {
+    aisle: AisleGraphQL,
+    items: Vec<ShoppingListItemGraphQL>,
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "The ingredients to buy for a set of recipes")]
+struct ShoppingListGraphQL {
+    id: String,
+    name: String,
+    /// Items by aisle, in the order of a walk through the shop.
+    items: Vec<ShoppingListItemGraphQL>,
+    aisles: Vec<ShoppingListAisleGraphQL>,
+}
+
+impl ShoppingListGraphQL {
+    fn from(shopping_list: ShoppingList) -> ShoppingListGraphQL {
+        let aisles = shopping_list
+            .get_aisles()
+            .into_iter()
+            .map(|(aisle, items)| ShoppingListAisleGraphQL {
+                aisle: AisleGraphQL::from(aisle),
+                items: items
+                    .into_iter()
+                    .map(ShoppingListItemGraphQL::from)
+                    .collect(),
+            })
+            .collect();
+        ShoppingListGraphQL {
+            id: shopping_list.id.to_hyphenated().to_string(),
+            name: shopping_list.name,
+            items: shopping_list
+                .items
+                .iter()
+                .map(ShoppingListItemGraphQL::from)
+                .collect(),
+            aisles,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "An ingredient the user has")]
+struct PantryItemGraphQL {
+    id: String,
+    name: String,
+    /// None when the amount is not tracked, e.g. for salt.
+    quantity: Option<f64>,
+    unit: Option<String>,
+    expires_on: Option<NaiveDate>,
+}
+
+impl PantryItemGraphQL {
+    fn from(item: &PantryItem) -> PantryItemGraphQL {
+        PantryItemGraphQL {
+            id: item.id.to_hyphenated().to_string(),
+            name: item.name.clone(),
+            quantity: item.quantity,
+            unit: item.unit.clone(),
+            expires_on: item.expires_on,
+        }
+    }
+}
+
+#[derive(juniper::GraphQLObject)]
+#[graphql(description = "A pantry item to use soon and the recipes using it")]
+struct ExpiringPantryItemGraphQL {
+    item: PantryItemGraphQL,
+    recipes: Vec<RecipeGraphQL>,
+}
+
 pub struct Context {
     recipe_interactor: RecipeInteractor,
     user_interactor: UserInteractor,
+    collection_interactor: CollectionInteractor,
+    meal_plan_interactor: MealPlanInteractor,
+    shopping_list_interactor: ShoppingListInteractor,
+    pantry_interactor: PantryInteractor,
     user_id: Option<String>,
 }
 
@@ -83,14 +918,11 @@ impl<'a, 'r> FromRequest<'a, 'r> for Context {
     fn from_request(request: &'a Request<'r>) -> request::Outcome<Context, Self::Error> {
         let user: LoggedUser = request.guard::<LoggedUser>()?;
         let db_con: DbCon = request.guard::<DbCon>()?;
-        // TODO: fix this, should be borrowed but lost against compiler lifetime.
-        let db_con2: DbCon = request.guard::<DbCon>()?;
-        let db_con3: DbCon = request.guard::<DbCon>()?;
+        let parser = request.guard::<State<SharedParser>>()?;
 
         Outcome::Success(Context::new(
             db_con.0,
-            db_con2.0,
-            db_con3.0,
+            parser.inner().clone(),
             user.0.map(|user_id| user_id.to_hyphenated().to_string()),
         ))
     }
@@ -98,20 +930,41 @@ impl<'a, 'r> FromRequest<'a, 'r> for Context {
 
 impl Context {
     pub fn new(
-        connection1: PooledConnection<ConnectionManager<PgConnection>>,
-        connection2: PooledConnection<ConnectionManager<PgConnection>>,
-        connection3: PooledConnection<ConnectionManager<PgConnection>>,
+        connection: PooledConnection<ConnectionManager<PgConnection>>,
+        parser: SharedParser,
         user_id: Option<String>,
     ) -> Context {
+        let connection = Rc::new(connection);
         Context {
             recipe_interactor: RecipeInteractor {
-                recipe_dao: Box::new(DieselRecipeDao::new(connection1)),
-                user_dao: Box::new(DieselUserDao::new(connection2)),
-                parser: Box::new(SelectParser::new()),
+                recipe_dao: Box::new(DieselRecipeDao::new(connection.clone())),
+                user_dao: Box::new(DieselUserDao::new(connection.clone())),
+                parser,
                 image_store: Box::new(S3ImageStore::default()),
             },
             user_interactor: UserInteractor {
-                dao: Box::new(DieselUserDao::new(connection3)),
+                dao: Box::new(DieselUserDao::new(connection.clone())),
+                household_dao: Box::new(DieselHouseholdDao::new(connection.clone())),
+            },
+            collection_interactor: CollectionInteractor {
+                collection_dao: Box::new(DieselCollectionDao::new(connection.clone())),
+                recipe_dao: Box::new(DieselRecipeDao::new(connection.clone())),
+                user_dao: Box::new(DieselUserDao::new(connection.clone())),
+            },
+            meal_plan_interactor: MealPlanInteractor {
+                meal_plan_dao: Box::new(DieselMealPlanDao::new(connection.clone())),
+                recipe_dao: Box::new(DieselRecipeDao::new(connection.clone())),
+                user_dao: Box::new(DieselUserDao::new(connection.clone())),
+            },
+            shopping_list_interactor: ShoppingListInteractor {
+                shopping_list_dao: Box::new(DieselShoppingListDao::new(connection.clone())),
+                meal_plan_dao: Box::new(DieselMealPlanDao::new(connection.clone())),
+                pantry_dao: Box::new(DieselPantryDao::new(connection.clone())),
+                recipe_dao: Box::new(DieselRecipeDao::new(connection.clone())),
+                user_dao: Box::new(DieselUserDao::new(connection.clone())),
+            },
+            pantry_interactor: PantryInteractor {
+                pantry_dao: Box::new(DieselPantryDao::new(connection)),
             },
             user_id,
         }
@@ -123,10 +976,82 @@ impl Context {
         }
         Ok(self.user_id.clone().unwrap())
     }
+
+    /// The pantry items not expired yet when subtracting the pantry, else none.
+    fn get_pantry_stock(
+        &self,
+        user_id: &str,
+        subtract_pantry: Option<bool>,
+    ) -> FieldResult<Vec<PantryItem>> {
+        if !subtract_pantry.unwrap_or(false) {
+            return Ok(vec![]);
+        }
+        let today = Utc::now().naive_utc().date();
+        (&self.pantry_interactor)
+            .get_my_stock(String::from(user_id), today)
+            .map_err(coded_field_error)
+    }
+
+    /// The requested unit system, or else the one preferred by the logged user.
+    fn get_unit_system(
+        &self,
+        requested: Option<UnitSystemGraphQL>,
+    ) -> FieldResult<Option<UnitSystem>> {
+        if let Some(requested) = requested {
+            return Ok(Some(requested.into_unit_system()));
+        }
+        match &self.user_id {
+            Some(user_id) => (&self.user_interactor)
+                .get_unit_system(user_id)
+                .map_err(coded_field_error),
+            None => Ok(None),
+        }
+    }
 }
 
 impl<'a> juniper::Context for Context {}
 
+/// Exposes the `RecipeError`, `CollectionError`, `MealPlanError`,
+/// `ShoppingListError`, `PantryError` and `UserError` codes in the
+/// GraphQL error extensions so that clients can react to a failure without
+/// parsing the message.
+fn coded_field_error(error: Box<dyn Error>) -> FieldError {
+    if let Some(recipe_error) = error.downcast_ref::<RecipeError>() {
+        return FieldError::new(
+            recipe_error,
+            graphql_value!({ "code": (recipe_error.code()) }),
+        );
+    }
+    if let Some(collection_error) = error.downcast_ref::<CollectionError>() {
+        return FieldError::new(
+            collection_error,
+            graphql_value!({ "code": (collection_error.code()) }),
+        );
+    }
+    if let Some(meal_plan_error) = error.downcast_ref::<MealPlanError>() {
+        return FieldError::new(
+            meal_plan_error,
+            graphql_value!({ "code": (meal_plan_error.code()) }),
+        );
+    }
+    if let Some(shopping_list_error) = error.downcast_ref::<ShoppingListError>() {
+        return FieldError::new(
+            shopping_list_error,
+            graphql_value!({ "code": (shopping_list_error.code()) }),
+        );
+    }
+    if let Some(pantry_error) = error.downcast_ref::<PantryError>() {
+        return FieldError::new(
+            pantry_error,
+            graphql_value!({ "code": (pantry_error.code()) }),
+        );
+    }
+    if let Some(user_error) = error.downcast_ref::<UserError>() {
+        return FieldError::new(user_error, graphql_value!({ "code": (user_error.code()) }));
+    }
+    FieldError::from(error)
+}
+
 pub struct Query;
 
 #[juniper::object(
@@ -139,15 +1064,272 @@ impl Query {
     pub fn get_my_recipes(
         context: &Context,
         query: Option<String>,
+        tags: Option<Vec<String>>,
+        unit_system: Option<UnitSystemGraphQL>,
+        shared_with_me: Option<bool>,
+        sort: Option<RecipeSortGraphQL>,
+        filter: Option<RecipeFilterGraphQL>,
     ) -> FieldResult<Vec<RecipeGraphQL>> {
         let user_id = context.get_user()?;
-        let recipes = (&context.recipe_interactor).get_my_recipes(user_id, query)?;
-        Ok(recipes.iter().map(|r| RecipeGraphQL::from(r)).collect())
+        let unit_system = context.get_unit_system(unit_system)?;
+        let recipes = (&context.recipe_interactor)
+            .get_my_recipes(
+                user_id,
+                recipe_filter(query, tags, shared_with_me, filter),
+                sort.map(RecipeSortGraphQL::into_sort).unwrap_or_default(),
+            )
+            .map_err(coded_field_error)?;
+        Ok(recipes
+            .iter()
+            .map(|r| RecipeGraphQL::from(r).with_conversion(r, unit_system))
+            .collect())
     }
 
-    pub fn get_recipe(context: &Context, id: String) -> FieldResult<RecipeGraphQL> {
-        let recipe = (&context.recipe_interactor).get_recipe(id)?;
-        Ok(RecipeGraphQL::from(&recipe))
+    /// The same recipes as `getMyRecipes`, `first` at a time (20 by default,
+    /// 100 at most) following the `after` cursor.
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_my_recipes_connection(
+        context: &Context,
+        first: Option<i32>,
+        after: Option<String>,
+        query: Option<String>,
+        tags: Option<Vec<String>>,
+        unit_system: Option<UnitSystemGraphQL>,
+        shared_with_me: Option<bool>,
+        sort: Option<RecipeSortGraphQL>,
+        filter: Option<RecipeFilterGraphQL>,
+    ) -> FieldResult<RecipeConnectionGraphQL> {
+        let user_id = context.get_user()?;
+        let unit_system = context.get_unit_system(unit_system)?;
+        let page = (&context.recipe_interactor)
+            .get_my_recipes_page(
+                user_id,
+                recipe_filter(query, tags, shared_with_me, filter),
+                sort.map(RecipeSortGraphQL::into_sort).unwrap_or_default(),
+                first.map(|f| f.max(0) as usize),
+                after,
+            )
+            .map_err(coded_field_error)?;
+        Ok(RecipeConnectionGraphQL::from(page, unit_system))
+    }
+
+    /// Categories and cuisines to filter `getMyRecipes` with, counting the
+    /// recipes matching the other arguments.
+    pub fn get_my_recipe_facets(
+        context: &Context,
+        query: Option<String>,
+        tags: Option<Vec<String>>,
+        shared_with_me: Option<bool>,
+        filter: Option<RecipeFilterGraphQL>,
+    ) -> FieldResult<RecipeFacetsGraphQL> {
+        let user_id = context.get_user()?;
+        let facets = (&context.recipe_interactor)
+            .get_my_recipe_facets(user_id, recipe_filter(query, tags, shared_with_me, filter))
+            .map_err(coded_field_error)?;
+        Ok(RecipeFacetsGraphQL::from(facets))
+    }
+
+    /// What can I cook with the ingredients on hand, e.g. ["eggs, flour, milk"],
+    /// and with the pantry items not expired yet when `fromPantry` is true?
+    pub fn get_cookable_recipes(
+        context: &Context,
+        ingredients: Option<Vec<String>>,
+        from_pantry: Option<bool>,
+    ) -> FieldResult<Vec<CookableRecipeGraphQL>> {
+        let user_id = context.get_user()?;
+        let mut ingredients = ingredients.unwrap_or_default();
+        ingredients.extend(
+            context
+                .get_pantry_stock(&user_id, from_pantry)?
+                .into_iter()
+                .map(|item| item.name),
+        );
+        let recipes = (&context.recipe_interactor)
+            .get_cookable_recipes(user_id, ingredients)
+            .map_err(coded_field_error)?;
+        Ok(recipes
+            .into_iter()
+            .map(CookableRecipeGraphQL::from)
+            .collect())
+    }
+
+    /// Searches the titles, descriptions, ingredients and instructions of the
+    /// recipes of the user and of their household, in French and in English.
+    pub fn search_recipes(
+        context: &Context,
+        query: String,
+        limit: Option<i32>,
+    ) -> FieldResult<Vec<RecipeSearchResultGraphQL>> {
+        let user_id = context.get_user()?;
+        let results = (&context.recipe_interactor)
+            .search_recipes(user_id, query, limit.map(|l| l.max(0) as usize))
+            .map_err(coded_field_error)?;
+        Ok(results
+            .into_iter()
+            .map(RecipeSearchResultGraphQL::from)
+            .collect())
+    }
+
+    pub fn get_recipe(
+        context: &Context,
+        id: String,
+        servings: Option<i32>,
+        unit_system: Option<UnitSystemGraphQL>,
+    ) -> FieldResult<RecipeGraphQL> {
+        let unit_system = context.get_unit_system(unit_system)?;
+        let recipe = (&context.recipe_interactor)
+            .get_recipe(id, context.user_id.clone())
+            .map_err(coded_field_error)?;
+        let mut recipe_graphql = RecipeGraphQL::from(&recipe).with_conversion(&recipe, unit_system);
+        if let Some(servings) = servings {
+            let scaling =
+                scale_recipe(&recipe, servings).map_err(|e| coded_field_error(Box::new(e)))?;
+            recipe_graphql.scaling = Some(RecipeScalingGraphQL::from(scaling));
+        }
+        Ok(recipe_graphql)
+    }
+
+    pub fn get_recipe_shares(
+        context: &Context,
+        id: String,
+    ) -> FieldResult<Vec<RecipeShareGraphQL>> {
+        let user_id = context.get_user()?;
+        let shares = (&context.recipe_interactor)
+            .get_shares(id, user_id)
+            .map_err(coded_field_error)?;
+        Ok(shares.iter().map(RecipeShareGraphQL::from).collect())
+    }
+
+    pub fn get_my_collections(context: &Context) -> FieldResult<Vec<CollectionGraphQL>> {
+        let user_id = context.get_user()?;
+        let collections = (&context.collection_interactor)
+            .get_my_collections(user_id)
+            .map_err(coded_field_error)?;
+        collections
+            .into_iter()
+            .map(|c| CollectionGraphQL::from(c, context))
+            .collect()
+    }
+
+    pub fn get_collection(context: &Context, id: String) -> FieldResult<CollectionGraphQL> {
+        let user_id = context.get_user()?;
+        let collection = (&context.collection_interactor)
+            .get_collection(id, user_id)
+            .map_err(coded_field_error)?;
+        CollectionGraphQL::from(collection, context)
+    }
+
+    /// The meals planned between the two dates, both included, at most 62
+    /// days apart.
+    pub fn get_my_meal_plan(
+        context: &Context,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> FieldResult<MealPlanGraphQL> {
+        let user_id = context.get_user()?;
+        let meal_plan = (&context.meal_plan_interactor)
+            .get_my_meal_plan(user_id, start, end)
+            .map_err(coded_field_error)?;
+        MealPlanGraphQL::from(meal_plan, context)
+    }
+
+    pub fn get_my_shopping_lists(context: &Context) -> FieldResult<Vec<ShoppingListGraphQL>> {
+        let user_id = context.get_user()?;
+        let shopping_lists = (&context.shopping_list_interactor)
+            .get_my_shopping_lists(user_id)
+            .map_err(coded_field_error)?;
+        Ok(shopping_lists
+            .into_iter()
+            .map(ShoppingListGraphQL::from)
+            .collect())
+    }
+
+    pub fn get_shopping_list(context: &Context, id: String) -> FieldResult<ShoppingListGraphQL> {
+        let user_id = context.get_user()?;
+        let shopping_list = (&context.shopping_list_interactor)
+            .get_shopping_list(id, user_id)
+            .map_err(coded_field_error)?;
+        Ok(ShoppingListGraphQL::from(shopping_list))
+    }
+
+    /// The ingredients the user has, the ones expiring first first.
+    pub fn get_my_pantry(context: &Context) -> FieldResult<Vec<PantryItemGraphQL>> {
+        let user_id = context.get_user()?;
+        let items = (&context.pantry_interactor)
+            .get_my_pantry(user_id)
+            .map_err(coded_field_error)?;
+        Ok(items.iter().map(PantryItemGraphQL::from).collect())
+    }
+
+    /// The pantry items expiring within the days, or already expired, with the
+    /// recipes of the user and of their household using them.
+    pub fn get_expiring_pantry_items(
+        context: &Context,
+        days: i32,
+    ) -> FieldResult<Vec<ExpiringPantryItemGraphQL>> {
+        let user_id = context.get_user()?;
+        let today = Utc::now().naive_utc().date();
+        let items = (&context.pantry_interactor)
+            .get_expiring_items(user_id.clone(), today, i64::from(days))
+            .map_err(coded_field_error)?;
+        let recipes = (&context.recipe_interactor)
+            .get_my_recipes(user_id, RecipeFilter::default(), RecipeSort::default())
+            .map_err(coded_field_error)?;
+        let ingredients: Vec<Vec<Vec<String>>> = recipes.iter().map(get_ingredient_words).collect();
+        Ok(items
+            .iter()
+            .map(|item| ExpiringPantryItemGraphQL {
+                item: PantryItemGraphQL::from(item),
+                recipes: recipes
+                    .iter()
+                    .zip(ingredients.iter())
+                    .filter(|(_, ingredients)| uses_ingredient(ingredients, &item.name))
+                    .map(|(recipe, _)| RecipeGraphQL::from(recipe))
+                    .collect(),
+            })
+            .collect())
+    }
+
+    pub fn get_my_household(context: &Context) -> FieldResult<Option<HouseholdGraphQL>> {
+        let user_id = context.get_user()?;
+        let household = (&context.user_interactor)
+            .get_my_household(&user_id)
+            .map_err(coded_field_error)?;
+        Ok(household.map(HouseholdGraphQL::from))
+    }
+
+    pub fn get_my_household_invitations(context: &Context) -> FieldResult<Vec<HouseholdGraphQL>> {
+        let user_id = context.get_user()?;
+        let households = (&context.user_interactor)
+            .get_my_invitations(&user_id)
+            .map_err(coded_field_error)?;
+        Ok(households.into_iter().map(HouseholdGraphQL::from).collect())
+    }
+
+    pub fn get_my_unit_system(context: &Context) -> FieldResult<Option<UnitSystemGraphQL>> {
+        let user_id = context.get_user()?;
+        let unit_system = (&context.user_interactor)
+            .get_unit_system(&user_id)
+            .map_err(coded_field_error)?;
+        Ok(unit_system.map(UnitSystemGraphQL::from))
+    }
+
+    /// The secret token of the meal plan feed, served at
+    /// `/calendar/<token>.ics`, or None when the feed is disabled.
+    pub fn get_my_calendar_token(context: &Context) -> FieldResult<Option<String>> {
+        let user_id = context.get_user()?;
+        let calendar_token = (&context.user_interactor)
+            .get_calendar_token(&user_id)
+            .map_err(coded_field_error)?;
+        Ok(calendar_token)
+    }
+
+    pub fn preview_import(context: &Context, url: String) -> FieldResult<RecipeDraftGraphQL> {
+        let user_id = context.get_user()?;
+        let recipe = (&context.recipe_interactor)
+            .preview_import(url, user_id)
+            .map_err(coded_field_error)?;
+        Ok(RecipeDraftGraphQL::from(recipe))
     }
 }
 
@@ -159,21 +1341,29 @@ pub struct Mutation;
 impl Mutation {
     fn createRecipe(context: &Context, new_recipe: NewRecipeGraphQL) -> FieldResult<RecipeGraphQL> {
         let user_id = context.get_user()?;
-        let recipe = (&context.recipe_interactor).add_recipe(Recipe {
-            id: Uuid::new_v4(),
-            user_id,
-            title: new_recipe.title,
-            description: new_recipe.description,
-            recipe_yield: new_recipe.recipe_yield,
-            category: new_recipe.category,
-            cuisine: new_recipe.cuisine,
-            prep_time_in_minute: new_recipe.prep_time_in_minute,
-            cook_time_in_minute: new_recipe.cook_time_in_minute,
-            instructions: new_recipe.instructions,
-            ingredients: new_recipe.ingredients,
-            imported_from: new_recipe.imported_from,
-            image_url: new_recipe.image_url,
-        })?;
+        let recipe = (&context.recipe_interactor)
+            .add_recipe(Recipe {
+                id: Uuid::new_v4(),
+                user_id,
+                title: new_recipe.title,
+                description: new_recipe.description,
+                recipe_yield: new_recipe.recipe_yield,
+                category: new_recipe.category,
+                cuisine: new_recipe.cuisine,
+                prep_time_in_minute: new_recipe.prep_time_in_minute,
+                cook_time_in_minute: new_recipe.cook_time_in_minute,
+                instructions: new_recipe.instructions,
+                instruction_sections: new_recipe.instruction_sections.unwrap_or_default(),
+                structured_ingredients: StructuredIngredient::parse_all(&new_recipe.ingredients),
+                ingredients: new_recipe.ingredients,
+                ingredient_sections: new_recipe.ingredient_sections.unwrap_or_default(),
+                tags: new_recipe.tags.unwrap_or_default(),
+                visibility: RecipeVisibility::Private,
+                household_id: None,
+                imported_from: new_recipe.imported_from,
+                image_url: new_recipe.image_url,
+            })
+            .map_err(coded_field_error)?;
 
         Ok(RecipeGraphQL::from(&recipe))
     }
@@ -184,43 +1374,370 @@ impl Mutation {
         new_recipe: NewRecipeGraphQL,
     ) -> FieldResult<RecipeGraphQL> {
         let user_id = context.get_user()?;
-        let recipe = (&context.recipe_interactor).update_recipe(Recipe {
-            id: Uuid::parse_str(id.as_str()).expect("Cannot parse UUID"),
-            user_id,
-            title: new_recipe.title,
-            description: new_recipe.description,
-            recipe_yield: new_recipe.recipe_yield,
-            category: new_recipe.category,
-            cuisine: new_recipe.cuisine,
-            prep_time_in_minute: new_recipe.prep_time_in_minute,
-            cook_time_in_minute: new_recipe.cook_time_in_minute,
-            instructions: new_recipe.instructions,
-            ingredients: new_recipe.ingredients,
-            imported_from: new_recipe.imported_from,
-            image_url: new_recipe.image_url,
-        })?;
+        let id = Uuid::parse_str(id.as_str())
+            .map_err(|_| coded_field_error(Box::new(RecipeError::RecipeNotFound)))?;
+        let recipe = (&context.recipe_interactor)
+            .update_recipe(
+                Recipe {
+                    id,
+                    user_id,
+                    title: new_recipe.title,
+                    description: new_recipe.description,
+                    recipe_yield: new_recipe.recipe_yield,
+                    category: new_recipe.category,
+                    cuisine: new_recipe.cuisine,
+                    prep_time_in_minute: new_recipe.prep_time_in_minute,
+                    cook_time_in_minute: new_recipe.cook_time_in_minute,
+                    instructions: new_recipe.instructions,
+                    instruction_sections: vec![],
+                    structured_ingredients: StructuredIngredient::parse_all(
+                        &new_recipe.ingredients,
+                    ),
+                    ingredients: new_recipe.ingredients,
+                    ingredient_sections: vec![],
+                    tags: new_recipe.tags.unwrap_or_default(),
+                    visibility: RecipeVisibility::Private,
+                    household_id: None,
+                    imported_from: new_recipe.imported_from,
+                    image_url: new_recipe.image_url,
+                },
+                new_recipe.instruction_sections,
+                new_recipe.ingredient_sections,
+            )
+            .map_err(coded_field_error)?;
 
         Ok(RecipeGraphQL::from(&recipe))
     }
 
     fn deleteRecipe(context: &Context, id: String) -> FieldResult<String> {
         let user_id = context.get_user()?;
-        (&context.recipe_interactor).delete_recipe(id.clone(), user_id)?;
+        (&context.recipe_interactor)
+            .delete_recipe(id.clone(), user_id)
+            .map_err(coded_field_error)?;
         Ok(id)
     }
 
     fn importRecipe(context: &Context, url: String) -> FieldResult<RecipeGraphQL> {
         let user_id = context.get_user()?;
-        let recipe = (&context.recipe_interactor).import_from(url, user_id)?;
+        let recipe = (&context.recipe_interactor)
+            .import_from(url, user_id)
+            .map_err(coded_field_error)?;
+        Ok(RecipeGraphQL::from(&recipe))
+    }
+
+    fn importRecipeFromHtml(
+        context: &Context,
+        html: String,
+        source_url: Option<String>,
+    ) -> FieldResult<RecipeGraphQL> {
+        let user_id = context.get_user()?;
+        let recipe = (&context.recipe_interactor)
+            .import_from_html(html, source_url, user_id)
+            .map_err(coded_field_error)?;
         Ok(RecipeGraphQL::from(&recipe))
     }
 
+    fn resyncRecipe(
+        context: &Context,
+        id: String,
+        apply: Option<bool>,
+    ) -> FieldResult<RecipeResyncGraphQL> {
+        let user_id = context.get_user()?;
+        let resync = (&context.recipe_interactor)
+            .resync_recipe(id, user_id, apply.unwrap_or(false))
+            .map_err(coded_field_error)?;
+        Ok(RecipeResyncGraphQL::from(resync))
+    }
+
     fn copyRecipe(context: &Context, recipe_id: String) -> FieldResult<RecipeGraphQL> {
         let user_id = context.get_user()?;
-        let recipe = (&context.recipe_interactor).copy_recipe(user_id, recipe_id)?;
+        let recipe = (&context.recipe_interactor)
+            .copy_recipe(user_id, recipe_id)
+            .map_err(coded_field_error)?;
+        Ok(RecipeGraphQL::from(&recipe))
+    }
+
+    fn setRecipeVisibility(
+        context: &Context,
+        id: String,
+        visibility: RecipeVisibilityGraphQL,
+    ) -> FieldResult<RecipeGraphQL> {
+        let user_id = context.get_user()?;
+        let recipe = (&context.recipe_interactor)
+            .set_visibility(id, user_id, visibility.into_visibility())
+            .map_err(coded_field_error)?;
         Ok(RecipeGraphQL::from(&recipe))
     }
 
+    /// Moves the recipe to the library of the household of the user, or back
+    /// to their own library.
+    fn setRecipeHousehold(
+        context: &Context,
+        id: String,
+        in_household: bool,
+    ) -> FieldResult<RecipeGraphQL> {
+        let user_id = context.get_user()?;
+        let recipe = (&context.recipe_interactor)
+            .set_household(id, user_id, in_household)
+            .map_err(coded_field_error)?;
+        Ok(RecipeGraphQL::from(&recipe))
+    }
+
+    fn shareRecipe(
+        context: &Context,
+        id: String,
+        email: String,
+        role: ShareRoleGraphQL,
+    ) -> FieldResult<Vec<RecipeShareGraphQL>> {
+        let user_id = context.get_user()?;
+        let shares = (&context.recipe_interactor)
+            .share_recipe(id, user_id, email, role.into_role())
+            .map_err(coded_field_error)?;
+        Ok(shares.iter().map(RecipeShareGraphQL::from).collect())
+    }
+
+    fn unshareRecipe(
+        context: &Context,
+        id: String,
+        email: String,
+    ) -> FieldResult<Vec<RecipeShareGraphQL>> {
+        let user_id = context.get_user()?;
+        let shares = (&context.recipe_interactor)
+            .unshare_recipe(id, user_id, email)
+            .map_err(coded_field_error)?;
+        Ok(shares.iter().map(RecipeShareGraphQL::from).collect())
+    }
+
+    fn addTag(context: &Context, id: String, tag: String) -> FieldResult<RecipeGraphQL> {
+        let user_id = context.get_user()?;
+        let recipe = (&context.recipe_interactor)
+            .add_tag(id, user_id, tag)
+            .map_err(coded_field_error)?;
+        Ok(RecipeGraphQL::from(&recipe))
+    }
+
+    fn removeTag(context: &Context, id: String, tag: String) -> FieldResult<RecipeGraphQL> {
+        let user_id = context.get_user()?;
+        let recipe = (&context.recipe_interactor)
+            .remove_tag(id, user_id, tag)
+            .map_err(coded_field_error)?;
+        Ok(RecipeGraphQL::from(&recipe))
+    }
+
+    fn createCollection(
+        context: &Context,
+        name: String,
+        description: Option<String>,
+    ) -> FieldResult<CollectionGraphQL> {
+        let user_id = context.get_user()?;
+        let collection = (&context.collection_interactor)
+            .add_collection(user_id, name, description)
+            .map_err(coded_field_error)?;
+        CollectionGraphQL::from(collection, context)
+    }
+
+    fn updateCollection(
+        context: &Context,
+        id: String,
+        name: String,
+        description: Option<String>,
+    ) -> FieldResult<CollectionGraphQL> {
+        let user_id = context.get_user()?;
+        let collection = (&context.collection_interactor)
+            .update_collection(id, user_id, name, description)
+            .map_err(coded_field_error)?;
+        CollectionGraphQL::from(collection, context)
+    }
+
+    fn deleteCollection(context: &Context, id: String) -> FieldResult<String> {
+        let user_id = context.get_user()?;
+        (&context.collection_interactor)
+            .delete_collection(id.clone(), user_id)
+            .map_err(coded_field_error)?;
+        Ok(id)
+    }
+
+    /// Adds the recipe at the 0-based position, or at the end. A recipe
+    /// already in the collection is moved.
+    fn addRecipeToCollection(
+        context: &Context,
+        id: String,
+        recipe_id: String,
+        position: Option<i32>,
+    ) -> FieldResult<CollectionGraphQL> {
+        let user_id = context.get_user()?;
+        let collection = (&context.collection_interactor)
+            .add_recipe(id, user_id, recipe_id, position.map(|p| p.max(0) as usize))
+            .map_err(coded_field_error)?;
+        CollectionGraphQL::from(collection, context)
+    }
+
+    fn removeRecipeFromCollection(
+        context: &Context,
+        id: String,
+        recipe_id: String,
+    ) -> FieldResult<CollectionGraphQL> {
+        let user_id = context.get_user()?;
+        let collection = (&context.collection_interactor)
+            .remove_recipe(id, user_id, recipe_id)
+            .map_err(coded_field_error)?;
+        CollectionGraphQL::from(collection, context)
+    }
+
+    /// Plans a recipe the user can read. Without servings, the yield of the
+    /// recipe is cooked.
+    fn addMealPlanEntry(
+        context: &Context,
+        recipe_id: String,
+        date: NaiveDate,
+        slot: MealSlotGraphQL,
+        servings: Option<i32>,
+    ) -> FieldResult<MealPlanEntryGraphQL> {
+        let user_id = context.get_user()?;
+        let entry = (&context.meal_plan_interactor)
+            .add_entry(user_id, recipe_id, date, slot.into_slot(), servings)
+            .map_err(coded_field_error)?;
+        MealPlanEntryGraphQL::load(entry, context)
+    }
+
+    fn moveMealPlanEntry(
+        context: &Context,
+        id: String,
+        date: NaiveDate,
+        slot: MealSlotGraphQL,
+    ) -> FieldResult<MealPlanEntryGraphQL> {
+        let user_id = context.get_user()?;
+        let entry = (&context.meal_plan_interactor)
+            .move_entry(id, user_id, date, slot.into_slot())
+            .map_err(coded_field_error)?;
+        MealPlanEntryGraphQL::load(entry, context)
+    }
+
+    fn removeMealPlanEntry(context: &Context, id: String) -> FieldResult<String> {
+        let user_id = context.get_user()?;
+        (&context.meal_plan_interactor)
+            .remove_entry(id.clone(), user_id)
+            .map_err(coded_field_error)?;
+        Ok(id)
+    }
+
+    /// Lists the ingredients of recipes the user can read, merging the
+    /// quantities of the same ingredient, without what the pantry has when
+    /// `subtractPantry` is true.
+    fn createShoppingList(
+        context: &Context,
+        name: String,
+        recipes: Vec<RecipeServingsGraphQL>,
+        subtract_pantry: Option<bool>,
+    ) -> FieldResult<ShoppingListGraphQL> {
+        let user_id = context.get_user()?;
+        let recipes = recipes
+            .into_iter()
+            .map(
+                |RecipeServingsGraphQL {
+                     recipe_id,
+                     servings,
+                 }| RecipeIdServings {
+                    recipe_id,
+                    servings,
+                },
+            )
+            .collect();
+        let today = Utc::now().naive_utc().date();
+        let shopping_list = (&context.shopping_list_interactor)
+            .create_shopping_list(
+                user_id,
+                name,
+                recipes,
+                subtract_pantry.unwrap_or(false),
+                today,
+            )
+            .map_err(coded_field_error)?;
+        Ok(ShoppingListGraphQL::from(shopping_list))
+    }
+
+    /// Lists the ingredients of the meals planned between the two dates, both
+    /// included, skipping the recipes the user can no longer read.
+    fn createShoppingListFromMealPlan(
+        context: &Context,
+        name: String,
+        start: NaiveDate,
+        end: NaiveDate,
+        subtract_pantry: Option<bool>,
+    ) -> FieldResult<ShoppingListGraphQL> {
+        let user_id = context.get_user()?;
+        let today = Utc::now().naive_utc().date();
+        let shopping_list = (&context.shopping_list_interactor)
+            .create_shopping_list_from_meal_plan(
+                user_id,
+                name,
+                start,
+                end,
+                subtract_pantry.unwrap_or(false),
+                today,
+            )
+            .map_err(coded_field_error)?;
+        Ok(ShoppingListGraphQL::from(shopping_list))
+    }
+
+    fn addPantryItem(
+        context: &Context,
+        name: String,
+        quantity: Option<f64>,
+        unit: Option<String>,
+        expires_on: Option<NaiveDate>,
+    ) -> FieldResult<PantryItemGraphQL> {
+        let user_id = context.get_user()?;
+        let item = (&context.pantry_interactor)
+            .add_item(user_id, name, quantity, unit, expires_on)
+            .map_err(coded_field_error)?;
+        Ok(PantryItemGraphQL::from(&item))
+    }
+
+    /// Replaces the quantity, unit and expiry date of the item.
+    fn updatePantryItem(
+        context: &Context,
+        id: String,
+        quantity: Option<f64>,
+        unit: Option<String>,
+        expires_on: Option<NaiveDate>,
+    ) -> FieldResult<PantryItemGraphQL> {
+        let user_id = context.get_user()?;
+        let item = (&context.pantry_interactor)
+            .update_item(id, user_id, quantity, unit, expires_on)
+            .map_err(coded_field_error)?;
+        Ok(PantryItemGraphQL::from(&item))
+    }
+
+    fn removePantryItem(context: &Context, id: String) -> FieldResult<String> {
+        let user_id = context.get_user()?;
+        (&context.pantry_interactor)
+            .remove_item(id.clone(), user_id)
+            .map_err(coded_field_error)?;
+        Ok(id)
+    }
+
+    fn checkShoppingListItem(
+        context: &Context,
+        id: String,
+        item_id: String,
+        checked: bool,
+    ) -> FieldResult<ShoppingListGraphQL> {
+        let user_id = context.get_user()?;
+        let shopping_list = (&context.shopping_list_interactor)
+            .check_item(id, user_id, item_id, checked)
+            .map_err(coded_field_error)?;
+        Ok(ShoppingListGraphQL::from(shopping_list))
+    }
+
+    fn deleteShoppingList(context: &Context, id: String) -> FieldResult<String> {
+        let user_id = context.get_user()?;
+        (&context.shopping_list_interactor)
+            .delete_shopping_list(id.clone(), user_id)
+            .map_err(coded_field_error)?;
+        Ok(id)
+    }
+
     fn signup(context: &Context, email: String, password: String) -> FieldResult<String> {
         let id = Uuid::new_v4();
         let user = (&context.user_interactor).signup(id, email, password)?;
@@ -234,6 +1751,68 @@ impl Mutation {
         Ok(jwt_token)
     }
 
+    fn createHousehold(context: &Context, name: String) -> FieldResult<HouseholdGraphQL> {
+        let user_id = context.get_user()?;
+        let household = (&context.user_interactor)
+            .create_household(&user_id, &name)
+            .map_err(coded_field_error)?;
+        Ok(HouseholdGraphQL::from(household))
+    }
+
+    fn inviteToHousehold(context: &Context, email: String) -> FieldResult<HouseholdGraphQL> {
+        let user_id = context.get_user()?;
+        let household = (&context.user_interactor)
+            .invite_to_household(&user_id, &email)
+            .map_err(coded_field_error)?;
+        Ok(HouseholdGraphQL::from(household))
+    }
+
+    fn acceptHouseholdInvitation(context: &Context, id: String) -> FieldResult<HouseholdGraphQL> {
+        let user_id = context.get_user()?;
+        let household = (&context.user_interactor)
+            .accept_invitation(&user_id, &id)
+            .map_err(coded_field_error)?;
+        Ok(HouseholdGraphQL::from(household))
+    }
+
+    fn declineHouseholdInvitation(context: &Context, id: String) -> FieldResult<String> {
+        let user_id = context.get_user()?;
+        (&context.user_interactor)
+            .decline_invitation(&user_id, &id)
+            .map_err(coded_field_error)?;
+        Ok(id)
+    }
+
+    fn leaveHousehold(context: &Context) -> FieldResult<String> {
+        let user_id = context.get_user()?;
+        let household_id = (&context.user_interactor)
+            .leave_household(&user_id)
+            .map_err(coded_field_error)?;
+        Ok(household_id)
+    }
+
+    fn setUnitSystem(
+        context: &Context,
+        unit_system: Option<UnitSystemGraphQL>,
+    ) -> FieldResult<Option<UnitSystemGraphQL>> {
+        let user_id = context.get_user()?;
+        let unit_system = (&context.user_interactor).set_unit_system(
+            &user_id,
+            unit_system.map(UnitSystemGraphQL::into_unit_system),
+        )?;
+        Ok(unit_system.map(UnitSystemGraphQL::from))
+    }
+
+    /// Enables the meal plan feed with a new secret token, revoking the
+    /// previous one, or disables it. Returns the token.
+    fn setCalendarFeed(context: &Context, enabled: bool) -> FieldResult<Option<String>> {
+        let user_id = context.get_user()?;
+        let calendar_token = (&context.user_interactor)
+            .set_calendar_feed(&user_id, enabled)
+            .map_err(coded_field_error)?;
+        Ok(calendar_token)
+    }
+
     pub fn get_photo_upload_url(context: &Context, extension: String) -> FieldResult<String> {
         let url = (&context.recipe_interactor).get_photo_upload_url(&extension)?;
         Ok(url)
@@ -1,18 +1,47 @@
-use rocket::http::Method;
+use chrono::Utc;
+use rocket::http::{ContentType, Method, Status};
 use rocket::{response::content, Rocket, State};
 use rocket_cors::{AllowedHeaders, AllowedOrigins, Cors, CorsOptions};
+use std::env;
+use std::rc::Rc;
+use std::sync::Arc;
 
+use crate::domain::meal_plans::interactors::meal_plan::MealPlanInteractor;
+use crate::domain::meal_plans::models::calendar::get_icalendar;
+use crate::domain::recipes::ports::fetcher::Fetcher;
+use crate::domain::recipes::ports::parser::Parser;
+use crate::domain::users::errors::UserError;
+use crate::infrastructure::http::fetcher::ReqwestFetcher;
+use crate::infrastructure::parser::html::SelectParser;
+use crate::infrastructure::sql::meal_plans::dao::DieselMealPlanDao;
+use crate::infrastructure::sql::recipes::dao::DieselRecipeDao;
+use crate::infrastructure::sql::users::dao::DieselUserDao;
 use crate::infrastructure::web::graphql_schema::{Context, Mutation, Query, Schema};
 
 #[database("master")]
 pub struct DbCon(diesel::PgConnection);
 
+/// The recipe page parser, built once and shared by all the requests.
+pub type SharedParser = Arc<dyn Parser + Send + Sync>;
+
 pub fn get_server() -> Rocket {
+    get_server_with_fetcher(Box::new(ReqwestFetcher::default()))
+}
+
+/// The server fetching the imported recipe pages with this fetcher.
+pub fn get_server_with_fetcher(fetcher: Box<dyn Fetcher + Send + Sync>) -> Rocket {
+    let parser: SharedParser = Arc::new(SelectParser::new(fetcher));
     rocket::ignite()
         .manage(Schema::new(Query, Mutation))
+        .manage(parser)
         .mount(
             "/",
-            rocket::routes![graphiql, get_graphql_handler, post_graphql_handler],
+            rocket::routes![
+                graphiql,
+                get_graphql_handler,
+                post_graphql_handler,
+                get_calendar_feed
+            ],
         )
         .attach(make_cors())
         .attach(DbCon::fairing())
@@ -45,6 +74,38 @@ fn post_graphql_handler(
     request.execute(&schema, &context)
 }
 
+/// The meal plan of the user having the secret token, as an iCalendar feed to
+/// subscribe to from a calendar app: `/calendar/<token>.ics`.
+#[rocket::get("/calendar/<file_name>")]
+fn get_calendar_feed(file_name: String, db_con: DbCon) -> Result<content::Content<String>, Status> {
+    let calendar_token = file_name.strip_suffix(".ics").ok_or(Status::NotFound)?;
+    let connection = Rc::new(db_con.0);
+    let meal_plan_interactor = MealPlanInteractor {
+        meal_plan_dao: Box::new(DieselMealPlanDao::new(connection.clone())),
+        recipe_dao: Box::new(DieselRecipeDao::new(connection.clone())),
+        user_dao: Box::new(DieselUserDao::new(connection)),
+    };
+    let now = Utc::now().naive_utc();
+    let events = meal_plan_interactor
+        .get_calendar(calendar_token, now.date())
+        .map_err(|error| match error.downcast_ref::<UserError>() {
+            Some(UserError::UserNotFound) => Status::NotFound,
+            _ => Status::InternalServerError,
+        })?;
+    Ok(content::Content(
+        ContentType::new("text", "calendar"),
+        get_icalendar(&events, &get_recipes_url(), now),
+    ))
+}
+
+/// Where the recipes are shown, followed by their id in the feed links.
+fn get_recipes_url() -> String {
+    match env::var("RECIPES_URL") {
+        Ok(recipes_url) => recipes_url,
+        Err(_e) => String::from("http://localhost:3000/recipes/"),
+    }
+}
+
 fn make_cors() -> Cors {
     let allowed_origins = AllowedOrigins::All;
 
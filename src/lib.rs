@@ -2,6 +2,7 @@
 
 #[macro_use]
 extern crate diesel;
+extern crate chrono;
 extern crate dotenv;
 extern crate iso8601_duration;
 extern crate itertools;
@@ -0,0 +1,292 @@
+extern crate recipes_backend;
+extern crate serde_json;
+
+use self::recipes_backend::domain::users::models::user::User;
+use self::recipes_backend::infrastructure::web::jwt::generate_header;
+use self::recipes_backend::infrastructure::web::server;
+use rocket::http::Header;
+
+use diesel::pg::PgConnection;
+use diesel::Connection;
+use diesel::RunQueryDsl;
+use rocket::http::{ContentType, Status};
+use rocket::local::Client;
+use serde_json::Value;
+use std::error::Error;
+use uuid::Uuid;
+
+use dotenv::dotenv;
+use std::env;
+
+fn get_database_url() -> String {
+    String::from(
+        env::var("DATABASE_URL")
+            .or_else(|_e| {
+                dotenv().ok();
+                env::var("DATABASE_URL")
+            })
+            .expect("DATABASE_URL must be set"),
+    )
+}
+
+fn get_rocket_client() -> Client {
+    env::set_var("JWT_SECRET", "SECRET");
+    env::set_var("BUCKET_NAME", "rustipe-photos-test");
+    env::set_var(
+        "ROCKET_DATABASE_master",
+        "{ url = \"postgres://localhost/rustipe-test\", pool_size = 1 }",
+    );
+    Client::new(server::get_server()).expect("valid rocket instance")
+}
+
+pub fn establish_connection() -> PgConnection {
+    let database_url = get_database_url();
+    PgConnection::establish(&database_url).expect(&format!("Error connecting to {}", database_url))
+}
+
+fn clean_db(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::schema::collection_recipes::dsl::collection_recipes;
+    use self::recipes_backend::infrastructure::sql::schema::collections::dsl::collections;
+    use self::recipes_backend::infrastructure::sql::schema::household_invitations::dsl::household_invitations;
+    use self::recipes_backend::infrastructure::sql::schema::households::dsl::households;
+    use self::recipes_backend::infrastructure::sql::schema::ingredients::dsl::ingredients;
+    use self::recipes_backend::infrastructure::sql::schema::instructions::dsl::instructions;
+    use self::recipes_backend::infrastructure::sql::schema::meal_plan_entries::dsl::meal_plan_entries;
+    use self::recipes_backend::infrastructure::sql::schema::pantry_items::dsl::pantry_items;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_import_snapshots::dsl::recipe_import_snapshots;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_shares::dsl::recipe_shares;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_tags::dsl::recipe_tags;
+    use self::recipes_backend::infrastructure::sql::schema::recipes::dsl::recipes;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_list_items::dsl::shopping_list_items;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_lists::dsl::shopping_lists;
+    use self::recipes_backend::infrastructure::sql::schema::users::dsl::users;
+
+    diesel::delete(pantry_items).execute(connexion)?;
+    diesel::delete(shopping_list_items).execute(connexion)?;
+    diesel::delete(shopping_lists).execute(connexion)?;
+    diesel::delete(meal_plan_entries).execute(connexion)?;
+    diesel::delete(collection_recipes).execute(connexion)?;
+    diesel::delete(collections).execute(connexion)?;
+    diesel::delete(recipe_import_snapshots).execute(connexion)?;
+    diesel::delete(recipe_shares).execute(connexion)?;
+    diesel::delete(recipe_tags).execute(connexion)?;
+    diesel::delete(ingredients).execute(connexion)?;
+    diesel::delete(instructions).execute(connexion)?;
+    diesel::delete(recipes).execute(connexion)?;
+    diesel::delete(users).execute(connexion)?;
+    diesel::delete(household_invitations).execute(connexion)?;
+    diesel::delete(households).execute(connexion)?;
+    Ok(())
+}
+
+fn init_with_users(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::models::*;
+    use self::recipes_backend::infrastructure::sql::schema::users;
+
+    let new_user_1 = NewUser {
+        id: "2f0194af-66e6-43f5-8e1a-2e836c9e44a8",
+        email: "email1",
+        password_hash: "password",
+    };
+    let new_user_2 = NewUser {
+        id: "2f0194af-66e6-43f5-8e1a-2e836c9e44a7",
+        email: "email2",
+        password_hash: "password",
+    };
+
+    diesel::insert_into(users::table)
+        .values(&vec![new_user_1, new_user_2])
+        .get_result::<User>(connexion)
+        .unwrap();
+    Ok(())
+}
+
+fn get_auth_user_1<'a>() -> Header<'a> {
+    let u = User {
+        id: Uuid::parse_str("2f0194af-66e6-43f5-8e1a-2e836c9e44a8").expect("Cannot parse UUID"),
+        email: String::from("email1"),
+    };
+    let token = generate_header(u).unwrap();
+
+    let mut value = String::from("Bearer ");
+    value.push_str(&token);
+    Header::new("Authorization", value)
+}
+
+fn create_recipe(client: &Client, title: &str) -> String {
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  createRecipe(newRecipe: {{title: \"{title}\", instructions: [\"ins1\"], ingredients: [\"ing1\"]}}) {{ id }} }}"}}"#,
+            title = title
+        ))
+        .dispatch();
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    String::from(body["data"]["createRecipe"]["id"].as_str().unwrap())
+}
+
+fn create_collection(client: &Client, name: &str) -> String {
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  createCollection(name: \"{name}\") {{ id }} }}"}}"#,
+            name = name
+        ))
+        .dispatch();
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    String::from(body["data"]["createCollection"]["id"].as_str().unwrap())
+}
+
+fn get_auth_user_2<'a>() -> Header<'a> {
+    let u = User {
+        id: Uuid::parse_str("2f0194af-66e6-43f5-8e1a-2e836c9e44a7").expect("Cannot parse UUID"),
+        email: String::from("email2"),
+    };
+    let token = generate_header(u).unwrap();
+
+    let mut value = String::from("Bearer ");
+    value.push_str(&token);
+    Header::new("Authorization", value)
+}
+
+#[test]
+fn test_add_recipes_to_collection() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let lasagna_id = create_recipe(&client, "lasagna");
+    let pasta_id = create_recipe(&client, "pasta");
+    let collection_id = create_collection(&client, "Weeknight");
+    client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  addRecipeToCollection(id: \"{id}\", recipeId: \"{recipe_id}\") {{ id }} }}"}}"#,
+            id = collection_id,
+            recipe_id = lasagna_id
+        ))
+        .dispatch();
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  addRecipeToCollection(id: \"{id}\", recipeId: \"{recipe_id}\", position: 0) {{ name recipes {{ title }} }} }}"}}"#,
+            id = collection_id,
+            recipe_id = pasta_id
+        ))
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.body_string(),
+        Some(String::from(
+            "{\"data\":{\"addRecipeToCollection\":{\"name\":\"Weeknight\",\"recipes\":[{\"title\":\"pasta\"},{\"title\":\"lasagna\"}]}}}"
+        ))
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_collection_of_another_user() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let collection_id = create_collection(&client, "Christmas 2026");
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_2())
+        .body(format!(
+            r#"{{"query":"{{  getCollection(id: \"{id}\") {{ name }} }}"}}"#,
+            id = collection_id
+        ))
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["errors"][0]["extensions"]["code"],
+        "COLLECTION_NOT_YOURS"
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_collection_skips_unreadable_recipes() {
+    use self::recipes_backend::infrastructure::sql::models::NewCollectionRecipe;
+    use self::recipes_backend::infrastructure::sql::schema::collection_recipes;
+
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let lasagna_id = create_recipe(&client, "lasagna");
+    let collection_id = create_collection(&client, "Weeknight");
+    client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  addRecipeToCollection(id: \"{id}\", recipeId: \"{recipe_id}\") {{ id }} }}"}}"#,
+            id = collection_id,
+            recipe_id = lasagna_id
+        ))
+        .dispatch();
+    let mut response_secret = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_2())
+        .body(r#"{"query":"mutation {  createRecipe(newRecipe: {title: \"secret\", instructions: [], ingredients: []}) { id } }"}"#)
+        .dispatch();
+    let body: Value = serde_json::from_str(&response_secret.body_string().unwrap()).unwrap();
+    let secret_id = body["data"]["createRecipe"]["id"].as_str().unwrap();
+    diesel::insert_into(collection_recipes::table)
+        .values(&NewCollectionRecipe {
+            collection_id: &collection_id,
+            recipe_id: secret_id,
+            position: 1,
+        })
+        .execute(&connexion)
+        .unwrap();
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"{{  getCollection(id: \"{id}\") {{ recipes {{ title }} }} }}"}}"#,
+            id = collection_id
+        ))
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.body_string(),
+        Some(String::from(
+            "{\"data\":{\"getCollection\":{\"recipes\":[{\"title\":\"lasagna\"}]}}}"
+        ))
+    );
+
+    clean_db(&connexion).unwrap();
+}
@@ -0,0 +1,237 @@
+extern crate recipes_backend;
+extern crate serde_json;
+
+use self::recipes_backend::domain::users::models::user::User;
+use self::recipes_backend::infrastructure::web::jwt::generate_header;
+use self::recipes_backend::infrastructure::web::server;
+use rocket::http::Header;
+
+use diesel::pg::PgConnection;
+use diesel::Connection;
+use diesel::RunQueryDsl;
+use rocket::http::{ContentType, Status};
+use rocket::local::Client;
+use serde_json::Value;
+use std::error::Error;
+use uuid::Uuid;
+
+use dotenv::dotenv;
+use std::env;
+
+fn get_database_url() -> String {
+    String::from(
+        env::var("DATABASE_URL")
+            .or_else(|_e| {
+                dotenv().ok();
+                env::var("DATABASE_URL")
+            })
+            .expect("DATABASE_URL must be set"),
+    )
+}
+
+fn get_rocket_client() -> Client {
+    env::set_var("JWT_SECRET", "SECRET");
+    env::set_var("BUCKET_NAME", "rustipe-photos-test");
+    env::set_var(
+        "ROCKET_DATABASE_master",
+        "{ url = \"postgres://localhost/rustipe-test\", pool_size = 1 }",
+    );
+    Client::new(server::get_server()).expect("valid rocket instance")
+}
+
+pub fn establish_connection() -> PgConnection {
+    let database_url = get_database_url();
+    PgConnection::establish(&database_url).expect(&format!("Error connecting to {}", database_url))
+}
+
+fn clean_db(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::schema::collection_recipes::dsl::collection_recipes;
+    use self::recipes_backend::infrastructure::sql::schema::collections::dsl::collections;
+    use self::recipes_backend::infrastructure::sql::schema::household_invitations::dsl::household_invitations;
+    use self::recipes_backend::infrastructure::sql::schema::households::dsl::households;
+    use self::recipes_backend::infrastructure::sql::schema::ingredients::dsl::ingredients;
+    use self::recipes_backend::infrastructure::sql::schema::instructions::dsl::instructions;
+    use self::recipes_backend::infrastructure::sql::schema::meal_plan_entries::dsl::meal_plan_entries;
+    use self::recipes_backend::infrastructure::sql::schema::pantry_items::dsl::pantry_items;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_import_snapshots::dsl::recipe_import_snapshots;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_shares::dsl::recipe_shares;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_tags::dsl::recipe_tags;
+    use self::recipes_backend::infrastructure::sql::schema::recipes::dsl::recipes;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_list_items::dsl::shopping_list_items;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_lists::dsl::shopping_lists;
+    use self::recipes_backend::infrastructure::sql::schema::users::dsl::users;
+
+    diesel::delete(pantry_items).execute(connexion)?;
+    diesel::delete(shopping_list_items).execute(connexion)?;
+    diesel::delete(shopping_lists).execute(connexion)?;
+    diesel::delete(meal_plan_entries).execute(connexion)?;
+    diesel::delete(collection_recipes).execute(connexion)?;
+    diesel::delete(collections).execute(connexion)?;
+    diesel::delete(recipe_import_snapshots).execute(connexion)?;
+    diesel::delete(recipe_shares).execute(connexion)?;
+    diesel::delete(recipe_tags).execute(connexion)?;
+    diesel::delete(ingredients).execute(connexion)?;
+    diesel::delete(instructions).execute(connexion)?;
+    diesel::delete(recipes).execute(connexion)?;
+    diesel::delete(users).execute(connexion)?;
+    diesel::delete(household_invitations).execute(connexion)?;
+    diesel::delete(households).execute(connexion)?;
+    Ok(())
+}
+
+fn init_with_users(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::models::*;
+    use self::recipes_backend::infrastructure::sql::schema::users;
+
+    let new_user_1 = NewUser {
+        id: "2f0194af-66e6-43f5-8e1a-2e836c9e44a8",
+        email: "email1",
+        password_hash: "password",
+    };
+    let new_user_2 = NewUser {
+        id: "2f0194af-66e6-43f5-8e1a-2e836c9e44a7",
+        email: "email2",
+        password_hash: "password",
+    };
+
+    diesel::insert_into(users::table)
+        .values(&vec![new_user_1, new_user_2])
+        .get_result::<User>(connexion)
+        .unwrap();
+    Ok(())
+}
+
+fn get_auth_user_1<'a>() -> Header<'a> {
+    let u = User {
+        id: Uuid::parse_str("2f0194af-66e6-43f5-8e1a-2e836c9e44a8").expect("Cannot parse UUID"),
+        email: String::from("email1"),
+    };
+    let token = generate_header(u).unwrap();
+
+    let mut value = String::from("Bearer ");
+    value.push_str(&token);
+    Header::new("Authorization", value)
+}
+
+fn get_auth_user_2<'a>() -> Header<'a> {
+    let u = User {
+        id: Uuid::parse_str("2f0194af-66e6-43f5-8e1a-2e836c9e44a7").expect("Cannot parse UUID"),
+        email: String::from("email2"),
+    };
+    let token = generate_header(u).unwrap();
+
+    let mut value = String::from("Bearer ");
+    value.push_str(&token);
+    Header::new("Authorization", value)
+}
+
+fn create_recipe(client: &Client, title: &str) -> String {
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  createRecipe(newRecipe: {{title: \"{title}\", instructions: [\"ins1\"], ingredients: [\"ing1\"]}}) {{ id }} }}"}}"#,
+            title = title
+        ))
+        .dispatch();
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    String::from(body["data"]["createRecipe"]["id"].as_str().unwrap())
+}
+
+fn post(client: &Client, auth: Header<'static>, query: String) -> Value {
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(auth)
+        .body(format!(r#"{{"query":"{query}"}}"#, query = query))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.body_string().unwrap()).unwrap()
+}
+
+#[test]
+fn test_share_recipes_with_household() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let body = post(
+        &client,
+        get_auth_user_1(),
+        String::from(r#"mutation {  createHousehold(name: \"Gery\") { id } }"#),
+    );
+    let household_id = String::from(body["data"]["createHousehold"]["id"].as_str().unwrap());
+    post(
+        &client,
+        get_auth_user_1(),
+        String::from(r#"mutation {  inviteToHousehold(email: \"email2\") { id } }"#),
+    );
+    let recipe_id = create_recipe(&client, "lasagna");
+    post(
+        &client,
+        get_auth_user_1(),
+        format!(
+            r#"mutation {{  setRecipeHousehold(id: \"{id}\", inHousehold: true) {{ id }} }}"#,
+            id = recipe_id
+        ),
+    );
+
+    // when
+    let invitations = post(
+        &client,
+        get_auth_user_2(),
+        String::from(r#"{  getMyHouseholdInvitations { id name } }"#),
+    );
+    let accepted = post(
+        &client,
+        get_auth_user_2(),
+        format!(
+            r#"mutation {{  acceptHouseholdInvitation(id: \"{id}\") {{ name members }} }}"#,
+            id = household_id
+        ),
+    );
+    let recipes = post(
+        &client,
+        get_auth_user_2(),
+        String::from(r#"{  getMyRecipes { title householdId } }"#),
+    );
+
+    // then
+    assert_eq!(
+        invitations["data"]["getMyHouseholdInvitations"],
+        serde_json::json!([{ "id": household_id, "name": "Gery" }])
+    );
+    assert_eq!(
+        accepted["data"]["acceptHouseholdInvitation"],
+        serde_json::json!({ "name": "Gery", "members": ["email1", "email2"] })
+    );
+    assert_eq!(
+        recipes["data"]["getMyRecipes"],
+        serde_json::json!([{ "title": "lasagna", "householdId": household_id }])
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_invite_without_household() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+
+    // when
+    let body = post(
+        &client,
+        get_auth_user_1(),
+        String::from(r#"mutation {  inviteToHousehold(email: \"email2\") { id } }"#),
+    );
+
+    // then
+    assert_eq!(body["errors"][0]["extensions"]["code"], "NOT_IN_HOUSEHOLD");
+
+    clean_db(&connexion).unwrap();
+}
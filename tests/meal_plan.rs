@@ -0,0 +1,339 @@
+extern crate recipes_backend;
+extern crate serde_json;
+
+use self::recipes_backend::domain::users::models::user::User;
+use self::recipes_backend::infrastructure::web::jwt::generate_header;
+use self::recipes_backend::infrastructure::web::server;
+use rocket::http::Header;
+
+use chrono::Utc;
+use diesel::pg::PgConnection;
+use diesel::Connection;
+use diesel::RunQueryDsl;
+use rocket::http::{ContentType, Status};
+use rocket::local::Client;
+use serde_json::Value;
+use std::error::Error;
+use uuid::Uuid;
+
+use dotenv::dotenv;
+use std::env;
+
+fn get_database_url() -> String {
+    String::from(
+        env::var("DATABASE_URL")
+            .or_else(|_e| {
+                dotenv().ok();
+                env::var("DATABASE_URL")
+            })
+            .expect("DATABASE_URL must be set"),
+    )
+}
+
+fn get_rocket_client() -> Client {
+    env::set_var("JWT_SECRET", "SECRET");
+    env::set_var("BUCKET_NAME", "rustipe-photos-test");
+    env::set_var(
+        "ROCKET_DATABASE_master",
+        "{ url = \"postgres://localhost/rustipe-test\", pool_size = 1 }",
+    );
+    Client::new(server::get_server()).expect("valid rocket instance")
+}
+
+pub fn establish_connection() -> PgConnection {
+    let database_url = get_database_url();
+    PgConnection::establish(&database_url).expect(&format!("Error connecting to {}", database_url))
+}
+
+fn clean_db(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::schema::collection_recipes::dsl::collection_recipes;
+    use self::recipes_backend::infrastructure::sql::schema::collections::dsl::collections;
+    use self::recipes_backend::infrastructure::sql::schema::household_invitations::dsl::household_invitations;
+    use self::recipes_backend::infrastructure::sql::schema::households::dsl::households;
+    use self::recipes_backend::infrastructure::sql::schema::ingredients::dsl::ingredients;
+    use self::recipes_backend::infrastructure::sql::schema::instructions::dsl::instructions;
+    use self::recipes_backend::infrastructure::sql::schema::meal_plan_entries::dsl::meal_plan_entries;
+    use self::recipes_backend::infrastructure::sql::schema::pantry_items::dsl::pantry_items;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_import_snapshots::dsl::recipe_import_snapshots;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_shares::dsl::recipe_shares;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_tags::dsl::recipe_tags;
+    use self::recipes_backend::infrastructure::sql::schema::recipes::dsl::recipes;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_list_items::dsl::shopping_list_items;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_lists::dsl::shopping_lists;
+    use self::recipes_backend::infrastructure::sql::schema::users::dsl::users;
+
+    diesel::delete(pantry_items).execute(connexion)?;
+    diesel::delete(shopping_list_items).execute(connexion)?;
+    diesel::delete(shopping_lists).execute(connexion)?;
+    diesel::delete(meal_plan_entries).execute(connexion)?;
+    diesel::delete(collection_recipes).execute(connexion)?;
+    diesel::delete(collections).execute(connexion)?;
+    diesel::delete(recipe_import_snapshots).execute(connexion)?;
+    diesel::delete(recipe_shares).execute(connexion)?;
+    diesel::delete(recipe_tags).execute(connexion)?;
+    diesel::delete(ingredients).execute(connexion)?;
+    diesel::delete(instructions).execute(connexion)?;
+    diesel::delete(recipes).execute(connexion)?;
+    diesel::delete(users).execute(connexion)?;
+    diesel::delete(household_invitations).execute(connexion)?;
+    diesel::delete(households).execute(connexion)?;
+    Ok(())
+}
+
+fn init_with_users(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::models::*;
+    use self::recipes_backend::infrastructure::sql::schema::users;
+
+    let new_user_1 = NewUser {
+        id: "2f0194af-66e6-43f5-8e1a-2e836c9e44a8",
+        email: "email1",
+        password_hash: "password",
+    };
+    let new_user_2 = NewUser {
+        id: "2f0194af-66e6-43f5-8e1a-2e836c9e44a7",
+        email: "email2",
+        password_hash: "password",
+    };
+
+    diesel::insert_into(users::table)
+        .values(&vec![new_user_1, new_user_2])
+        .get_result::<User>(connexion)
+        .unwrap();
+    Ok(())
+}
+
+fn get_auth_user_1<'a>() -> Header<'a> {
+    let u = User {
+        id: Uuid::parse_str("2f0194af-66e6-43f5-8e1a-2e836c9e44a8").expect("Cannot parse UUID"),
+        email: String::from("email1"),
+    };
+    let token = generate_header(u).unwrap();
+
+    let mut value = String::from("Bearer ");
+    value.push_str(&token);
+    Header::new("Authorization", value)
+}
+
+fn get_auth_user_2<'a>() -> Header<'a> {
+    let u = User {
+        id: Uuid::parse_str("2f0194af-66e6-43f5-8e1a-2e836c9e44a7").expect("Cannot parse UUID"),
+        email: String::from("email2"),
+    };
+    let token = generate_header(u).unwrap();
+
+    let mut value = String::from("Bearer ");
+    value.push_str(&token);
+    Header::new("Authorization", value)
+}
+
+fn create_recipe(client: &Client, title: &str) -> String {
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  createRecipe(newRecipe: {{title: \"{title}\", instructions: [\"ins1\"], ingredients: [\"ing1\"]}}) {{ id }} }}"}}"#,
+            title = title
+        ))
+        .dispatch();
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    String::from(body["data"]["createRecipe"]["id"].as_str().unwrap())
+}
+
+fn post(client: &Client, auth: Header<'static>, query: String) -> Value {
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(auth)
+        .body(format!(r#"{{"query":"{query}"}}"#, query = query))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.body_string().unwrap()).unwrap()
+}
+
+#[test]
+fn test_plan_meals_of_the_week() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let soup_id = create_recipe(&client, "soup");
+    let salad_id = create_recipe(&client, "salad");
+    for (recipe_id, date, slot) in &[
+        (&soup_id, "2026-10-19", "DINNER"),
+        (&salad_id, "2026-10-19", "LUNCH"),
+        (&soup_id, "2026-10-26", "LUNCH"),
+    ] {
+        post(
+            &client,
+            get_auth_user_1(),
+            format!(
+                r#"mutation {{  addMealPlanEntry(recipeId: \"{recipe_id}\", date: \"{date}\", slot: {slot}, servings: 2) {{ id }} }}"#,
+                recipe_id = recipe_id,
+                date = date,
+                slot = slot
+            ),
+        );
+    }
+    let body = post(
+        &client,
+        get_auth_user_1(),
+        format!(
+            r#"mutation {{  addMealPlanEntry(recipeId: \"{recipe_id}\", date: \"2026-10-20\", slot: BREAKFAST) {{ id }} }}"#,
+            recipe_id = salad_id
+        ),
+    );
+    let entry_id = String::from(body["data"]["addMealPlanEntry"]["id"].as_str().unwrap());
+
+    // when
+    let moved = post(
+        &client,
+        get_auth_user_1(),
+        format!(
+            r#"mutation {{  moveMealPlanEntry(id: \"{id}\", date: \"2026-10-21\", slot: DINNER) {{ date slot }} }}"#,
+            id = entry_id
+        ),
+    );
+    let meal_plan = post(
+        &client,
+        get_auth_user_1(),
+        String::from(
+            r#"{  getMyMealPlan(start: \"2026-10-19\", end: \"2026-10-25\") { start end entries { date slot servings recipe { title } } } }"#,
+        ),
+    );
+
+    // then
+    assert_eq!(
+        moved["data"]["moveMealPlanEntry"],
+        serde_json::json!({ "date": "2026-10-21", "slot": "DINNER" })
+    );
+    assert_eq!(
+        meal_plan["data"]["getMyMealPlan"],
+        serde_json::json!({
+            "start": "2026-10-19",
+            "end": "2026-10-25",
+            "entries": [
+                { "date": "2026-10-19", "slot": "LUNCH", "servings": 2, "recipe": { "title": "salad" } },
+                { "date": "2026-10-19", "slot": "DINNER", "servings": 2, "recipe": { "title": "soup" } },
+                { "date": "2026-10-21", "slot": "DINNER", "servings": null, "recipe": { "title": "salad" } }
+            ]
+        })
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_plan_meals_with_recipes_and_entries_of_another_user() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let recipe_id = create_recipe(&client, "soup");
+    let body = post(
+        &client,
+        get_auth_user_1(),
+        format!(
+            r#"mutation {{  addMealPlanEntry(recipeId: \"{recipe_id}\", date: \"2026-10-19\", slot: DINNER) {{ id }} }}"#,
+            recipe_id = recipe_id
+        ),
+    );
+    let entry_id = String::from(body["data"]["addMealPlanEntry"]["id"].as_str().unwrap());
+
+    // when
+    let private_recipe = post(
+        &client,
+        get_auth_user_2(),
+        format!(
+            r#"mutation {{  addMealPlanEntry(recipeId: \"{recipe_id}\", date: \"2026-10-19\", slot: DINNER) {{ id }} }}"#,
+            recipe_id = recipe_id
+        ),
+    );
+    let other_entry = post(
+        &client,
+        get_auth_user_2(),
+        format!(
+            r#"mutation {{  removeMealPlanEntry(id: \"{id}\") }}"#,
+            id = entry_id
+        ),
+    );
+    let too_long_range = post(
+        &client,
+        get_auth_user_2(),
+        String::from(r#"{  getMyMealPlan(start: \"2026-10-19\", end: \"2027-10-19\") { start } }"#),
+    );
+
+    // then
+    assert_eq!(
+        private_recipe["errors"][0]["extensions"]["code"],
+        "RECIPE_NOT_FOUND"
+    );
+    assert_eq!(
+        other_entry["errors"][0]["extensions"]["code"],
+        "ENTRY_NOT_YOURS"
+    );
+    assert_eq!(
+        too_long_range["errors"][0]["extensions"]["code"],
+        "INVALID_RANGE"
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_subscribe_to_meal_plan_calendar() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let recipe_id = create_recipe(&client, "soup");
+    post(
+        &client,
+        get_auth_user_1(),
+        format!(
+            r#"mutation {{  addMealPlanEntry(recipeId: \"{recipe_id}\", date: \"{date}\", slot: DINNER) {{ id }} }}"#,
+            recipe_id = recipe_id,
+            date = Utc::now().naive_utc().date().format("%Y-%m-%d")
+        ),
+    );
+    let body = post(
+        &client,
+        get_auth_user_1(),
+        String::from(r#"mutation {  setCalendarFeed(enabled: true) }"#),
+    );
+    let calendar_token = String::from(body["data"]["setCalendarFeed"].as_str().unwrap());
+
+    // when
+    let mut response = client
+        .get(format!("/calendar/{}.ics", calendar_token))
+        .dispatch();
+    let without_extension = client
+        .get(format!("/calendar/{}", calendar_token))
+        .dispatch();
+    post(
+        &client,
+        get_auth_user_1(),
+        String::from(r#"mutation {  setCalendarFeed(enabled: false) }"#),
+    );
+    let revoked = client
+        .get(format!("/calendar/{}.ics", calendar_token))
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("text", "calendar"))
+    );
+    let calendar = response.body_string().unwrap();
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(calendar.contains("\r\nSUMMARY:soup\r\n"));
+    assert!(calendar.contains(&format!("/recipes/{}", recipe_id)));
+    assert_eq!(without_extension.status(), Status::NotFound);
+    assert_eq!(revoked.status(), Status::NotFound);
+
+    clean_db(&connexion).unwrap();
+}
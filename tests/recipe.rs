@@ -1,18 +1,23 @@
 extern crate recipes_backend;
 extern crate serde_json;
 
+use self::recipes_backend::domain::recipes::errors::RecipeError;
+use self::recipes_backend::domain::recipes::ports::fetcher::Fetcher;
 use self::recipes_backend::domain::users::models::user::User;
+use self::recipes_backend::infrastructure::sql::schema::{recipe_import_snapshots, recipes};
 use self::recipes_backend::infrastructure::web::jwt::generate_header;
 use self::recipes_backend::infrastructure::web::server;
 use rocket::http::Header;
 
 use diesel::pg::PgConnection;
 use diesel::Connection;
-use diesel::RunQueryDsl;
+use diesel::{QueryDsl, RunQueryDsl};
 use rocket::http::{ContentType, Status};
 use rocket::local::Client;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::error::Error;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 use dotenv::dotenv;
@@ -30,13 +35,74 @@ fn get_database_url() -> String {
 }
 
 fn get_rocket_client() -> Client {
+    set_server_env();
+    Client::new(server::get_server()).expect("valid rocket instance")
+}
+
+/// A client of the server importing the pages served by this fetcher.
+fn get_rocket_client_with_fetcher(fetcher: StubFetcher) -> Client {
+    set_server_env();
+    Client::new(server::get_server_with_fetcher(Box::new(fetcher))).expect("valid rocket instance")
+}
+
+fn set_server_env() {
     env::set_var("JWT_SECRET", "SECRET");
     env::set_var("BUCKET_NAME", "rustipe-photos-test");
     env::set_var(
         "ROCKET_DATABASE_master",
         "{ url = \"postgres://localhost/rustipe-test\", pool_size = 1 }",
     );
-    Client::new(server::get_server()).expect("valid rocket instance")
+}
+
+/// Serves the pages set by the test instead of the websites, which are all
+/// unreachable. Its clones share the same pages.
+#[derive(Clone, Default)]
+struct StubFetcher {
+    pages: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl StubFetcher {
+    fn set_page(&self, url: &str, html: &str) {
+        self.pages
+            .lock()
+            .unwrap()
+            .insert(String::from(url), String::from(html));
+    }
+}
+
+impl Fetcher for StubFetcher {
+    fn fetch(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        match self.pages.lock().unwrap().get(url) {
+            Some(html) => Ok(html.clone()),
+            None => Err(Box::new(RecipeError::RecipeImportedWebsiteUnreachable)),
+        }
+    }
+}
+
+/// A page holding this schema.org recipe as JSON-LD.
+fn get_recipe_page(recipe: Value) -> String {
+    format!(
+        r#"<html><head><script type="application/ld+json">{}</script></head></html>"#,
+        recipe
+    )
+}
+
+/// Posts the GraphQL query along with its variables.
+fn post_with_variables(
+    client: &Client,
+    auth: Header<'static>,
+    query: &str,
+    variables: Value,
+) -> Value {
+    let request = serde_json::json!({ "query": query, "variables": variables });
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(auth)
+        .body(request.to_string())
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.body_string().unwrap()).unwrap()
 }
 
 pub fn establish_connection() -> PgConnection {
@@ -45,15 +111,37 @@ pub fn establish_connection() -> PgConnection {
 }
 
 fn clean_db(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::schema::collection_recipes::dsl::collection_recipes;
+    use self::recipes_backend::infrastructure::sql::schema::collections::dsl::collections;
+    use self::recipes_backend::infrastructure::sql::schema::household_invitations::dsl::household_invitations;
+    use self::recipes_backend::infrastructure::sql::schema::households::dsl::households;
     use self::recipes_backend::infrastructure::sql::schema::ingredients::dsl::ingredients;
     use self::recipes_backend::infrastructure::sql::schema::instructions::dsl::instructions;
+    use self::recipes_backend::infrastructure::sql::schema::meal_plan_entries::dsl::meal_plan_entries;
+    use self::recipes_backend::infrastructure::sql::schema::pantry_items::dsl::pantry_items;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_import_snapshots::dsl::recipe_import_snapshots;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_shares::dsl::recipe_shares;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_tags::dsl::recipe_tags;
     use self::recipes_backend::infrastructure::sql::schema::recipes::dsl::recipes;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_list_items::dsl::shopping_list_items;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_lists::dsl::shopping_lists;
     use self::recipes_backend::infrastructure::sql::schema::users::dsl::users;
 
+    diesel::delete(pantry_items).execute(connexion)?;
+    diesel::delete(shopping_list_items).execute(connexion)?;
+    diesel::delete(shopping_lists).execute(connexion)?;
+    diesel::delete(meal_plan_entries).execute(connexion)?;
+    diesel::delete(collection_recipes).execute(connexion)?;
+    diesel::delete(collections).execute(connexion)?;
+    diesel::delete(recipe_import_snapshots).execute(connexion)?;
+    diesel::delete(recipe_shares).execute(connexion)?;
+    diesel::delete(recipe_tags).execute(connexion)?;
     diesel::delete(ingredients).execute(connexion)?;
     diesel::delete(instructions).execute(connexion)?;
     diesel::delete(recipes).execute(connexion)?;
     diesel::delete(users).execute(connexion)?;
+    diesel::delete(household_invitations).execute(connexion)?;
+    diesel::delete(households).execute(connexion)?;
     Ok(())
 }
 
@@ -115,6 +203,20 @@ fn get_auth_user_3<'a>() -> Header<'a> {
     Header::new("Authorization", value)
 }
 
+fn create_recipe(client: &Client, title: &str) -> String {
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  createRecipe(newRecipe: {{title: \"{title}\", instructions: [\"ins1\"], ingredients: [\"ing1\"]}}) {{ id }} }}"}}"#,
+            title = title
+        ))
+        .dispatch();
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    String::from(body["data"]["createRecipe"]["id"].as_str().unwrap())
+}
+
 #[test]
 fn test_get_recipes_without_recipes() {
     // given
@@ -308,6 +410,7 @@ fn test_get_single_recipe() {
     let mut response = client
         .post("/graphql")
         .header(ContentType::JSON)
+        .header(get_auth_user_1())
         .body(format!(
             r#"{{"query":"{{  getRecipe(id: \"{id}\") {{ id title }} }}"}}"#,
             id = id
@@ -370,6 +473,87 @@ fn test_update_recipe() {
     clean_db(&connexion).unwrap();
 }
 
+#[test]
+fn test_update_recipe_without_sections_keeps_them() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let request = serde_json::json!({
+        "variables": { "newRecipe": {
+            "title": "Tarte",
+            "instructions": ["Étaler la pâte.", "Garnir."],
+            "instructionSections": ["Pâte", "Garniture"],
+            "ingredients": ["200 g de farine", "3 pommes"],
+            "ingredientSections": ["Pâte", "Garniture"]
+        } },
+        "query": "mutation ($newRecipe: NewRecipeGraphQL!) {\n  createRecipe(newRecipe: $newRecipe) {\n    id\n  }\n}\n"
+    });
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(request.to_string())
+        .dispatch();
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    let id = body["data"]["createRecipe"]["id"].as_str().unwrap();
+    let request = serde_json::json!({
+        "variables": { "id": id, "newRecipe": {
+            "title": "Tarte aux pommes",
+            "instructions": ["Étaler la pâte.", "Garnir.", "Cuire 30 min."],
+            "ingredients": ["200 g de farine"]
+        } },
+        "query": "mutation ($id: String!, $newRecipe: NewRecipeGraphQL!) {\n  updateRecipe(id: $id, newRecipe: $newRecipe) {\n    instructionSections\n    ingredientSections\n  }\n}\n"
+    });
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(request.to_string())
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["updateRecipe"]["instructionSections"],
+        serde_json::json!(["Pâte", "Garniture", null])
+    );
+    assert_eq!(
+        body["data"]["updateRecipe"]["ingredientSections"],
+        serde_json::json!(["Pâte"])
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_update_recipe_with_invalid_id() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  updateRecipe(id: \"not-a-uuid\", newRecipe: {title: \"my recipe\", instructions: [], ingredients: []}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    assert_eq!(body["errors"][0]["extensions"]["code"], "RECIPE_NOT_FOUND");
+
+    clean_db(&connexion).unwrap();
+}
+
 #[test]
 fn test_copy_recipe() {
     // given
@@ -386,6 +570,15 @@ fn test_copy_recipe() {
     assert_eq!(response_recipe_1.status(), Status::Ok);
     let body: Value = serde_json::from_str(&response_recipe_1.body_string().unwrap()).unwrap();
     let id: &str = &body["data"]["createRecipe"]["id"].as_str().unwrap();
+    client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  setRecipeVisibility(id: \"{id}\", visibility: PUBLIC) {{ id }} }}"}}"#,
+            id = id
+        ))
+        .dispatch();
 
     // when
     let mut response = client
@@ -412,34 +605,1019 @@ fn test_copy_recipe() {
 }
 
 #[test]
-fn test_get_photo_upload_url() {
+fn test_get_private_recipe_of_another_user() {
     // given
     let connexion = establish_connection();
     clean_db(&connexion).unwrap();
     init_with_users(&connexion).unwrap();
     let client = get_rocket_client();
+    let mut response_recipe_1 = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"my recipe\",  instructions: [\"ins1\"], ingredients: [\"ing1\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+    let body: Value = serde_json::from_str(&response_recipe_1.body_string().unwrap()).unwrap();
+    let id: &str = &body["data"]["createRecipe"]["id"].as_str().unwrap();
 
     // when
-    let mut response = client
+    let mut response_get = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_2())
+        .body(format!(
+            r#"{{"query":"{{  getRecipe(id: \"{id}\") {{ title }} }}"}}"#,
+            id = id
+        ))
+        .dispatch();
+    let mut response_copy = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_2())
+        .body(format!(
+            r#"{{"query":"mutation {{  copyRecipe(recipeId: \"{id}\") {{ title }} }}"}}"#,
+            id = id
+        ))
+        .dispatch();
+
+    // then
+    let body: Value = serde_json::from_str(&response_get.body_string().unwrap()).unwrap();
+    assert_eq!(body["errors"][0]["extensions"]["code"], "RECIPE_NOT_FOUND");
+    let body: Value = serde_json::from_str(&response_copy.body_string().unwrap()).unwrap();
+    assert_eq!(body["errors"][0]["extensions"]["code"], "RECIPE_NOT_FOUND");
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_unlisted_recipe_without_being_logged() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let mut response_recipe_1 = client
         .post("/graphql")
         .header(ContentType::JSON)
         .header(get_auth_user_1())
-        .body(r#"{"variables":{"extension":"jpeg"},"query":"mutation ($extension: String!) {\n  getPhotoUploadUrl(extension: $extension)\n}\n"}"#)
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"my recipe\",  instructions: [\"ins1\"], ingredients: [\"ing1\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+    let body: Value = serde_json::from_str(&response_recipe_1.body_string().unwrap()).unwrap();
+    let id: &str = &body["data"]["createRecipe"]["id"].as_str().unwrap();
+    let mut response_visibility = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  setRecipeVisibility(id: \"{id}\", visibility: UNLISTED) {{ visibility }} }}"}}"#,
+            id = id
+        ))
+        .dispatch();
+    assert_eq!(
+        response_visibility.body_string(),
+        Some(String::from(
+            "{\"data\":{\"setRecipeVisibility\":{\"visibility\":\"UNLISTED\"}}}"
+        ))
+    );
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .body(format!(
+            r#"{{"query":"{{  getRecipe(id: \"{id}\") {{ title }} }}"}}"#,
+            id = id
+        ))
         .dispatch();
 
     // then
     assert_eq!(response.status(), Status::Ok);
-    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(
+        response.body_string(),
+        Some(String::from(
+            "{\"data\":{\"getRecipe\":{\"title\":\"my recipe\"}}}"
+        ))
+    );
 
-    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
-    assert!(body["data"]["getPhotoUploadUrl"]
-        .as_str()
-        .unwrap()
-        .starts_with("https://rustipe-photos-test.s3-eu-west-3.amazonaws.com/"),);
-    assert!(body["data"]["getPhotoUploadUrl"]
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_share_recipe_as_editor() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let mut response_recipe_1 = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"my recipe\",  instructions: [\"ins1\"], ingredients: [\"ing1\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+    let body: Value = serde_json::from_str(&response_recipe_1.body_string().unwrap()).unwrap();
+    let id: &str = &body["data"]["createRecipe"]["id"].as_str().unwrap();
+    let mut response_share = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  shareRecipe(id: \"{id}\", email: \"email2\", role: EDITOR) {{ email role }} }}"}}"#,
+            id = id
+        ))
+        .dispatch();
+    assert_eq!(
+        response_share.body_string(),
+        Some(String::from(
+            "{\"data\":{\"shareRecipe\":[{\"email\":\"email2\",\"role\":\"EDITOR\"}]}}"
+        ))
+    );
+
+    // when
+    let mut response_update = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_2())
+        .body(format!(
+            r#"{{"query":"mutation {{  updateRecipe(id: \"{id}\", newRecipe: {{ title: \"our recipe\", instructions: [\"ins1\"], ingredients: [\"ing1\"]}}) {{ userId title }} }}"}}"#,
+            id = id
+        ))
+        .dispatch();
+    let mut response_shared = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_2())
+        .body(r#"{"query":"{  getMyRecipes(sharedWithMe: true) { title } }"}"#)
+        .dispatch();
+    let mut response_delete = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_2())
+        .body(format!(
+            r#"{{"query":"mutation {{  deleteRecipe(id: \"{id}\") }}"}}"#,
+            id = id
+        ))
+        .dispatch();
+
+    // then
+    assert_eq!(
+        response_update.body_string(),
+        Some(String::from(
+            "{\"data\":{\"updateRecipe\":{\"userId\":\"2f0194af-66e6-43f5-8e1a-2e836c9e44a8\",\"title\":\"our recipe\"}}}"
+        ))
+    );
+    assert_eq!(
+        response_shared.body_string(),
+        Some(String::from(
+            "{\"data\":{\"getMyRecipes\":[{\"title\":\"our recipe\"}]}}"
+        ))
+    );
+    let body: Value = serde_json::from_str(&response_delete.body_string().unwrap()).unwrap();
+    assert_eq!(body["errors"][0]["extensions"]["code"], "RECIPE_NOT_YOURS");
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_search_recipes() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"Blanquette\", description: \"Un classique\", instructions: [\"Couper les veaux en morceaux\"], ingredients: [\"800 g de veau\", \"2 carottes\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+    client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"Roast chicken\", instructions: [\"Roast the chicken with carrots\"], ingredients: [\"1 chicken\", \"3 carrots\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+
+    // when
+    let mut response_veau = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{  searchRecipes(query: \"veau\") { recipe { title } snippet } }"}"#)
+        .dispatch();
+    let mut response_carrot = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{  searchRecipes(query: \"carrot\") { recipe { title } } }"}"#)
+        .dispatch();
+
+    // then
+    let body: Value = serde_json::from_str(&response_veau.body_string().unwrap()).unwrap();
+    let results = body["data"]["searchRecipes"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["recipe"]["title"], "Blanquette");
+    assert!(results[0]["snippet"]
         .as_str()
         .unwrap()
-        .contains(".jpeg"),);
+        .contains("<mark>veau</mark>"));
+    let body: Value = serde_json::from_str(&response_carrot.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["searchRecipes"],
+        serde_json::json!([{ "recipe": { "title": "Roast chicken" } }])
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_search_recipes_escapes_snippet() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    post_with_variables(
+        &client,
+        get_auth_user_1(),
+        "mutation ($description: String) {\n  createRecipe(newRecipe: {title: \"Blanquette\", description: $description, instructions: [\"ins1\"], ingredients: [\"800 g de veau\"]}) {\n    id\n  }\n}\n",
+        serde_json::json!({ "description": "<img src=x onerror=alert(1)> Veau & carottes" }),
+    );
+
+    // when
+    let body = post_with_variables(
+        &client,
+        get_auth_user_1(),
+        "{  searchRecipes(query: \"veau\") { snippet } }",
+        serde_json::json!({}),
+    );
+
+    // then
+    assert_eq!(
+        body["data"]["searchRecipes"][0]["snippet"],
+        "Blanquette &lt;img src=x onerror=alert(1)&gt; <mark>Veau</mark> &amp; carottes 800 g de <mark>veau</mark> ins1"
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_recipes_page_sorted_by_total_time() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    for new_recipe in &[
+        r#"title: \"Soupe\", cookTimeInMinute: 30"#,
+        r#"title: \"Tarte\", prepTimeInMinute: 20, cookTimeInMinute: 45"#,
+        r#"title: \"Salade\", prepTimeInMinute: 10"#,
+    ] {
+        client
+            .post("/graphql")
+            .header(ContentType::JSON)
+            .header(get_auth_user_1())
+            .body(format!(
+                r#"{{"query":"mutation {{ createRecipe(newRecipe: {{{}, instructions: [], ingredients: []}}) {{ id }} }}"}}"#,
+                new_recipe
+            ))
+            .dispatch();
+    }
+
+    // when
+    let mut response_first_page = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{ getMyRecipesConnection(first: 2, sort: {field: TOTAL_TIME}) { edges { node { title } } pageInfo { hasNextPage endCursor } totalCount } }"}"#)
+        .dispatch();
+    let body: Value = serde_json::from_str(&response_first_page.body_string().unwrap()).unwrap();
+    let first_page = &body["data"]["getMyRecipesConnection"];
+    let mut response_second_page = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"{{ getMyRecipesConnection(first: 2, after: \"{}\", sort: {{field: TOTAL_TIME}}) {{ edges {{ node {{ title }} }} pageInfo {{ hasNextPage hasPreviousPage }} }} }}"}}"#,
+            first_page["pageInfo"]["endCursor"].as_str().unwrap()
+        ))
+        .dispatch();
+    let mut response_bad_cursor = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{ getMyRecipesConnection(after: \"nope\") { totalCount } }"}"#)
+        .dispatch();
+
+    // then
+    assert_eq!(
+        first_page["edges"],
+        serde_json::json!([{ "node": { "title": "Salade" } }, { "node": { "title": "Soupe" } }])
+    );
+    assert_eq!(first_page["pageInfo"]["hasNextPage"], true);
+    assert_eq!(first_page["totalCount"], 3);
+    let body: Value = serde_json::from_str(&response_second_page.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["getMyRecipesConnection"],
+        serde_json::json!({
+            "edges": [{ "node": { "title": "Tarte" } }],
+            "pageInfo": { "hasNextPage": false, "hasPreviousPage": true }
+        })
+    );
+    let body: Value = serde_json::from_str(&response_bad_cursor.body_string().unwrap()).unwrap();
+    assert_eq!(body["errors"][0]["extensions"]["code"], "INVALID_CURSOR");
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_recipes_page_with_the_same_title() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let mut ids = vec![
+        create_recipe(&client, "Soupe"),
+        create_recipe(&client, "soupe"),
+        create_recipe(&client, "Soupe"),
+    ];
+    ids.sort();
+    let query = "query($after: String) { getMyRecipesConnection(first: 1, after: $after, sort: {field: TITLE}) { edges { node { id } } pageInfo { hasNextPage endCursor } } }";
+
+    // when
+    let mut page_ids = Vec::new();
+    let mut after = Value::Null;
+    loop {
+        let body = post_with_variables(
+            &client,
+            get_auth_user_1(),
+            query,
+            serde_json::json!({ "after": after }),
+        );
+        let page = &body["data"]["getMyRecipesConnection"];
+        for edge in page["edges"].as_array().unwrap() {
+            page_ids.push(String::from(edge["node"]["id"].as_str().unwrap()));
+        }
+        if page["pageInfo"]["hasNextPage"] != true {
+            break;
+        }
+        after = page["pageInfo"]["endCursor"].clone();
+    }
+
+    // then
+    assert_eq!(page_ids, ids);
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_recipes_with_facets() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    for new_recipe in &[
+        r#"title: \"Crêpes\", category: \"Dessert\", cuisine: \"Française\", cookTimeInMinute: 20, imageUrl: \"https://img/crepes.jpg\""#,
+        r#"title: \"Tiramisu\", category: \"Dessert\", cuisine: \"Italienne\", prepTimeInMinute: 30, cookTimeInMinute: 10"#,
+        r#"title: \"Clafoutis\", category: \"Dessert\", cuisine: \"Française\""#,
+        r#"title: \"Risotto\", category: \"Plat \", cuisine: \" Italienne\", cookTimeInMinute: 25"#,
+    ] {
+        client
+            .post("/graphql")
+            .header(ContentType::JSON)
+            .header(get_auth_user_1())
+            .body(format!(
+                r#"{{"query":"mutation {{ createRecipe(newRecipe: {{{}, instructions: [], ingredients: []}}) {{ id }} }}"}}"#,
+                new_recipe
+            ))
+            .dispatch();
+    }
+
+    // when
+    let mut response_quick_desserts = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{ getMyRecipes(filter: {categories: [\"Dessert\"], maxTotalTimeInMinute: 40}) { title } }"}"#)
+        .dispatch();
+    let mut response_with_image = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{ getMyRecipes(filter: {hasImage: true}) { title } }"}"#)
+        .dispatch();
+    let mut response_facets = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{ getMyRecipeFacets(filter: {cuisines: [\"Italienne \"]}) { categories { value count } cuisines { value count } } }"}"#)
+        .dispatch();
+
+    // then
+    let body: Value =
+        serde_json::from_str(&response_quick_desserts.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["getMyRecipes"],
+        serde_json::json!([{ "title": "Crêpes" }, { "title": "Tiramisu" }])
+    );
+    let body: Value = serde_json::from_str(&response_with_image.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["getMyRecipes"],
+        serde_json::json!([{ "title": "Crêpes" }])
+    );
+    let body: Value = serde_json::from_str(&response_facets.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["getMyRecipeFacets"],
+        serde_json::json!({
+            "categories": [{ "value": "Dessert", "count": 1 }, { "value": "Plat", "count": 1 }],
+            "cuisines": [{ "value": "Française", "count": 2 }, { "value": "Italienne", "count": 2 }]
+        })
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_photo_upload_url() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"variables":{"extension":"jpeg"},"query":"mutation ($extension: String!) {\n  getPhotoUploadUrl(extension: $extension)\n}\n"}"#)
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    assert!(body["data"]["getPhotoUploadUrl"]
+        .as_str()
+        .unwrap()
+        .starts_with("https://rustipe-photos-test.s3-eu-west-3.amazonaws.com/"),);
+    assert!(body["data"]["getPhotoUploadUrl"]
+        .as_str()
+        .unwrap()
+        .contains(".jpeg"),);
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_import_recipe_from_html() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let html = r#"<html><head><script type="application/ld+json">{"@type": "Recipe", "name": "Soupe de poireaux", "recipeIngredient": ["2 poireaux", "1 l d'eau"], "recipeInstructions": ["Couper les poireaux.", "Cuire 20 min."]}</script></head></html>"#;
+    let request = serde_json::json!({
+        "variables": { "html": html, "sourceUrl": "https://example.com/soupe" },
+        "query": "mutation ($html: String!, $sourceUrl: String) {\n  importRecipeFromHtml(html: $html, sourceUrl: $sourceUrl) {\n    title\n    ingredients\n    instructions\n    importedFrom\n  }\n}\n"
+    });
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(request.to_string())
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["importRecipeFromHtml"]["title"],
+        "Soupe de poireaux"
+    );
+    assert_eq!(
+        body["data"]["importRecipeFromHtml"]["ingredients"],
+        serde_json::json!(["2 poireaux", "1 l d'eau"])
+    );
+    assert_eq!(
+        body["data"]["importRecipeFromHtml"]["importedFrom"],
+        "https://example.com/soupe"
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_structured_ingredients() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let request = serde_json::json!({
+        "query": "mutation {\n  createRecipe(newRecipe: {title: \"cake\", instructions: [\"ins1\"], ingredients: [\"150 g de beurre en dés\", \"1 1/2 cups flour\"]}) {\n    structuredIngredients {\n      text\n      quantity\n      unit\n      name\n      note\n    }\n  }\n}\n"
+    });
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(request.to_string())
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["createRecipe"]["structuredIngredients"],
+        serde_json::json!([
+            {"text": "150 g de beurre en dés", "quantity": 150.0, "unit": "g", "name": "beurre", "note": "en dés"},
+            {"text": "1 1/2 cups flour", "quantity": 1.5, "unit": "cup", "name": "flour", "note": null}
+        ])
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_scaled_recipe() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let mut response_recipe_1 = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"crêpes\", recipeYield: \"4 personnes\", instructions: [\"ins1\"], ingredients: [\"250 g de farine\", \"Sel\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+    let body: Value = serde_json::from_str(&response_recipe_1.body_string().unwrap()).unwrap();
+    let id: &str = &body["data"]["createRecipe"]["id"].as_str().unwrap();
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"{{  getRecipe(id: \"{id}\", servings: 8) {{ scaling {{ factor ingredients {{ text scaled }} }} }} }}"}}"#,
+            id = id
+        ))
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["getRecipe"]["scaling"],
+        serde_json::json!({
+            "factor": 2.0,
+            "ingredients": [
+                {"text": "500 g de farine", "scaled": true},
+                {"text": "Sel", "scaled": false}
+            ]
+        })
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_recipes_in_preferred_unit_system() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"gâteau\", instructions: [\"Cuire à 180°C.\"], ingredients: [\"200 g de sucre\", \"3 oeufs\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+    let mut response_unit_system = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  setUnitSystem(unitSystem: IMPERIAL)\n}\n"}"#)
+        .dispatch();
+    assert_eq!(
+        response_unit_system.body_string(),
+        Some(String::from("{\"data\":{\"setUnitSystem\":\"IMPERIAL\"}}"))
+    );
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{\n  getMyRecipes {\n    conversion {\n      unitSystem\n      ingredients {\n        text\n        converted\n      }\n      instructions\n    }\n  }\n}\n"}"#)
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    assert_eq!(
+        body["data"]["getMyRecipes"][0]["conversion"],
+        serde_json::json!({
+            "unitSystem": "IMPERIAL",
+            "ingredients": [
+                {"text": "1 cup de sucre", "converted": true},
+                {"text": "3 oeufs", "converted": false}
+            ],
+            "instructions": ["Cuire à 355°F."]
+        })
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_get_recipes_with_tags() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"pasta bolognese\", instructions: [\"ins1\"], ingredients: [\"ing1\"], tags: [\"Italian\", \"weeknight\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+    let mut response_recipe = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"mutation {\n  createRecipe(newRecipe: {title: \"pasta carbonara\", instructions: [\"ins1\"], ingredients: [\"ing1\"], tags: [\"italian\"]}) {\n    id\n  }\n}\n"}"#)
+        .dispatch();
+    let body: Value = serde_json::from_str(&response_recipe.body_string().unwrap()).unwrap();
+    let id: &str = &body["data"]["createRecipe"]["id"].as_str().unwrap();
+    let mut response_tag = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  addTag(id: \"{id}\", tag: \" Weeknight \") {{ tags }} }}"}}"#,
+            id = id
+        ))
+        .dispatch();
+    assert_eq!(
+        response_tag.body_string(),
+        Some(String::from(
+            "{\"data\":{\"addTag\":{\"tags\":[\"italian\",\"weeknight\"]}}}"
+        ))
+    );
+
+    // when
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(r#"{"query":"{\n  getMyRecipes(query: \"pasta c\", tags: [\"italian\", \"weeknight\"]) {\n    title\n    tags\n  }\n}\n"}"#)
+        .dispatch();
+
+    // then
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.body_string(),
+        Some(String::from(
+            "{\"data\":{\"getMyRecipes\":[{\"title\":\"pasta carbonara\",\"tags\":[\"italian\",\"weeknight\"]}]}}"
+        ))
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+const SOUP_URL: &str = "https://example.com/soupe";
+const RESYNC_QUERY: &str = "mutation ($id: String!) {\n  resyncRecipe(id: $id, apply: true) {\n    recipe { title description }\n    changes { field current imported locallyEdited }\n  }\n}\n";
+
+fn import_soup(client: &Client, fetcher: &StubFetcher) -> String {
+    fetcher.set_page(
+        SOUP_URL,
+        &get_recipe_page(serde_json::json!({
+            "@type": "Recipe",
+            "name": "Soupe de poireaux",
+            "description": "Une soupe d'hiver.",
+            "recipeIngredient": ["2 poireaux", "1 l d'eau"],
+            "recipeInstructions": ["Couper les poireaux.", "Cuire 20 min."]
+        })),
+    );
+    let body = post_with_variables(
+        client,
+        get_auth_user_1(),
+        "mutation ($url: String!) {\n  importRecipe(url: $url) {\n    id\n  }\n}\n",
+        serde_json::json!({ "url": SOUP_URL }),
+    );
+    String::from(body["data"]["importRecipe"]["id"].as_str().unwrap())
+}
+
+fn update_soup_page(fetcher: &StubFetcher) {
+    fetcher.set_page(
+        SOUP_URL,
+        &get_recipe_page(serde_json::json!({
+            "@type": "Recipe",
+            "name": "Soupe poireaux pommes de terre",
+            "description": "Une soupe d'hiver, plus épaisse.",
+            "recipeIngredient": ["2 poireaux", "1 l d'eau"],
+            "recipeInstructions": ["Couper les poireaux.", "Cuire 20 min."]
+        })),
+    );
+}
+
+#[test]
+fn test_resync_recipe_keeps_local_edits() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let fetcher = StubFetcher::default();
+    let client = get_rocket_client_with_fetcher(fetcher.clone());
+    let id = import_soup(&client, &fetcher);
+    post_with_variables(
+        &client,
+        get_auth_user_1(),
+        "mutation ($id: String!, $url: String) {\n  updateRecipe(id: $id, newRecipe: {title: \"Ma soupe\", description: \"Une soupe d'hiver.\", instructions: [\"Couper les poireaux.\", \"Cuire 20 min.\"], ingredients: [\"2 poireaux\", \"1 l d'eau\"], importedFrom: $url}) {\n    id\n  }\n}\n",
+        serde_json::json!({ "id": id, "url": SOUP_URL }),
+    );
+    update_soup_page(&fetcher);
+
+    // when
+    let body = post_with_variables(
+        &client,
+        get_auth_user_1(),
+        RESYNC_QUERY,
+        serde_json::json!({ "id": id }),
+    );
+
+    // then
+    assert_eq!(
+        body["data"]["resyncRecipe"],
+        serde_json::json!({
+            "recipe": {"title": "Ma soupe", "description": "Une soupe d'hiver, plus épaisse."},
+            "changes": [
+                {"field": "title", "current": "Ma soupe", "imported": "Soupe poireaux pommes de terre", "locallyEdited": true},
+                {"field": "description", "current": "Une soupe d'hiver.", "imported": "Une soupe d'hiver, plus épaisse.", "locallyEdited": false}
+            ]
+        })
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_resync_recipe_without_snapshot_keeps_every_field() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let fetcher = StubFetcher::default();
+    let client = get_rocket_client_with_fetcher(fetcher.clone());
+    let id = import_soup(&client, &fetcher);
+    diesel::delete(recipe_import_snapshots::table)
+        .execute(&connexion)
+        .unwrap();
+    update_soup_page(&fetcher);
+
+    // when
+    let body = post_with_variables(
+        &client,
+        get_auth_user_1(),
+        RESYNC_QUERY,
+        serde_json::json!({ "id": id }),
+    );
+
+    // then
+    assert_eq!(
+        body["data"]["resyncRecipe"],
+        serde_json::json!({
+            "recipe": {"title": "Soupe de poireaux", "description": "Une soupe d'hiver."},
+            "changes": [
+                {"field": "title", "current": "Soupe de poireaux", "imported": "Soupe poireaux pommes de terre", "locallyEdited": true},
+                {"field": "description", "current": "Une soupe d'hiver.", "imported": "Une soupe d'hiver, plus épaisse.", "locallyEdited": true}
+            ]
+        })
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+const PREVIEW_QUERY: &str =
+    "query ($url: String!) {\n  previewImport(url: $url) {\n    title\n    ingredients\n  }\n}\n";
+
+#[test]
+fn test_preview_import_saves_nothing() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let fetcher = StubFetcher::default();
+    fetcher.set_page(
+        SOUP_URL,
+        &get_recipe_page(serde_json::json!({
+            "@type": "Recipe",
+            "name": "Soupe de poireaux",
+            "recipeIngredient": ["2 poireaux", "1 l d'eau"],
+            "recipeInstructions": ["Couper les poireaux.", "Cuire 20 min."]
+        })),
+    );
+    let client = get_rocket_client_with_fetcher(fetcher);
+
+    // when
+    let body = post_with_variables(
+        &client,
+        get_auth_user_1(),
+        PREVIEW_QUERY,
+        serde_json::json!({ "url": SOUP_URL }),
+    );
+
+    // then
+    assert_eq!(
+        body["data"]["previewImport"],
+        serde_json::json!({"title": "Soupe de poireaux", "ingredients": ["2 poireaux", "1 l d'eau"]})
+    );
+    let recipe_count: i64 = recipes::table.count().get_result(&connexion).unwrap();
+    let snapshot_count: i64 = recipe_import_snapshots::table
+        .count()
+        .get_result(&connexion)
+        .unwrap();
+    assert_eq!((recipe_count, snapshot_count), (0, 0));
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_preview_import_errors_have_codes() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let fetcher = StubFetcher::default();
+    fetcher.set_page(SOUP_URL, "<html><body>Pas de recette ici</body></html>");
+    let client = get_rocket_client_with_fetcher(fetcher);
+    let urls = vec![
+        (SOUP_URL, "WEBSITE_NOT_SUPPORTED"),
+        ("https://example.com/unreachable", "WEBSITE_UNREACHABLE"),
+    ];
+
+    for (url, code) in urls {
+        // when
+        let body = post_with_variables(
+            &client,
+            get_auth_user_1(),
+            PREVIEW_QUERY,
+            serde_json::json!({ "url": url }),
+        );
+
+        // then
+        assert_eq!(body["data"], Value::Null);
+        assert_eq!(body["errors"][0]["extensions"]["code"], code);
+    }
+
+    clean_db(&connexion).unwrap();
+}
+
+const SHARE_QUERY: &str = "mutation ($id: String!, $email: String!, $role: ShareRoleGraphQL!) {\n  shareRecipe(id: $id, email: $email, role: $role) {\n    email\n    role\n  }\n}\n";
+
+#[test]
+fn test_share_recipe_with_unknown_email() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let id = create_recipe(&client, "my recipe");
+
+    // when
+    let body = post_with_variables(
+        &client,
+        get_auth_user_1(),
+        SHARE_QUERY,
+        serde_json::json!({ "id": id, "email": "nobody@example.com", "role": "EDITOR" }),
+    );
+
+    // then
+    assert_eq!(body, serde_json::json!({"data": {"shareRecipe": []}}));
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_tag_recipe_shared_with_editor_only() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let id = create_recipe(&client, "my recipe");
+    let add_tag =
+        "mutation ($id: String!) {\n  addTag(id: $id, tag: \"dessert\") {\n    tags\n  }\n}\n";
+    post_with_variables(
+        &client,
+        get_auth_user_1(),
+        SHARE_QUERY,
+        serde_json::json!({ "id": id, "email": "email2", "role": "VIEWER" }),
+    );
+    let body_viewer = post_with_variables(
+        &client,
+        get_auth_user_2(),
+        add_tag,
+        serde_json::json!({ "id": id }),
+    );
+    post_with_variables(
+        &client,
+        get_auth_user_1(),
+        SHARE_QUERY,
+        serde_json::json!({ "id": id, "email": "email2", "role": "EDITOR" }),
+    );
+
+    // when
+    let body_editor = post_with_variables(
+        &client,
+        get_auth_user_2(),
+        add_tag,
+        serde_json::json!({ "id": id }),
+    );
+
+    // then
+    assert_eq!(
+        body_viewer["errors"][0]["extensions"]["code"],
+        "RECIPE_NOT_YOURS"
+    );
+    assert_eq!(
+        body_editor,
+        serde_json::json!({"data": {"addTag": {"tags": ["dessert"]}}})
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_delete_recipe_planned_by_another_user() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let id = create_recipe(&client, "my recipe");
+    let add_entry = "mutation ($id: String!) {\n  addMealPlanEntry(recipeId: $id, date: \"2026-03-02\", slot: DINNER) {\n    id\n  }\n}\n";
+    let delete = "mutation ($id: String!) {\n  deleteRecipe(id: $id)\n}\n";
+    post_with_variables(
+        &client,
+        get_auth_user_1(),
+        SHARE_QUERY,
+        serde_json::json!({ "id": id, "email": "email2", "role": "VIEWER" }),
+    );
+    post_with_variables(
+        &client,
+        get_auth_user_1(),
+        add_entry,
+        serde_json::json!({ "id": id }),
+    );
+    let entry = post_with_variables(
+        &client,
+        get_auth_user_2(),
+        add_entry,
+        serde_json::json!({ "id": id }),
+    );
+
+    // when
+    let body_planned = post_with_variables(
+        &client,
+        get_auth_user_1(),
+        delete,
+        serde_json::json!({ "id": id }),
+    );
+    post_with_variables(
+        &client,
+        get_auth_user_2(),
+        "mutation ($id: String!) {\n  removeMealPlanEntry(id: $id)\n}\n",
+        serde_json::json!({ "id": entry["data"]["addMealPlanEntry"]["id"] }),
+    );
+    let body_unplanned = post_with_variables(
+        &client,
+        get_auth_user_1(),
+        delete,
+        serde_json::json!({ "id": id }),
+    );
+
+    // then
+    assert_eq!(
+        body_planned["errors"][0]["extensions"]["code"],
+        "RECIPE_PLANNED_BY_OTHERS"
+    );
+    assert_eq!(
+        body_unplanned,
+        serde_json::json!({"data": {"deleteRecipe": id}})
+    );
 
     clean_db(&connexion).unwrap();
 }
@@ -0,0 +1,345 @@
+extern crate recipes_backend;
+extern crate serde_json;
+
+use self::recipes_backend::domain::users::models::user::User;
+use self::recipes_backend::infrastructure::web::jwt::generate_header;
+use self::recipes_backend::infrastructure::web::server;
+use rocket::http::Header;
+
+use diesel::pg::PgConnection;
+use diesel::Connection;
+use diesel::RunQueryDsl;
+use rocket::http::{ContentType, Status};
+use rocket::local::Client;
+use serde_json::Value;
+use std::error::Error;
+use uuid::Uuid;
+
+use dotenv::dotenv;
+use std::env;
+
+fn get_database_url() -> String {
+    String::from(
+        env::var("DATABASE_URL")
+            .or_else(|_e| {
+                dotenv().ok();
+                env::var("DATABASE_URL")
+            })
+            .expect("DATABASE_URL must be set"),
+    )
+}
+
+fn get_rocket_client() -> Client {
+    env::set_var("JWT_SECRET", "SECRET");
+    env::set_var("BUCKET_NAME", "rustipe-photos-test");
+    env::set_var(
+        "ROCKET_DATABASE_master",
+        "{ url = \"postgres://localhost/rustipe-test\", pool_size = 1 }",
+    );
+    Client::new(server::get_server()).expect("valid rocket instance")
+}
+
+pub fn establish_connection() -> PgConnection {
+    let database_url = get_database_url();
+    PgConnection::establish(&database_url).expect(&format!("Error connecting to {}", database_url))
+}
+
+fn clean_db(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::schema::collection_recipes::dsl::collection_recipes;
+    use self::recipes_backend::infrastructure::sql::schema::collections::dsl::collections;
+    use self::recipes_backend::infrastructure::sql::schema::household_invitations::dsl::household_invitations;
+    use self::recipes_backend::infrastructure::sql::schema::households::dsl::households;
+    use self::recipes_backend::infrastructure::sql::schema::ingredients::dsl::ingredients;
+    use self::recipes_backend::infrastructure::sql::schema::instructions::dsl::instructions;
+    use self::recipes_backend::infrastructure::sql::schema::meal_plan_entries::dsl::meal_plan_entries;
+    use self::recipes_backend::infrastructure::sql::schema::pantry_items::dsl::pantry_items;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_import_snapshots::dsl::recipe_import_snapshots;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_shares::dsl::recipe_shares;
+    use self::recipes_backend::infrastructure::sql::schema::recipe_tags::dsl::recipe_tags;
+    use self::recipes_backend::infrastructure::sql::schema::recipes::dsl::recipes;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_list_items::dsl::shopping_list_items;
+    use self::recipes_backend::infrastructure::sql::schema::shopping_lists::dsl::shopping_lists;
+    use self::recipes_backend::infrastructure::sql::schema::users::dsl::users;
+
+    diesel::delete(pantry_items).execute(connexion)?;
+    diesel::delete(shopping_list_items).execute(connexion)?;
+    diesel::delete(shopping_lists).execute(connexion)?;
+    diesel::delete(meal_plan_entries).execute(connexion)?;
+    diesel::delete(collection_recipes).execute(connexion)?;
+    diesel::delete(collections).execute(connexion)?;
+    diesel::delete(recipe_import_snapshots).execute(connexion)?;
+    diesel::delete(recipe_shares).execute(connexion)?;
+    diesel::delete(recipe_tags).execute(connexion)?;
+    diesel::delete(ingredients).execute(connexion)?;
+    diesel::delete(instructions).execute(connexion)?;
+    diesel::delete(recipes).execute(connexion)?;
+    diesel::delete(users).execute(connexion)?;
+    diesel::delete(household_invitations).execute(connexion)?;
+    diesel::delete(households).execute(connexion)?;
+    Ok(())
+}
+
+fn init_with_users(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::models::*;
+    use self::recipes_backend::infrastructure::sql::schema::users;
+
+    let new_user_1 = NewUser {
+        id: "2f0194af-66e6-43f5-8e1a-2e836c9e44a8",
+        email: "email1",
+        password_hash: "password",
+    };
+    let new_user_2 = NewUser {
+        id: "2f0194af-66e6-43f5-8e1a-2e836c9e44a7",
+        email: "email2",
+        password_hash: "password",
+    };
+
+    diesel::insert_into(users::table)
+        .values(&vec![new_user_1, new_user_2])
+        .get_result::<User>(connexion)
+        .unwrap();
+    Ok(())
+}
+
+fn get_auth_user_1<'a>() -> Header<'a> {
+    let u = User {
+        id: Uuid::parse_str("2f0194af-66e6-43f5-8e1a-2e836c9e44a8").expect("Cannot parse UUID"),
+        email: String::from("email1"),
+    };
+    let token = generate_header(u).unwrap();
+
+    let mut value = String::from("Bearer ");
+    value.push_str(&token);
+    Header::new("Authorization", value)
+}
+
+fn get_auth_user_2<'a>() -> Header<'a> {
+    let u = User {
+        id: Uuid::parse_str("2f0194af-66e6-43f5-8e1a-2e836c9e44a7").expect("Cannot parse UUID"),
+        email: String::from("email2"),
+    };
+    let token = generate_header(u).unwrap();
+
+    let mut value = String::from("Bearer ");
+    value.push_str(&token);
+    Header::new("Authorization", value)
+}
+
+fn create_recipe(client: &Client, title: &str, recipe_yield: &str, ingredients: &[&str]) -> String {
+    let ingredients: Vec<String> = ingredients
+        .iter()
+        .map(|i| format!(r#"\"{}\""#, i))
+        .collect();
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(get_auth_user_1())
+        .body(format!(
+            r#"{{"query":"mutation {{  createRecipe(newRecipe: {{title: \"{title}\", recipeYield: \"{recipe_yield}\", instructions: [\"ins1\"], ingredients: [{ingredients}]}}) {{ id }} }}"}}"#,
+            title = title,
+            recipe_yield = recipe_yield,
+            ingredients = ingredients.join(", ")
+        ))
+        .dispatch();
+    let body: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+    String::from(body["data"]["createRecipe"]["id"].as_str().unwrap())
+}
+
+fn post(client: &Client, auth: Header<'static>, query: String) -> Value {
+    let mut response = client
+        .post("/graphql")
+        .header(ContentType::JSON)
+        .header(auth)
+        .body(format!(r#"{{"query":"{query}"}}"#, query = query))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.body_string().unwrap()).unwrap()
+}
+
+#[test]
+fn test_create_shopping_list_and_check_items() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let soup_id = create_recipe(
+        &client,
+        "soup",
+        "2 personnes",
+        &["200 g de carottes", "1 oignon", "1 l de lait", "Sel"],
+    );
+    let salad_id = create_recipe(
+        &client,
+        "salad",
+        "4 personnes",
+        &["100 g de carottes", "sel", "Un peu d'amour"],
+    );
+
+    // when
+    let created = post(
+        &client,
+        get_auth_user_1(),
+        format!(
+            r#"mutation {{  createShoppingList(name: \"Week\", recipes: [{{recipeId: \"{soup_id}\", servings: 4}}, {{recipeId: \"{salad_id}\"}}]) {{ id name aisles {{ aisle items {{ id text }} }} }} }}"#,
+            soup_id = soup_id,
+            salad_id = salad_id
+        ),
+    );
+    let shopping_list = &created["data"]["createShoppingList"];
+    let id = shopping_list["id"].as_str().unwrap();
+    let item_id = shopping_list["aisles"][0]["items"][0]["id"]
+        .as_str()
+        .unwrap();
+    post(
+        &client,
+        get_auth_user_1(),
+        format!(
+            r#"mutation {{  checkShoppingListItem(id: \"{id}\", itemId: \"{item_id}\", checked: true) {{ id }} }}"#,
+            id = id,
+            item_id = item_id
+        ),
+    );
+    let shopping_lists = post(
+        &client,
+        get_auth_user_1(),
+        String::from(r#"{  getMyShoppingLists { name items { text aisle checked } } }"#),
+    );
+
+    // then
+    assert_eq!(shopping_list["name"], "Week");
+    assert_eq!(
+        shopping_lists["data"]["getMyShoppingLists"],
+        serde_json::json!([{
+            "name": "Week",
+            "items": [
+                { "text": "500 g carottes", "aisle": "PRODUCE", "checked": true },
+                { "text": "2 oignon", "aisle": "PRODUCE", "checked": false },
+                { "text": "2 l lait", "aisle": "DAIRY", "checked": false },
+                { "text": "Sel", "aisle": "SPICES", "checked": false },
+                { "text": "Un peu d'amour", "aisle": "OTHER", "checked": false }
+            ]
+        }])
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_create_shopping_list_from_meal_plan_and_of_another_user() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let recipe_id = create_recipe(&client, "soup", "2 personnes", &["2 carottes"]);
+    for date in &["2026-10-19", "2026-10-20"] {
+        post(
+            &client,
+            get_auth_user_1(),
+            format!(
+                r#"mutation {{  addMealPlanEntry(recipeId: \"{recipe_id}\", date: \"{date}\", slot: DINNER, servings: 4) {{ id }} }}"#,
+                recipe_id = recipe_id,
+                date = date
+            ),
+        );
+    }
+
+    // when
+    let created = post(
+        &client,
+        get_auth_user_1(),
+        String::from(
+            r#"mutation {  createShoppingListFromMealPlan(name: \"Week\", start: \"2026-10-19\", end: \"2026-10-25\") { id items { text } } }"#,
+        ),
+    );
+    let id = created["data"]["createShoppingListFromMealPlan"]["id"]
+        .as_str()
+        .unwrap();
+    let private_recipe = post(
+        &client,
+        get_auth_user_2(),
+        format!(
+            r#"mutation {{  createShoppingList(name: \"Mine\", recipes: [{{recipeId: \"{recipe_id}\"}}]) {{ id }} }}"#,
+            recipe_id = recipe_id
+        ),
+    );
+    let other_list = post(
+        &client,
+        get_auth_user_2(),
+        format!(
+            r#"mutation {{  deleteShoppingList(id: \"{id}\") }}"#,
+            id = id
+        ),
+    );
+
+    // then
+    assert_eq!(
+        created["data"]["createShoppingListFromMealPlan"]["items"],
+        serde_json::json!([{ "text": "8 carottes" }])
+    );
+    assert_eq!(
+        private_recipe["errors"][0]["extensions"]["code"],
+        "RECIPE_NOT_FOUND"
+    );
+    assert_eq!(
+        other_list["errors"][0]["extensions"]["code"],
+        "SHOPPING_LIST_NOT_YOURS"
+    );
+
+    clean_db(&connexion).unwrap();
+}
+
+#[test]
+fn test_create_shopping_list_from_meal_plan_skips_recipes_no_longer_shared() {
+    // given
+    let connexion = establish_connection();
+    clean_db(&connexion).unwrap();
+    init_with_users(&connexion).unwrap();
+    let client = get_rocket_client();
+    let shared_id = create_recipe(&client, "soup", "2 personnes", &["2 carottes"]);
+    let unshared_id = create_recipe(&client, "tart", "4 personnes", &["3 poires"]);
+    for recipe_id in &[&shared_id, &unshared_id] {
+        post(
+            &client,
+            get_auth_user_1(),
+            format!(
+                r#"mutation {{  shareRecipe(id: \"{recipe_id}\", email: \"email2\", role: VIEWER) {{ email }} }}"#,
+                recipe_id = recipe_id
+            ),
+        );
+        post(
+            &client,
+            get_auth_user_2(),
+            format!(
+                r#"mutation {{  addMealPlanEntry(recipeId: \"{recipe_id}\", date: \"2026-10-19\", slot: DINNER) {{ id }} }}"#,
+                recipe_id = recipe_id
+            ),
+        );
+    }
+    post(
+        &client,
+        get_auth_user_1(),
+        format!(
+            r#"mutation {{  unshareRecipe(id: \"{recipe_id}\", email: \"email2\") {{ email }} }}"#,
+            recipe_id = unshared_id
+        ),
+    );
+
+    // when
+    let created = post(
+        &client,
+        get_auth_user_2(),
+        String::from(
+            r#"mutation {  createShoppingListFromMealPlan(name: \"Week\", start: \"2026-10-19\", end: \"2026-10-25\") { items { text } } }"#,
+        ),
+    );
+
+    // then
+    assert_eq!(
+        created["data"]["createShoppingListFromMealPlan"]["items"],
+        serde_json::json!([{ "text": "2 carottes" }])
+    );
+
+    clean_db(&connexion).unwrap();
+}
@@ -36,8 +36,12 @@ pub fn establish_connection() -> PgConnection {
 }
 
 fn clean_db(connexion: &PgConnection) -> Result<(), Box<dyn Error>> {
+    use self::recipes_backend::infrastructure::sql::schema::household_invitations::dsl::household_invitations;
+    use self::recipes_backend::infrastructure::sql::schema::households::dsl::households;
     use self::recipes_backend::infrastructure::sql::schema::users::dsl::users;
     diesel::delete(users).execute(connexion)?;
+    diesel::delete(household_invitations).execute(connexion)?;
+    diesel::delete(households).execute(connexion)?;
     Ok(())
 }
 